@@ -53,6 +53,29 @@
 //!    Otherwise, return a [`error::ParseError::UnexpectedTokenError`].
 //!
 //!  - todo
+//!
+//! ## Error recovery
+//!
+//! A parser that stops at the first error only tells the user about one mistake at a time.
+//! To keep going after a rule fails, take the [`BufferedLexer::depth`] before calling it and
+//! pass the error to [`BufferedLexer::recover`]; the error gets recorded and the lexer is put
+//! back into a usable state. Then skip the rest of the broken construct with
+//! [`BufferedLexer::skip_until`] and carry on parsing. Every recorded errors can be retrieved
+//! with [`BufferedLexer::take_errors`] once parsing is done.
+//!
+//! ## Lossless mode
+//!
+//! By default, the lexer only sees the tokens that logos emits, anything skipped (whitespaces,
+//! comments) is gone. Tools that need to rewrite the source (formatters, refactorings) can
+//! instead emit those as tokens and construct the lexer using [`BufferedLexer::new_lossless`]
+//! with the list of trivia tokens. The parser never sees them, they're attached to the
+//! significant token next to them as a [`LosslessToken`], see [`BufferedLexer::lossless_tokens`]
+//! and [`BufferedLexer::reconstruct`].
+//!
+//! ## Lines and columns
+//!
+//! Tokens and errors only carry byte offsets, [`source_map::SourceMap`] turns them into lines
+//! and columns (in UTF-8 bytes or UTF-16 code units) and excerpts snippets out of the source.
 
 use log::{info, trace};
 use logos::{Lexer, Logos, Source};
@@ -61,6 +84,8 @@ use std::fmt::Debug;
 #[cfg(test)]
 mod tests;
 
+pub mod source_map;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SpannedToken<'source, T: Debug + Clone + PartialEq> {
     pub token: T,
@@ -86,6 +111,18 @@ impl<T: Debug + Clone + PartialEq> From<SpannedToken<'_, T>> for SpannedTokenOwn
     }
 }
 
+/// A significant token along with the trivia tokens around it, collected when the lexer is in
+/// the lossless mode (see [`BufferedLexer::new_lossless`]).
+///
+/// Trivia tokens that come after a token up until (and including) the first trivia that spans
+/// a newline are its `trailing` trivia, the rest are the `leading` trivia of the next token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LosslessToken<'source, T: Debug + Clone + PartialEq> {
+    pub leading: Vec<SpannedToken<'source, T>>,
+    pub token: SpannedToken<'source, T>,
+    pub trailing: Vec<SpannedToken<'source, T>>,
+}
+
 /// [`BufferedLexer`] is a wrapper to the Logos' [`logos::Lexer`] that implements token buffering, saving contexts and restoring them.
 pub struct BufferedLexer<'source, T: Logos<'source> + Debug + Clone + PartialEq>
 where
@@ -107,6 +144,19 @@ where
     index: usize,
 
     err_tok: T,
+
+    // errors recorded while recovering
+    errors: Vec<error::ParseError<T, SpannedTokenOwned<T>>>,
+    // the error at the furthest position reached, with the expected tokens of every alternatives
+    // that failed there
+    furthest_error: Option<error::ParseError<T, SpannedTokenOwned<T>>>,
+
+    // only set on the lossless mode
+    trivia: Option<Vec<T>>,
+    lossless_tokens: Vec<LosslessToken<'source, T>>,
+    // trivia that hasn't been attached to any token yet
+    pending_trivia: Vec<SpannedToken<'source, T>>,
+    eof_trivia: Vec<SpannedToken<'source, T>>,
 }
 
 impl<'source, T> BufferedLexer<'source, T>
@@ -130,6 +180,136 @@ where
             blacklist: vec![],
             index: 0,
             err_tok,
+            errors: Vec::new(),
+            furthest_error: None,
+            trivia: None,
+            lossless_tokens: Vec::new(),
+            pending_trivia: Vec::new(),
+            eof_trivia: Vec::new(),
+        }
+    }
+
+    /// Constructs a new [`BufferedLexer`] in the lossless mode. The given `trivia` tokens will
+    /// never be returned by the lexer just as if they got skipped, but are kept and attached to
+    /// their neighbouring significant token, accessible through
+    /// [`BufferedLexer::lossless_tokens`].
+    ///
+    /// This lets the original source text to be recovered (see [`BufferedLexer::reconstruct`])
+    /// without having to change the parser.
+    pub fn new_lossless(
+        inner: Lexer<'source, T>,
+        err_tok: T,
+        trivia: Vec<T>,
+    ) -> BufferedLexer<'source, T> {
+        BufferedLexer {
+            trivia: Some(trivia),
+            ..Self::new(inner, err_tok)
+        }
+    }
+
+    /// Returns whether this lexer is constructed using [`BufferedLexer::new_lossless`]
+    pub fn is_lossless(&self) -> bool {
+        self.trivia.is_some()
+    }
+
+    /// Returns every significant tokens that have been lexed so far along with their trivia.
+    /// Always empty when the lexer is not in the lossless mode.
+    pub fn lossless_tokens(&self) -> &[LosslessToken<'source, T>] {
+        &self.lossless_tokens
+    }
+
+    /// Retrieves the lexed token at the given position (as in [`SpannedToken::pos`]) along with
+    /// its trivia. Always returns `None` when the lexer is not in the lossless mode.
+    pub fn trivia_of(&self, pos: &std::ops::Range<usize>) -> Option<&LosslessToken<'source, T>> {
+        self.lossless_tokens
+            .binary_search_by_key(&pos.start, |tok| tok.token.pos.start)
+            .ok()
+            .and_then(|index| self.lossless_tokens.get(index))
+    }
+
+    /// Returns the trivia tokens placed at the end of the source, after the last significant
+    /// token's trailing trivia. Only filled after the lexer has reached EOF.
+    pub fn eof_trivia(&self) -> &[SpannedToken<'source, T>] {
+        &self.eof_trivia
+    }
+
+    /// Reconstructs the source text of the tokens that have been lexed so far by putting the
+    /// trivia back in between them. Once the lexer has reached EOF, this will be exactly the
+    /// same as the original source (given that it has no error tokens).
+    pub fn reconstruct(&self) -> String {
+        let mut result = String::new();
+
+        for tok in &self.lossless_tokens {
+            tok.leading
+                .iter()
+                .chain(std::iter::once(&tok.token))
+                .chain(tok.trailing.iter())
+                .for_each(|tok| result.push_str(tok.slice));
+        }
+
+        self.eof_trivia
+            .iter()
+            .for_each(|tok| result.push_str(tok.slice));
+
+        result
+    }
+
+    // pulls the next token that isn't a trivia out of the inner lexer, collecting the trivia
+    // in between when we're on the lossless mode
+    fn next_inner(&mut self) -> Option<T> {
+        if self.trivia.is_none() {
+            return self.inner.next();
+        }
+
+        loop {
+            let Some(token) = self.inner.next() else {
+                self.attach_trivia(None);
+                return None;
+            };
+
+            let spanned = SpannedToken {
+                token: token.clone(),
+                slice: self.inner.slice().as_ref(),
+                pos: self.inner.span(),
+            };
+
+            if matches!(&self.trivia, Some(trivia) if trivia.contains(&token)) {
+                self.pending_trivia.push(spanned);
+                continue;
+            }
+
+            // keep the trivia for the next token when this is an error, the lexer doesn't cache it
+            if token != self.err_tok {
+                self.attach_trivia(Some(spanned));
+            }
+
+            return Some(token);
+        }
+    }
+
+    // splits the pending trivia into the trailing trivia of the previous token and the leading
+    // trivia of the next token (or the eof trivia when there aren't any)
+    fn attach_trivia(&mut self, next: Option<SpannedToken<'source, T>>) {
+        let mut pending = std::mem::take(&mut self.pending_trivia);
+
+        if let Some(previous) = self.lossless_tokens.last_mut() {
+            let line_end = pending
+                .iter()
+                .position(|tok| tok.slice.contains('\n'))
+                .map_or(pending.len(), |index| index + 1);
+
+            let leading = pending.split_off(line_end);
+            previous.trailing.append(&mut pending);
+            pending = leading;
+        }
+
+        match next {
+            Some(token) => self.lossless_tokens.push(LosslessToken {
+                leading: pending,
+                token,
+                trailing: Vec::new(),
+            }),
+            None => self.eof_trivia.append(&mut pending),
         }
     }
 
@@ -160,7 +340,7 @@ where
         // check if the next token is not already cached
         if self.index >= self.inner_index {
             // yep, this is up-to-date! go next and save it to the cache
-            let next_token = self.inner.next().ok_or_else(|| {
+            let next_token = self.next_inner().ok_or_else(|| {
                 trace!(
                     "{} * encountered eof (now {}, inner {})",
                     "  ".repeat(self.save_points.len()),
@@ -249,11 +429,14 @@ where
 
         // loop until the next token is not blacklisted
         let next = loop {
-            let next: SpannedTokenOwned<T> = self
-                .next()
-                .map_err(|err| err.map_eof_expected(|| vec![tok.clone()]))?
-                .clone()
-                .into();
+            let next: SpannedTokenOwned<T> = match self.next() {
+                Ok(next) => next.clone().into(),
+                Err(err) => {
+                    let err = err.map_eof_expected(|| vec![tok.clone()]);
+                    self.record_failure(&err);
+                    return Err(err);
+                }
+            };
 
             if !self.blacklist.contains(&next.token) {
                 break next;
@@ -276,11 +459,14 @@ where
                 tok
             );
 
-            Err(error::ParseError::UnexpectedTokenError {
+            let err = error::ParseError::UnexpectedTokenError {
                 expected: Some(vec![tok]),
                 pos: next.pos.clone(),
-                unexpected_token: next.into(),
-            })
+                unexpected_token: next,
+            };
+
+            self.record_failure(&err);
+            Err(err)
         }
     }
 
@@ -383,11 +569,14 @@ where
         self.current_save_point()
             .expect("start() must be called first");
 
-        let next: SpannedTokenOwned<T> = self
-            .next()
-            .map_err(|err| err.map_eof_expected(|| Vec::from(tokens)))?
-            .clone()
-            .into();
+        let next: SpannedTokenOwned<T> = match self.next() {
+            Ok(next) => next.clone().into(),
+            Err(err) => {
+                let err = err.map_eof_expected(|| Vec::from(tokens));
+                self.record_failure(&err);
+                return Err(err);
+            }
+        };
 
         if tokens.contains(&next.token) {
             trace!(
@@ -406,11 +595,14 @@ where
                 tokens
             );
 
-            Err(error::ParseError::UnexpectedTokenError {
+            let err = error::ParseError::UnexpectedTokenError {
                 expected: Some(Vec::from(tokens)),
                 pos: next.pos.clone(),
-                unexpected_token: next.into(),
-            })
+                unexpected_token: next,
+            };
+
+            self.record_failure(&err);
+            Err(err)
         }
     }
 
@@ -590,28 +782,219 @@ where
         self.index = state_start_point;
     }
 
-    /// Pops the current save point and deletes all the cached tokens before the current index.
+    /// Pops the current save point and deletes the cached tokens that can no longer be reached,
+    /// that is the tokens before the current index and before every remaining save points.
     pub fn success(&mut self) {
-        // prevents splitting when there are multiple success calls at the end of parsing
-        // (since there isn't any tokens left)
-        if self.index != self.cache_start_point {
-            self.cached_tokens = self
-                .cached_tokens
-                .split_off(self.index - self.cache_start_point - 1);
-        }
-
-        self.cache_start_point = if self.index != 0 { self.index - 1 } else { 0 };
-
         self.save_points
             .pop()
             .expect("Failed to pop the previous save point, is success() called after a start()?");
 
+        // the save points below can still be restored to, their tokens must be kept. one token
+        // before the current index is also kept for previous()
+        let keep_from = self
+            .save_points
+            .iter()
+            .copied()
+            .min()
+            .unwrap_or(self.index)
+            .min(self.index.saturating_sub(1));
+
+        if keep_from > self.cache_start_point {
+            self.cached_tokens
+                .drain(..keep_from - self.cache_start_point);
+            self.cache_start_point = keep_from;
+        }
+
         trace!(
             "{}<== success ({})",
             "  ".repeat(self.save_points.len() + 1),
             self.index
         );
     }
+
+    /// Returns the amount of save points the lexer currently has, take this before calling a rule
+    /// that might fail and pass it to [`BufferedLexer::recover`].
+    pub fn depth(&self) -> usize {
+        self.save_points.len()
+    }
+
+    /// Recovers from an error returned by a rule so that parsing can keep going. The error gets
+    /// recorded (see [`BufferedLexer::take_errors`]), the save points that the failed rule left
+    /// behind (the ones above `depth`) are dropped, and the token it didn't expect is put back
+    /// if it was consumed, since it may be the token to synchronize to.
+    ///
+    /// Returns the error back when it's an EOF, as there is nothing left to recover to.
+    pub fn recover(
+        &mut self,
+        depth: usize,
+        err: error::ParseError<T, SpannedTokenOwned<T>>,
+    ) -> Result<(), error::ParseError<T, SpannedTokenOwned<T>>> {
+        self.save_points.truncate(depth.max(1));
+
+        if let error::ParseError::EOF { .. } = err {
+            return Err(self.merge_expected(err));
+        }
+
+        if let error::ParseError::UnexpectedTokenError { pos, .. } = &err {
+            let consumed = self.index > self.cache_start_point
+                && self.index > *self.current_save_point().unwrap()
+                && self.cached_tokens[self.index - 1 - self.cache_start_point].pos == *pos;
+
+            if consumed {
+                self.index -= 1;
+            }
+        }
+
+        trace!(
+            "{} ! recovered from {:?} (now {})",
+            "  ".repeat(self.save_points.len()),
+            err,
+            self.index
+        );
+
+        self.report(err);
+        Ok(())
+    }
+
+    /// Skips tokens until one of the given tokens is reached, the token is returned but not
+    /// consumed. Tokens in between of the `nesting` pairs of opening and closing tokens are
+    /// skipped entirely, so reaching a token inside of a nested bracket doesn't stop it.
+    ///
+    /// Lexer errors along the way are recorded as if they were given to
+    /// [`BufferedLexer::report`]. Returns `None` when it has reached EOF.
+    pub fn skip_until(&mut self, tokens: &[T], nesting: &[(T, T)]) -> Option<SpannedTokenOwned<T>> {
+        let mut depth = 0usize;
+
+        loop {
+            let next: SpannedTokenOwned<T> = match self.next() {
+                Ok(next) => next.clone().into(),
+                Err(error::ParseError::EOF { .. }) => return None,
+                Err(err) => {
+                    // the error token is skipped by the lexer already
+                    self.errors.push(err);
+                    continue;
+                }
+            };
+
+            if depth == 0 && tokens.contains(&next.token) {
+                self.index -= 1;
+
+                trace!(
+                    "{} - skipped until {:?}",
+                    "  ".repeat(self.save_points.len()),
+                    next
+                );
+
+                return Some(next);
+            }
+
+            if nesting.iter().any(|(open, _)| *open == next.token) {
+                depth += 1;
+            } else if nesting.iter().any(|(_, close)| *close == next.token) {
+                depth = depth.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Records an error without stopping the parsing, see [`BufferedLexer::take_errors`]. The
+    /// error is merged with the other failures at the furthest position reached, see
+    /// [`BufferedLexer::merge_expected`].
+    pub fn report(&mut self, err: error::ParseError<T, SpannedTokenOwned<T>>) {
+        let err = self.merge_expected(err);
+        self.errors.push(err);
+    }
+
+    /// Turns an error of a rule into the error at the furthest position the lexer has reached
+    /// since the last merge, with the expected tokens of every alternatives that failed there.
+    ///
+    /// When a rule tries several alternatives, the error it returns only knows about the last
+    /// alternative tried; this makes the error list everything that would've been accepted.
+    /// Errors before the furthest position are replaced, lexer errors are returned as is.
+    pub fn merge_expected(
+        &mut self,
+        err: error::ParseError<T, SpannedTokenOwned<T>>,
+    ) -> error::ParseError<T, SpannedTokenOwned<T>> {
+        // the error itself may have expected something else at the same position
+        self.record_failure(&err);
+
+        let Some(furthest) = self.furthest_error.take() else {
+            return err;
+        };
+
+        match (failure_position(&err), failure_position(&furthest)) {
+            (Some(pos), Some(furthest_pos)) if furthest_pos >= pos => furthest,
+            _ => err,
+        }
+    }
+
+    /// Records that one of `tokens` would've been accepted at the current position without
+    /// consuming anything, as if it were expected and failed. The tokens are then listed on the
+    /// error at this position, see [`BufferedLexer::merge_expected`].
+    ///
+    /// This is for alternatives that are ruled out without being expected, like after peeking
+    /// the next token to decide which rule to parse.
+    pub fn record_expected(&mut self, tokens: &[T]) {
+        let err = match self.peek() {
+            Ok(next) if tokens.contains(&next.token) => return,
+            Ok(next) => error::ParseError::UnexpectedTokenError {
+                expected: Some(Vec::from(tokens)),
+                pos: next.pos.clone(),
+                unexpected_token: next,
+            },
+            Err(err) => err.map_eof_expected(|| Vec::from(tokens)),
+        };
+
+        self.record_failure(&err);
+    }
+
+    // keeps track of the furthest failure, merging the expected tokens of failures at the same
+    // position
+    fn record_failure(&mut self, err: &error::ParseError<T, SpannedTokenOwned<T>>) {
+        let Some(pos) = failure_position(err) else {
+            return;
+        };
+
+        let furthest_pos = self.furthest_error.as_ref().and_then(failure_position);
+
+        match (&mut self.furthest_error, furthest_pos) {
+            (Some(furthest), Some(furthest_pos)) if furthest_pos == pos => {
+                match (furthest.expected_mut(), err.expected()) {
+                    (Some(tokens), Some(expected)) => {
+                        for token in expected {
+                            if !tokens.contains(token) {
+                                tokens.push(token.clone());
+                            }
+                        }
+                    }
+                    (None, Some(_)) => *furthest = err.clone(),
+                    _ => (),
+                }
+            }
+            (_, Some(furthest_pos)) if furthest_pos > pos => (),
+            _ => self.furthest_error = Some(err.clone()),
+        }
+    }
+
+    /// Returns the errors that are recorded so far, in the order they're found.
+    pub fn errors(&self) -> &[error::ParseError<T, SpannedTokenOwned<T>>] {
+        &self.errors
+    }
+
+    /// Takes every errors that are recorded so far, leaving none behind.
+    pub fn take_errors(&mut self) -> Vec<error::ParseError<T, SpannedTokenOwned<T>>> {
+        std::mem::take(&mut self.errors)
+    }
+}
+
+// the position in which a rule failed to parse, an EOF is always the furthest
+fn failure_position<T: Debug + Clone + PartialEq>(
+    err: &error::ParseError<T, SpannedTokenOwned<T>>,
+) -> Option<usize> {
+    match err {
+        error::ParseError::UnexpectedTokenError { pos, .. } => Some(pos.start),
+        error::ParseError::EOF { .. } => Some(usize::MAX),
+        error::ParseError::LexerError { .. } => None,
+    }
 }
 
 pub mod error {
@@ -696,6 +1079,24 @@ pub mod error {
             }
         }
 
+        /// Returns the tokens that were expected, if there are any
+        pub fn expected(&self) -> Option<&Vec<ET>> {
+            match self {
+                ParseError::UnexpectedTokenError { expected, .. } => expected.as_ref(),
+                ParseError::EOF { expected } => expected.as_ref(),
+                ParseError::LexerError { .. } => None,
+            }
+        }
+
+        /// Returns the tokens that were expected as mutable, if there are any
+        pub fn expected_mut(&mut self) -> Option<&mut Vec<ET>> {
+            match self {
+                ParseError::UnexpectedTokenError { expected, .. } => expected.as_mut(),
+                ParseError::EOF { expected } => expected.as_mut(),
+                ParseError::LexerError { .. } => None,
+            }
+        }
+
         /// Maps the `expected` field of [`ParseError::EOF`]
         pub fn map_eof_expected<F>(self, f: F) -> Self
         where
@@ -710,6 +1111,15 @@ pub mod error {
         }
     }
 
+    // lists the expected tokens, e.g. `Comma or RBrace`
+    fn expected_tokens<ET: Debug>(expected: &[ET]) -> String {
+        expected
+            .iter()
+            .map(|tok| format!("{:?}", tok))
+            .collect::<Vec<_>>()
+            .join(" or ")
+    }
+
     impl<ET: Debug, UET: Debug> Debug for ParseError<ET, UET> {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
             match self {
@@ -717,55 +1127,31 @@ pub mod error {
                     expected,
                     unexpected_token,
                     ..
-                } => {
-                    if let Some(e) = expected {
-                        if e.len() == 1 {
-                            write!(
-                                f,
-                                "expected token {:?}, got {:?} instead",
-                                e.get(0),
-                                unexpected_token
-                            )
-                        } else {
-                            write!(
-                                f,
-                                "expected a {}, got {:?} instead",
-                                e.iter()
-                                    .skip(1)
-                                    // safety: .unwrap() because we wouldn't "didn't expect a token" if we don't expect anything.
-                                    .fold(
-                                        format!("{:?}", e.iter().nth(0).unwrap()),
-                                        |acc, tok| format!("{} or {:?}", acc, tok)
-                                    ),
-                                unexpected_token
-                            )
-                        }
-                    } else {
-                        write!(f, "unexpected token {:?}", unexpected_token)
-                    }
-                }
-                ParseError::EOF { expected } => {
-                    if let Some(expected) = expected {
-                        if expected.len() == 1 {
-                            write!(
-                                f,
-                                "expected token {:?}, but reached end-of-file",
-                                expected.get(0)
-                            )
-                        } else {
-                            write!(
-                                f,
-                                "expected a {:?}, but reached end-of-file",
-                                expected.iter().fold(String::new(), |acc, tok| {
-                                    format!("{:?} or {:?}", acc, tok)
-                                })[..3]
-                                    .to_string(), // removes the trailing ` or`
-                            )
-                        }
-                    } else {
-                        write!(f, "reached end-of-file")
+                } => match expected {
+                    Some(e) if e.len() == 1 => write!(
+                        f,
+                        "expected token {:?}, got {:?} instead",
+                        e[0], unexpected_token
+                    ),
+                    Some(e) => write!(
+                        f,
+                        "expected a {}, got {:?} instead",
+                        expected_tokens(e),
+                        unexpected_token
+                    ),
+                    None => write!(f, "unexpected token {:?}", unexpected_token),
+                },
+                ParseError::EOF { expected } => match expected {
+                    Some(e) if e.len() == 1 => {
+                        write!(f, "expected token {:?}, but reached end-of-file", e[0])
                     }
-                }
+                    Some(e) => write!(
+                        f,
+                        "expected a {}, but reached end-of-file",
+                        expected_tokens(e)
+                    ),
+                    None => write!(f, "reached end-of-file"),
+                },
                 ParseError::LexerError {
                     err_token,
                     pos,
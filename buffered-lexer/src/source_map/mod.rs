@@ -0,0 +1,311 @@
+//! Turns the byte offsets that the lexer gives (see [`crate::SpannedToken::pos`] and
+//! [`crate::error::ParseError`]) into lines and columns, and excerpts snippets out of the source.
+//!
+//! ```
+//! use buffered_lexer::source_map::{ColumnMode, LineCol, SourceMap};
+//!
+//! let mut map = SourceMap::new();
+//! let main = map.add("main.logic", "number a\nonCreate {\n}");
+//!
+//! assert_eq!(
+//!     map.file(main).line_col(9, ColumnMode::Utf8),
+//!     LineCol { line: 2, column: 1 }
+//! );
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// Identifies a file that is added to a [`SourceMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileId(usize);
+
+/// How the columns of a [`LineCol`] are counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnMode {
+    /// Columns are counted in UTF-8 bytes
+    Utf8,
+    /// Columns are counted in UTF-16 code units, this is what the language server protocol uses
+    Utf16,
+}
+
+/// A position on a source file, both the line and the column start from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LineCol {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for LineCol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// A set of source files, each of them are identified with a [`FileId`].
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        SourceMap::default()
+    }
+
+    /// Adds a source file into the map, returns the id to refer to it
+    pub fn add(&mut self, name: impl ToString, source: impl ToString) -> FileId {
+        self.files.push(SourceFile::new(name, source));
+        FileId(self.files.len() - 1)
+    }
+
+    /// Returns the file of the given id.
+    ///
+    /// Panics if the id doesn't come from this map.
+    pub fn file(&self, id: FileId) -> &SourceFile {
+        self.files
+            .get(id.0)
+            .expect("the file id doesn't belong to this source map")
+    }
+
+    /// Finds the id of a file by its name
+    pub fn find(&self, name: &str) -> Option<FileId> {
+        self.files
+            .iter()
+            .position(|file| file.name == name)
+            .map(FileId)
+    }
+
+    /// Iterates over the files, in the order they're added
+    pub fn files(&self) -> impl Iterator<Item = (FileId, &SourceFile)> {
+        self.files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| (FileId(index), file))
+    }
+
+    /// Converts a byte offset of a file into its line and column, see [`SourceFile::line_col`]
+    pub fn line_col(&self, id: FileId, offset: usize, mode: ColumnMode) -> LineCol {
+        self.file(id).line_col(offset, mode)
+    }
+}
+
+/// A source file along with where each of its lines start.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceFile {
+    name: String,
+    source: String,
+    // byte offsets of the start of every lines, the first line always starts at 0
+    line_starts: Vec<usize>,
+}
+
+impl SourceFile {
+    pub fn new(name: impl ToString, source: impl ToString) -> Self {
+        let source = source.to_string();
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+
+        SourceFile {
+            name: name.to_string(),
+            source,
+            line_starts,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the amount of lines there are, an empty file has one empty line
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the content of a line (starting from 1) without its line ending
+    pub fn line(&self, line: usize) -> Option<&str> {
+        let range = self.line_range(line.checked_sub(1)?)?;
+        let text = &self.source[range];
+
+        Some(
+            text.strip_suffix('\n')
+                .map(|text| text.strip_suffix('\r').unwrap_or(text))
+                .unwrap_or(text),
+        )
+    }
+
+    /// Converts a byte offset into its line and column. Offsets past the end of the file point
+    /// to the end of the file, and offsets in the middle of a character point to the character.
+    pub fn line_col(&self, offset: usize, mode: ColumnMode) -> LineCol {
+        let offset = self.char_boundary(offset);
+        let line = self.line_index(offset);
+        let before = &self.source[self.line_starts[line]..offset];
+
+        LineCol {
+            line: line + 1,
+            column: column_width(before, mode) + 1,
+        }
+    }
+
+    /// Converts a line and column back into a byte offset, returns `None` when the line doesn't
+    /// exist. Columns past the end of the line point to the end of the line.
+    pub fn offset(&self, position: LineCol, mode: ColumnMode) -> Option<usize> {
+        let text = self.line(position.line)?;
+        let start = self.line_starts[position.line - 1];
+        let column = position.column.saturating_sub(1);
+
+        let mut width = 0;
+        for (index, ch) in text.char_indices() {
+            if width >= column {
+                return Some(start + index);
+            }
+
+            width += match mode {
+                ColumnMode::Utf8 => ch.len_utf8(),
+                ColumnMode::Utf16 => ch.len_utf16(),
+            };
+        }
+
+        Some(start + text.len())
+    }
+
+    /// Converts a span of bytes into a span of characters, for tools that count characters
+    pub fn char_span(&self, span: Range<usize>) -> Range<usize> {
+        let start = self.char_boundary(span.start);
+        let end = self.char_boundary(span.end.max(span.start));
+        let start_chars = self.source[..start].chars().count();
+
+        start_chars..start_chars + self.source[start..end].chars().count()
+    }
+
+    /// Excerpts the lines that a span is on, along with `context` lines before and after it.
+    pub fn snippet(&self, span: Range<usize>, context: usize) -> Snippet<'_> {
+        let start = self.char_boundary(span.start);
+        let end = self.char_boundary(span.end.max(span.start));
+
+        let first_line = self.line_index(start);
+        let last_line = self.line_index(end);
+
+        let from = first_line.saturating_sub(context);
+        let to = (last_line + context).min(self.line_count() - 1);
+
+        let lines = (from..=to)
+            .map(|line| {
+                let text = self.line(line + 1).unwrap();
+                let line_start = self.line_starts[line];
+
+                let highlight = (first_line..=last_line).contains(&line).then(|| {
+                    let highlight_start = start.max(line_start) - line_start;
+                    let highlight_end = (end - line_start.min(end)).min(text.len());
+
+                    highlight_start.min(text.len())..highlight_end.max(highlight_start)
+                });
+
+                SnippetLine {
+                    number: line + 1,
+                    text,
+                    highlight,
+                }
+            })
+            .collect();
+
+        Snippet { lines }
+    }
+
+    // the zero-based line that the offset is on
+    fn line_index(&self, offset: usize) -> usize {
+        match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        }
+    }
+
+    // the byte range of a zero-based line, including its line ending
+    fn line_range(&self, line: usize) -> Option<Range<usize>> {
+        let start = *self.line_starts.get(line)?;
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .copied()
+            .unwrap_or(self.source.len());
+
+        Some(start..end)
+    }
+
+    // moves an offset back to the start of the character it's in, clamped to the end of the file
+    fn char_boundary(&self, offset: usize) -> usize {
+        let mut offset = offset.min(self.source.len());
+
+        while !self.source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        offset
+    }
+}
+
+fn column_width(text: &str, mode: ColumnMode) -> usize {
+    match mode {
+        ColumnMode::Utf8 => text.len(),
+        ColumnMode::Utf16 => text.encode_utf16().count(),
+    }
+}
+
+/// An excerpt of a source file, created by [`SourceFile::snippet`]. Its [`Display`] shows the
+/// lines with their line numbers, with the span underlined:
+///
+/// ```text
+/// 2 | onCreate {
+/// 3 |     a = "hello" * 2
+///   |         ^^^^^^^
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snippet<'a> {
+    pub lines: Vec<SnippetLine<'a>>,
+}
+
+/// A line of a [`Snippet`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnippetLine<'a> {
+    /// The line number, starting from 1
+    pub number: usize,
+    pub text: &'a str,
+    /// The bytes of the line that are covered by the span, if the span is on this line
+    pub highlight: Option<Range<usize>>,
+}
+
+impl Display for Snippet<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let gutter = self
+            .lines
+            .last()
+            .map_or(1, |line| line.number.to_string().len());
+
+        for line in &self.lines {
+            writeln!(f, "{:>gutter$} | {}", line.number, line.text)?;
+
+            if let Some(highlight) = &line.highlight {
+                // the carets are aligned by characters, an empty span still gets one
+                let padding = line.text[..highlight.start].chars().count();
+                let width = line.text[highlight.clone()].chars().count().max(1);
+
+                writeln!(
+                    f,
+                    "{:gutter$} | {}{}",
+                    "",
+                    " ".repeat(padding),
+                    "^".repeat(width)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use super::{ColumnMode, LineCol, SourceFile, SourceMap};
+
+fn pos(line: usize, column: usize) -> LineCol {
+    LineCol { line, column }
+}
+
+const SOURCE: &str = "number a\r\nonCreate {\n    toast(\"héllo 👋\")\n}\n";
+
+#[test]
+fn line_col() {
+    let file = SourceFile::new("main.logic", SOURCE);
+
+    assert_eq!(file.line_count(), 5);
+    assert_eq!(file.line(1), Some("number a"));
+    assert_eq!(file.line(3), Some("    toast(\"héllo 👋\")"));
+    assert_eq!(file.line(5), Some(""));
+    assert_eq!(file.line(0), None);
+    assert_eq!(file.line(6), None);
+
+    let at = |offset, mode| file.line_col(offset, mode);
+
+    assert_eq!(at(0, ColumnMode::Utf8), pos(1, 1));
+    assert_eq!(at(10, ColumnMode::Utf8), pos(2, 1));
+
+    // `ello` comes after a two-byte character, but only one UTF-16 code unit
+    let ello = SOURCE.find("llo").unwrap();
+    assert_eq!(at(ello, ColumnMode::Utf8), pos(3, 15));
+    assert_eq!(at(ello, ColumnMode::Utf16), pos(3, 14));
+
+    // the emoji is four bytes, and two UTF-16 code units
+    let quote = SOURCE.rfind('"').unwrap();
+    assert_eq!(at(quote, ColumnMode::Utf8), pos(3, 23));
+    assert_eq!(at(quote, ColumnMode::Utf16), pos(3, 20));
+
+    // offsets inside of a character point to the character, past the end point to the end
+    assert_eq!(at(quote - 1, ColumnMode::Utf16), pos(3, 18));
+    assert_eq!(at(1000, ColumnMode::Utf8), pos(5, 1));
+}
+
+#[test]
+fn offset() {
+    let file = SourceFile::new("main.logic", SOURCE);
+
+    for offset in [
+        0,
+        5,
+        10,
+        SOURCE.find("llo").unwrap(),
+        SOURCE.rfind('"').unwrap(),
+    ] {
+        for mode in [ColumnMode::Utf8, ColumnMode::Utf16] {
+            let position = file.line_col(offset, mode);
+            assert_eq!(file.offset(position, mode), Some(offset));
+        }
+    }
+
+    // columns past the line end point to the end of the line, not including the line ending
+    let end = file.offset(pos(1, 100), ColumnMode::Utf8);
+    assert_eq!(end, Some(8));
+
+    assert_eq!(file.offset(pos(9, 1), ColumnMode::Utf8), None);
+}
+
+#[test]
+fn char_span() {
+    let file = SourceFile::new("main.logic", SOURCE);
+
+    let start = SOURCE.find('h').unwrap();
+    let end = SOURCE.rfind('"').unwrap();
+
+    let span = file.char_span(start..end);
+    let chars = SOURCE.chars().collect::<Vec<_>>();
+
+    assert_eq!(chars[span].iter().collect::<String>(), "héllo 👋");
+}
+
+#[test]
+fn snippet() {
+    let file = SourceFile::new("main.logic", SOURCE);
+    let start = SOURCE.find("toast").unwrap();
+
+    let snippet = file.snippet(start..start + 5, 1);
+    assert_eq!(
+        snippet.to_string(),
+        "2 | onCreate {\n3 |     toast(\"héllo 👋\")\n  |     ^^^^^\n4 | }\n"
+    );
+
+    // spans over several lines are underlined on each of them, empty spans get a single caret
+    let snippet = file.snippet(SOURCE.find('{').unwrap()..start + 1, 0);
+    assert_eq!(
+        snippet.to_string(),
+        "2 | onCreate {\n  |          ^\n3 |     toast(\"héllo 👋\")\n  | ^^^^^\n"
+    );
+
+    let snippet = file.snippet(SOURCE.len()..SOURCE.len(), 0);
+    assert_eq!(snippet.to_string(), "5 | \n  | ^\n");
+}
+
+#[test]
+fn source_map() {
+    let mut map = SourceMap::new();
+
+    let main = map.add("main.logic", SOURCE);
+    let layout = map.add("main.layout", "LinearLayout {\n}");
+
+    assert_ne!(main, layout);
+    assert_eq!(map.find("main.layout"), Some(layout));
+    assert_eq!(map.find("other.logic"), None);
+
+    assert_eq!(map.file(layout).name(), "main.layout");
+    assert_eq!(map.line_col(layout, 15, ColumnMode::Utf16), pos(2, 1));
+
+    let names = map.files().map(|(_, file)| file.name()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["main.logic", "main.layout"]);
+}
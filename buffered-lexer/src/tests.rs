@@ -2,7 +2,7 @@ extern crate logos;
 
 use logos::Logos;
 
-use crate::{error::ParseError, BufferedLexer, SpannedTokenOwned};
+use crate::{error::ParseError, BufferedLexer, SpannedToken, SpannedTokenOwned};
 
 #[derive(Logos, Clone, PartialEq, Debug)]
 enum Token {
@@ -88,9 +88,15 @@ fn expect_fail() {
 
     let Err(ParseError::UnexpectedTokenError {
         expected: Some(expected),
-        unexpected_token: SpannedTokenOwned { token: Token::Hello, slice, pos },
-        pos: u_pos
-    }) = lex.expect(Token::World) else {
+        unexpected_token:
+            SpannedTokenOwned {
+                token: Token::Hello,
+                slice,
+                pos,
+            },
+        pos: u_pos,
+    }) = lex.expect(Token::World)
+    else {
         panic!("Not unexpected?");
     };
 
@@ -189,3 +195,268 @@ fn expect_multiple_choices_fail() {
         assert_eq!(i, lex.get_index());
     }
 }
+
+#[derive(Logos, Clone, PartialEq, Debug)]
+enum TriviaToken {
+    #[token("hello")]
+    Hello,
+    #[token("world")]
+    World,
+
+    #[regex(r"[ \t\n]+")]
+    Whitespace,
+    #[regex(r"//[^\n]*\n?")]
+    Comment,
+
+    #[error]
+    Error,
+}
+
+#[inline]
+fn create_lossless(raw: &str) -> BufferedLexer<'_, TriviaToken> {
+    BufferedLexer::new_lossless(
+        TriviaToken::lexer(raw),
+        TriviaToken::Error,
+        vec![TriviaToken::Whitespace, TriviaToken::Comment],
+    )
+}
+
+#[test]
+fn lossless_skips_trivia() {
+    let raw = "// greeting\nhello  world // end\n";
+    let mut lex = create_lossless(raw);
+
+    assert!(lex.is_lossless());
+
+    let next = lex.expect(TriviaToken::Hello).unwrap();
+    assert_eq!(next.slice, "hello");
+    assert_eq!(next.pos, 12..17);
+
+    let next = lex.expect(TriviaToken::World).unwrap();
+    assert_eq!(next.slice, "world");
+    assert_eq!(next.pos, 19..24);
+    assert_eq!(lex.get_index(), 2);
+
+    let Err(ParseError::EOF { .. }) = lex.next() else {
+        panic!("Not eof?");
+    };
+}
+
+fn slices<'s>(tokens: &[SpannedToken<'s, TriviaToken>]) -> Vec<&'s str> {
+    tokens.iter().map(|tok| tok.slice).collect()
+}
+
+#[test]
+fn lossless_attaches_trivia() {
+    let raw = "// greeting\nhello  world // end\n\n// trailing\n";
+    let mut lex = create_lossless(raw);
+
+    let hello = lex.expect(TriviaToken::Hello).unwrap();
+    let world = lex.expect(TriviaToken::World).unwrap();
+    let _ = lex.next();
+
+    let hello = lex.trivia_of(&hello.pos).unwrap();
+    assert_eq!(slices(&hello.leading), vec!["// greeting\n"]);
+    assert_eq!(slices(&hello.trailing), vec!["  "]);
+
+    let world = lex.trivia_of(&world.pos).unwrap();
+    assert_eq!(slices(&world.leading), Vec::<&str>::new());
+    assert_eq!(slices(&world.trailing), vec![" ", "// end\n"]);
+
+    assert_eq!(slices(lex.eof_trivia()), vec!["\n", "// trailing\n"]);
+    assert_eq!(lex.reconstruct(), raw);
+}
+
+#[test]
+fn lossless_restore() {
+    let raw = "hello // comment\nworld";
+    let mut lex = create_lossless(raw);
+
+    lex.start();
+    lex.expect(TriviaToken::Hello).unwrap();
+    lex.expect(TriviaToken::World).unwrap();
+    lex.restore();
+
+    // going through the cached tokens shouldn't collect their trivia twice
+    lex.expect(TriviaToken::Hello).unwrap();
+    lex.expect(TriviaToken::World).unwrap();
+    let _ = lex.next();
+
+    assert_eq!(lex.lossless_tokens().len(), 2);
+    assert_eq!(lex.reconstruct(), raw);
+}
+
+#[test]
+fn not_lossless() {
+    let mut lex = create("hello world");
+
+    lex.next().unwrap();
+    lex.next().unwrap();
+
+    assert!(!lex.is_lossless());
+    assert!(lex.lossless_tokens().is_empty());
+    assert_eq!(lex.reconstruct(), "");
+}
+
+#[test]
+fn restore_after_nested_success() {
+    let mut lex = create("hello world foo bar");
+
+    lex.next().unwrap();
+
+    lex.start();
+    lex.next().unwrap();
+
+    lex.start();
+    lex.next().unwrap();
+    lex.next().unwrap();
+    lex.success();
+
+    // the tokens after the outer save point must still be there
+    lex.restore();
+
+    assert_eq!(lex.next().unwrap().token, Token::World);
+}
+
+#[derive(Logos, Clone, PartialEq, Debug)]
+enum BracketToken {
+    #[token("(")]
+    LParen,
+    #[token(")")]
+    RParen,
+    #[token(",")]
+    Comma,
+
+    #[regex(r"[a-zA-Z_][a-zA-Z0-9_]*")]
+    Identifier,
+
+    #[error]
+    #[regex(r"[ \t]+", logos::skip)] // whitespace
+    Error,
+}
+
+#[inline]
+fn create_bracket(raw: &str) -> BufferedLexer<'_, BracketToken> {
+    BufferedLexer::new(BracketToken::lexer(raw), BracketToken::Error)
+}
+
+const NESTING: &[(BracketToken, BracketToken)] = &[(BracketToken::LParen, BracketToken::RParen)];
+
+#[test]
+fn skip_until() {
+    let raw = "a (b, c) $ d, e";
+    let mut lex = create_bracket(raw);
+
+    let reached = lex
+        .skip_until(&[BracketToken::Comma], NESTING)
+        .expect("should've reached a comma");
+
+    // the commas inside the parentheses are skipped
+    assert_eq!(reached.pos, 12..13);
+    assert_eq!(lex.next().unwrap().pos, 12..13);
+
+    // the invalid token is recorded
+    assert!(matches!(
+        lex.errors(),
+        [ParseError::LexerError { slice, .. }] if slice == "$"
+    ));
+
+    assert_eq!(lex.skip_until(&[BracketToken::Comma], NESTING), None);
+}
+
+#[test]
+fn recover() {
+    let mut lex = create_bracket("a, b) c");
+
+    // a rule that fails after consuming a token, leaving its save points behind
+    let depth = lex.depth();
+    let err = (|| {
+        lex.start();
+        lex.expect(BracketToken::Identifier)?;
+
+        lex.start();
+        lex.expect(BracketToken::Identifier)?;
+
+        lex.success();
+        lex.success();
+        Ok(())
+    })()
+    .unwrap_err();
+
+    lex.recover(depth, err).unwrap();
+    assert_eq!(lex.depth(), depth);
+
+    // the comma it didn't expect is put back
+    assert_eq!(lex.next().unwrap().token, BracketToken::Comma);
+
+    let reached = lex.skip_until(&[BracketToken::RParen], NESTING).unwrap();
+    assert_eq!(reached.token, BracketToken::RParen);
+
+    let errors = lex.take_errors();
+    assert_eq!(errors.len(), 1);
+    assert!(lex.errors().is_empty());
+
+    // there's nothing to recover to at the end of the file
+    lex.next().unwrap();
+    lex.next().unwrap();
+
+    let err = lex.expect(BracketToken::Identifier).unwrap_err();
+    assert!(lex.recover(depth, err).is_err());
+}
+
+#[test]
+fn merge_expected() {
+    let mut lex = create("hello foo");
+
+    // the first alternative goes further before failing
+    lex.start();
+    lex.expect(Token::Hello).unwrap();
+    let _ = lex.expect(Token::World).unwrap_err();
+    lex.restore();
+
+    lex.start();
+    lex.expect(Token::Hello).unwrap();
+    let _ = lex.expect(Token::Bar).unwrap_err();
+    lex.restore();
+
+    // the last one fails right away
+    lex.start();
+    let err = lex.expect(Token::Foo).unwrap_err();
+    lex.restore();
+
+    let ParseError::UnexpectedTokenError { expected, pos, .. } = lex.merge_expected(err) else {
+        panic!("not an unexpected token error");
+    };
+
+    assert_eq!(pos, 6..9);
+    assert_eq!(expected, Some(vec![Token::World, Token::Bar]));
+
+    // the failures are forgotten once they're merged
+    let err = lex.expect(Token::Foo).unwrap_err();
+    assert_eq!(lex.merge_expected(err.clone()), err);
+}
+
+#[test]
+fn record_expected() {
+    let mut lex = create("hello foo");
+
+    lex.expect(Token::Hello).unwrap();
+    lex.record_expected(&[Token::World, Token::Bar]);
+
+    // nothing is consumed
+    assert_eq!(lex.get_index(), 1);
+
+    let err = lex.expect(Token::Hello).unwrap_err();
+    let ParseError::UnexpectedTokenError { expected, pos, .. } = lex.merge_expected(err) else {
+        panic!("not an unexpected token error");
+    };
+
+    assert_eq!(pos, 6..9);
+    assert_eq!(expected, Some(vec![Token::World, Token::Bar, Token::Hello]));
+
+    // nothing is recorded when one of them is next
+    let mut lex = create("foo");
+    lex.record_expected(&[Token::Foo]);
+    let err = lex.expect(Token::Bar).unwrap_err();
+    assert_eq!(lex.merge_expected(err.clone()), err);
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use yew_agent::{HandlerId, Public, WorkerLink};
 
+use rafflesia::diagnostic::Diagnostics;
+
 use crate::{virtfs::VirtualFs, compiler};
 
 pub struct CompilerWorker {
@@ -60,7 +62,13 @@ impl yew_agent::Worker for CompilerWorker {
                     library: swrs::encrypt_sw(raw.library.as_bytes()),
                 })
             })
-            .unwrap_or_else(|error| CompilerWorkerOutput::Failure(error.to_string())));
+            .unwrap_or_else(|error| CompilerWorkerOutput::Failure(
+                // show the reports of the source files just like the cli does
+                match error.chain().find_map(|err| err.downcast_ref::<Diagnostics>()) {
+                    Some(diagnostics) => format!("{}\n{}", error, diagnostics.render(false)),
+                    None => format!("{:#}", error),
+                }
+            )));
     }
 
     fn name_of_resource() -> &'static str {
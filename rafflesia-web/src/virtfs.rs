@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
+use rafflesia::core::source::{SourceError, SourceProvider};
 
 // todo: is `id` needed here?
 
@@ -197,8 +198,41 @@ impl VirtualFs {
     pub fn get_folder(&mut self, path: &[String]) -> Result<&Entry, IOError> {
         self.root.get_entry(path, None)
     }
+
+    // finds an entry by a path separated with `/`, like "src/main.logic"
+    fn entry_at(&self, path: &str) -> Option<&Entry> {
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(&self.root, |entry, name| match entry {
+                Entry::Folder { children, .. } => children.get(name),
+                Entry::File { .. } => None,
+            })
+    }
+}
+
+// lets the rafflesia compiler read the project files straight from the fs, paths are resolved
+// by the names of the entries, starting from the root
+impl SourceProvider for VirtualFs {
+    fn read_bytes(&self, path: &str) -> Result<Vec<u8>, SourceError> {
+        let Some(Entry::File { content, .. }) = self.entry_at(path)
+            else { return Err(SourceError::NotFound { path: path.to_string() }) };
+
+        Ok(content.clone())
+    }
+
+    fn list(&self, folder: &str) -> Result<Vec<String>, SourceError> {
+        let Some(Entry::Folder { children, .. }) = self.entry_at(folder)
+            else { return Ok(vec![]) };
+
+        // children are in a BTreeMap, they're already sorted by their names
+        Ok(children.iter()
+            .filter(|(_, entry)| matches!(entry, Entry::File { .. }))
+            .map(|(name, _)| name.clone())
+            .collect())
+    }
 }
 
+
 #[derive(thiserror::Error, Debug, Clone, PartialEq)]
 pub enum IOError {
     #[error("given path doesnt exists")]
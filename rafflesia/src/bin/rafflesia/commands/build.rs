@@ -1,16 +1,145 @@
+use crate::commands::AlreadyReported;
 use anyhow::Result;
-use clap::{AppSettings, ArgMatches, Command};
-use rafflesia::ops::build;
+use clap::{AppSettings, Arg, ArgMatches, Command};
+use console::style;
+use indicatif::{ProgressBar, ProgressStyle};
+use rafflesia::core::source::DirSource;
+use rafflesia::diagnostic::json::{BuildSummary, Message};
+use rafflesia::diagnostic::{Severity, SourceDiagnostics};
+use rafflesia::ops::build::{self, BuildOptions, BuildReporter, BuildStep};
+use std::path::PathBuf;
 
 pub fn cli() -> Command<'static> {
     Command::new("build")
         .dont_collapse_args_in_usage(true)
+        .args(&[Arg::new("message-format")
+            .long("message-format")
+            .takes_value(true)
+            .possible_values(["human", "json"])
+            .default_value("human")
+            .help("The format of the output, `json` prints every diagnostics and a summary of the build as JSON objects, one per line.")])
         .setting(AppSettings::DeriveDisplayOrder)
         .about("Builds a rafflesia project")
         .after_help("Run `rafflesia help build` for more detailed information.\n")
 }
 
 pub fn exec(args: &ArgMatches) -> Result<()> {
-    // todo: pass args
-    build::build()
+    let path = std::env::current_dir()?;
+    let source = DirSource::new(&path);
+    let options = BuildOptions {
+        output: Some(path.join("build")),
+    };
+
+    if args.value_of("message-format") != Some("json") {
+        build::build_project(&source, &options, &mut ConsoleReporter::default())?;
+        return Ok(());
+    }
+
+    let mut reporter = JsonReporter::default();
+    let result = build::build_project(&source, &options, &mut reporter);
+
+    let summary = match &result {
+        Ok(_) => BuildSummary {
+            success: true,
+            errors: 0,
+            warnings: reporter.warnings,
+            error: None,
+        },
+
+        // the diagnostics have been printed by the reporter
+        Err(_) if reporter.errors > 0 => BuildSummary {
+            success: false,
+            errors: reporter.errors,
+            warnings: reporter.warnings,
+            error: None,
+        },
+
+        Err(err) => BuildSummary {
+            success: false,
+            errors: 1,
+            warnings: reporter.warnings,
+            error: Some(format!("{:#}", err)),
+        },
+    };
+
+    println!("{}", Message::Summary(summary).to_json());
+
+    // everything has been printed as json, there's nothing left for main to show
+    result
+        .map(|_| ())
+        .map_err(|err| err.context(AlreadyReported))
+}
+
+// shows the progress of the build with a spinner, the diagnostics are rendered by main once the
+// build fails
+#[derive(Default)]
+struct ConsoleReporter {
+    pb: Option<ProgressBar>,
+    output: Option<PathBuf>,
+}
+
+impl BuildReporter for ConsoleReporter {
+    fn started(&mut self, project_name: &str) {
+        println!("Building project {}", style(project_name).bold().cyan());
+
+        let pb = ProgressBar::new_spinner().with_style(
+            ProgressStyle::default_spinner()
+                .template("{spinner:.dim.bold} Building - {wide_msg}")
+                .tick_chars("/|\\- "),
+        );
+        pb.enable_steady_tick(200);
+
+        self.pb = Some(pb);
+    }
+
+    fn progress(&mut self, step: BuildStep) {
+        let Some(pb) = &self.pb else { return };
+
+        match step {
+            BuildStep::CompilingActivity(name) => {
+                pb.set_message(format!("Compiling {}", style(name).cyan()))
+            }
+            BuildStep::CompilingCustomView(name) => {
+                pb.set_message(format!("Compiling custom view {}", style(name).cyan()))
+            }
+            BuildStep::Packing => pb.set_message("Packing it all together"),
+            BuildStep::Writing(output) => {
+                pb.set_message("Writing the project files");
+                self.output = Some(output.to_path_buf());
+            }
+        }
+    }
+
+    fn finished(&mut self, success: bool) {
+        let Some(pb) = self.pb.take() else { return };
+
+        if let (true, Some(output)) = (success, &self.output) {
+            pb.println(format!("Files written to {}", output.display()));
+        }
+
+        pb.finish_and_clear();
+
+        if success {
+            println!("{}", style("Done").green());
+        }
+    }
+}
+
+// prints the diagnostics as json messages as soon as they're found, the summary is printed after
+// the build
+#[derive(Default)]
+struct JsonReporter {
+    errors: usize,
+    warnings: usize,
+}
+
+impl BuildReporter for JsonReporter {
+    fn diagnostics(&mut self, diagnostics: &SourceDiagnostics) {
+        for message in diagnostics.json_messages() {
+            println!("{}", message.to_json());
+        }
+
+        self.errors += diagnostics.count(Severity::Error);
+        self.warnings += diagnostics.count(Severity::Warning);
+    }
 }
@@ -0,0 +1,18 @@
+use anyhow::Result;
+use clap::{AppSettings, Arg, ArgMatches, Command};
+use rafflesia::ops::fmt;
+
+pub fn cli() -> Command<'static> {
+    Command::new("fmt")
+        .dont_collapse_args_in_usage(true)
+        .args(&[Arg::new("check")
+            .long("check")
+            .help("Checks if the files are formatted without modifying them, fails if any of them aren't.")])
+        .setting(AppSettings::DeriveDisplayOrder)
+        .about("Formats the logic and layout files of a rafflesia project")
+        .after_help("Run `rafflesia help fmt` for more detailed information.\n")
+}
+
+pub fn exec(args: &ArgMatches) -> Result<()> {
+    fmt::fmt(args.is_present("check"))
+}
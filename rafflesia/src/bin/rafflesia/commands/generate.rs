@@ -1,16 +1,28 @@
 use anyhow::Result;
-use clap::{AppSettings, ArgMatches, Command};
+use clap::{AppSettings, Arg, ArgMatches, Command};
+use rafflesia::ops::generate;
+use std::path::Path;
 
 pub fn cli() -> Command<'static> {
     Command::new("generate")
         .dont_collapse_args_in_usage(true)
+        .args(&[
+            Arg::new("project_path")
+                .help("The folder containing the sketchware project files (project, file, logic, view, resource and library).")
+                .takes_value(true)
+                .required(true),
+            Arg::new("output")
+                .help("The folder of where the rafflesia project will be generated, defaults to the project's workspace name.")
+                .takes_value(true),
+        ])
         .setting(AppSettings::DeriveDisplayOrder)
         .about("Generate a rafflesia project from a sketchware project")
         .after_help("Run `rafflesia help generate` for more detailed information.\n")
 }
 
 pub fn exec(args: &ArgMatches) -> Result<()> {
-    println!("Generating rafflesia projects is currently work-in-progress");
+    let project_path = args.value_of("project_path").unwrap();
+    let output = args.value_of("output");
 
-    Ok(())
+    generate::generate(Path::new(project_path), output.map(Path::new))
 }
@@ -1,14 +1,28 @@
 use anyhow::Result;
 use clap::{ArgMatches, Command};
+use thiserror::Error;
+
+/// An error that has already been shown to the user by the command, exits without printing it
+/// again.
+#[derive(Debug, Error)]
+#[error("the error has already been reported")]
+pub struct AlreadyReported;
 
 pub fn builtin() -> Vec<Command<'static>> {
-    vec![new::cli(), build::cli(), generate::cli(), metadata::cli()]
+    vec![
+        new::cli(),
+        build::cli(),
+        fmt::cli(),
+        generate::cli(),
+        metadata::cli(),
+    ]
 }
 
 pub fn builtin_exec(cmd: &str) -> Option<fn(&ArgMatches) -> Result<()>> {
     Some(match cmd {
         "new" => new::exec,
         "build" => build::exec,
+        "fmt" => fmt::exec,
         "generate" => generate::exec,
         "metadata" => metadata::exec,
         _ => return None,
@@ -16,6 +30,7 @@ pub fn builtin_exec(cmd: &str) -> Option<fn(&ArgMatches) -> Result<()>> {
 }
 
 pub mod build;
+pub mod fmt;
 pub mod generate;
 pub mod metadata;
 pub mod new;
@@ -105,10 +105,7 @@ pub fn exec(args: &ArgMatches) -> Result<()> {
     let mut activities = HashMap::new();
     activities.insert(
         "main".to_string(),
-        ActivityTable {
-            logic: "main.logic".to_string(),
-            layout: "layout.logic".to_string(),
-        },
+        ActivityTable::new("main.logic", "layout.logic"),
     );
 
     let now = Utc::now();
@@ -139,7 +136,9 @@ pub fn exec(args: &ArgMatches) -> Result<()> {
             colors: Some(palette),
         },
         activity: activities,
+        view: HashMap::new(),
         library: Some(libraries),
+        resources: None,
     })?;
 
     // we now do file operation funsies
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use clap::{AppSettings, Command};
 use console::style;
+use rafflesia::diagnostic::Diagnostics;
 use std::process::{ExitCode, Termination};
 
 mod commands;
@@ -26,6 +27,22 @@ fn try_main() -> Result<()> {
 
 fn main() -> ExitCode {
     if let Err(err) = try_main() {
+        if err.is::<commands::AlreadyReported>() {
+            return ExitCode::FAILURE;
+        }
+
+        // errors on source files are shown as a report instead of the error chain
+        if let Some(diagnostics) = err
+            .chain()
+            .find_map(|err_item| err_item.downcast_ref::<Diagnostics>())
+        {
+            eprintln!();
+            diagnostics.eprint();
+            eprintln!("\n{} {}\n", style("error:").bold().red(), err);
+
+            return ExitCode::FAILURE;
+        }
+
         eprintln!("\n{} {}", style("error:").bold().red(), err);
         eprintln!();
         eprintln!("Caused by:");
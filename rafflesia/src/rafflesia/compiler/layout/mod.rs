@@ -4,8 +4,8 @@ pub mod parser;
 mod tests;
 
 use crate::compiler::layout::attr_parser::{parse_color, parse_gravity, parse_text_style};
-use parser::View;
-use std::collections::HashMap;
+use crate::compiler::{CompileErrors, ResourceKind, ResourceNames, Span};
+use parser::{Attribute, View};
 use std::num::{ParseFloatError, ParseIntError};
 use std::str::ParseBoolError;
 use swrs::api::view::{SidesValue, View as SWRSView, ViewType};
@@ -15,18 +15,22 @@ use swrs::parser::view::models::layout::gravity::Gravity;
 use swrs::parser::view::models::layout::{gravity, Orientation, Size};
 use swrs::parser::view::models::text::{ImeOption, InputType, TextType};
 use swrs::parser::view::models::{AndroidView, SpinnerMode};
+use swrs::LinkedHashMap;
 use thiserror::Error;
 
-// todo: use position stuff so we can pinpoint exactly where an error come from
-
 /// Compiles a parsed view into an swrs [`swrs::api::view::View`].
-pub fn compile_view_tree(parsed: View) -> Result<SWRSView, ViewCompileError> {
+///
+/// Compilation doesn't stop at the first error; invalid attributes are skipped and every error
+/// found throughout the view tree are returned.
+pub fn compile_view_tree(parsed: View) -> Result<SWRSView, CompileErrors<ViewCompileError>> {
+    // returns None when the view itself is unknown, errors are collected into `errors`
     fn compile(
         parsed: View,
         parent_id: &str,
         parent_type: i8,
         state: &mut u32,
-    ) -> Result<SWRSView, ViewCompileError> {
+        errors: &mut Vec<ViewCompileError>,
+    ) -> Option<SWRSView> {
         let view_id = if let Some(id) = parsed.view_id {
             id
         } else {
@@ -34,206 +38,321 @@ pub fn compile_view_tree(parsed: View) -> Result<SWRSView, ViewCompileError> {
             format!("view{}", *state - 1)
         };
 
-        Ok(if let Some(mut attrs) = parsed.attributes {
-            let view = map_view_name_attrs(parsed.name, &mut attrs)?;
-
-            macro_rules! attr_number_get {
-                ($name:expr,$default:expr) => {
-                    if let Some(val) = attrs.remove($name) {
-                        val.parse().map_err(|err| {
-                            ViewCompileError::AttributeParseError(
-                                AttributeParseError::InvalidIntValue {
-                                    attribute_name: $name.to_string(),
-                                    attribute_value: val,
-                                    err,
-                                },
-                            )
-                        })?
-                    } else {
-                        $default
-                    }
-                };
+        // a view without attributes is the same as a view with an empty attributes
+        let mut attrs = parsed.attributes.unwrap_or_default();
+        let view = map_view_name_attrs(parsed.name, parsed.name_span, &mut attrs, errors);
+
+        macro_rules! attr_number_get {
+            ($name:expr,$default:expr) => {
+                parse_attribute(&mut attrs, $name, $default, errors, |val, value_span| {
+                    val.parse()
+                        .map_err(|err| ViewCompileError::AttributeParseError {
+                            error: AttributeParseError::InvalidIntValue {
+                                attribute_name: $name.to_string(),
+                                attribute_value: val,
+                                err,
+                            },
+                            span: value_span,
+                        })
+                })
+            };
+        }
+
+        let padding = if attrs.contains_key("padding") {
+            let padding = attr_number_get!("padding", 8);
+
+            SidesValue {
+                top: padding,
+                right: padding,
+                bottom: padding,
+                left: padding,
+            }
+        } else {
+            SidesValue {
+                top: attr_number_get!("padding_top", 8),
+                right: attr_number_get!("padding_right", 8),
+                bottom: attr_number_get!("padding_bottom", 8),
+                left: attr_number_get!("padding_left", 8),
             }
+        };
 
-            let padding = attrs
-                .remove("padding")
-                .map(|e| match e.parse() {
-                    Ok(val) => Ok(SidesValue {
-                        top: val,
-                        right: val,
-                        bottom: val,
-                        left: val,
-                    }),
-                    Err(err) => Err(ViewCompileError::AttributeParseError(
-                        AttributeParseError::InvalidIntValue {
-                            attribute_name: "margin".to_string(),
-                            attribute_value: e,
-                            err,
-                        },
-                    )),
-                })
-                .unwrap_or_else(|| {
-                    Ok(SidesValue {
-                        top: attr_number_get!("padding_top", 8),
-                        right: attr_number_get!("padding_right", 8),
-                        bottom: attr_number_get!("padding_bottom", 8),
-                        left: attr_number_get!("padding_left", 8),
-                    })
-                })?;
-
-            let margin = attrs
-                .remove("margin")
-                .map(|e| match e.parse() {
-                    Ok(val) => Ok(SidesValue {
-                        top: val,
-                        right: val,
-                        bottom: val,
-                        left: val,
-                    }),
-                    Err(err) => Err(ViewCompileError::AttributeParseError(
-                        AttributeParseError::InvalidIntValue {
-                            attribute_name: "padding".to_string(),
-                            attribute_value: e,
-                            err,
-                        },
-                    )),
-                })
-                .unwrap_or_else(|| {
-                    Ok(SidesValue {
-                        top: attr_number_get!("margin_top", 0),
-                        right: attr_number_get!("margin_right", 0),
-                        bottom: attr_number_get!("margin_bottom", 0),
-                        left: attr_number_get!("margin_left", 0),
-                    })
-                })?;
+        let margin = if attrs.contains_key("margin") {
+            let margin = attr_number_get!("margin", 0);
 
-            SWRSView {
-                background_color: if let Some(color) = attrs.remove("background_color") {
-                    parse_color(&*color, "background_color")?
-                } else {
-                    Color::from(0xFFFFFF)
-                },
-                height: if let Some(height) = attrs.remove("height") {
-                    match height.as_str() {
-                        "match_parent" => Size::MatchParent,
-                        "wrap_content" => Size::WrapContent,
-                        _ => Size::Fixed(height.parse().map_err(|err| {
-                            ViewCompileError::AttributeParseError(
-                                AttributeParseError::InvalidIntValue {
-                                    attribute_name: "height".to_string(),
-                                    attribute_value: height,
-                                    err,
-                                },
-                            )
-                        })?),
-                    }
-                } else {
-                    Size::WrapContent
-                },
-                width: if let Some(width) = attrs.remove("width") {
-                    match width.as_str() {
-                        "match_parent" => Size::MatchParent,
-                        "wrap_content" => Size::WrapContent,
-                        _ => Size::Fixed(width.parse().map_err(|err| {
-                            ViewCompileError::AttributeParseError(
-                                AttributeParseError::InvalidIntValue {
-                                    attribute_name: "width".to_string(),
-                                    attribute_value: width,
-                                    err,
-                                },
-                            )
-                        })?),
-                    }
-                } else {
-                    Size::WrapContent
-                },
-                padding,
-                margin,
-                weight: attr_number_get!("weight", 0),
-                weight_sum: attr_number_get!("weight_sum", 0),
-                layout_gravity: if let Some(layout_gravity) = attrs.remove("layout_gravity") {
-                    parse_gravity(&*layout_gravity, "layout_gravity")?
-                } else {
-                    Gravity::default()
-                },
-                children: if let Some(children) = parsed.children {
-                    children
-                        .into_iter()
-                        .enumerate()
-                        .map(|(index, v)| {
-                            compile(v, &*view_id, view.get_type_id() as i8, state).map_err(|e| {
-                                ViewCompileError::ChildCompileError {
-                                    index,
-                                    source: Box::new(e),
-                                }
-                            })
-                        })
-                        .collect::<Result<Vec<_>, _>>()?
-                } else {
-                    vec![]
-                },
-                raw: AndroidView::new_empty(
-                    view_id.as_str(),
-                    view.get_type_id(),
-                    parent_id,
-                    parent_type,
-                ),
-                id: view_id,
-                view: Ok(view),
+            SidesValue {
+                top: margin,
+                right: margin,
+                bottom: margin,
+                left: margin,
             }
         } else {
-            // when there's no attributes provided
-            let view = map_view_name_attrs(parsed.name, &mut HashMap::new())?;
-
-            SWRSView {
-                id: view_id.to_string(),
-                background_color: Color::from(0xffffff),
-                height: Size::WrapContent,
-                width: Size::WrapContent,
-                padding: SidesValue {
-                    top: 8,
-                    right: 8,
-                    bottom: 8,
-                    left: 8,
-                },
-                margin: SidesValue {
-                    top: 0,
-                    right: 0,
-                    bottom: 0,
-                    left: 0,
-                },
-                weight: 0,
-                weight_sum: 0,
-                layout_gravity: Default::default(),
-                children: vec![],
-                raw: AndroidView::new_empty(
-                    view_id.as_str(),
-                    view.get_type_id(),
-                    parent_id,
-                    parent_type,
-                ),
-                view: Ok(view),
+            SidesValue {
+                top: attr_number_get!("margin_top", 0),
+                right: attr_number_get!("margin_right", 0),
+                bottom: attr_number_get!("margin_bottom", 0),
+                left: attr_number_get!("margin_left", 0),
             }
+        };
+
+        let background_color = parse_attribute(
+            &mut attrs,
+            "background_color",
+            Color::from(0xFFFFFF),
+            errors,
+            |color, value_span| parse_color(&color, "background_color", &value_span),
+        );
+
+        let height = parse_attribute(
+            &mut attrs,
+            "height",
+            Size::WrapContent,
+            errors,
+            |height, value_span| parse_size(height, "height", value_span),
+        );
+
+        let width = parse_attribute(
+            &mut attrs,
+            "width",
+            Size::WrapContent,
+            errors,
+            |width, value_span| parse_size(width, "width", value_span),
+        );
+
+        let weight = attr_number_get!("weight", 0);
+        let weight_sum = attr_number_get!("weight_sum", 0);
+
+        let layout_gravity = parse_attribute(
+            &mut attrs,
+            "layout_gravity",
+            Gravity::default(),
+            errors,
+            |layout_gravity, value_span| {
+                parse_gravity(&layout_gravity, "layout_gravity", &value_span)
+            },
+        );
+
+        // children are still compiled when this view is unknown, to check them for errors
+        let type_id = view.as_ref().map(|view| view.get_type_id()).unwrap_or(0);
+
+        let children = parsed
+            .children
+            .map(|children| {
+                children
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(index, child)| {
+                        let span = child.name_span.clone();
+                        let mut child_errors = Vec::new();
+                        let child =
+                            compile(child, &view_id, type_id as i8, state, &mut child_errors);
+
+                        errors.extend(child_errors.into_iter().map(|err| {
+                            ViewCompileError::ChildCompileError {
+                                index,
+                                source: Box::new(err),
+                                span: span.clone(),
+                            }
+                        }));
+
+                        child
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let view = view?;
+
+        Some(SWRSView {
+            background_color,
+            height,
+            width,
+            padding,
+            margin,
+            weight,
+            weight_sum,
+            layout_gravity,
+            children,
+            raw: AndroidView::new_empty(
+                view_id.as_str(),
+                view.get_type_id(),
+                parent_id,
+                parent_type,
+            ),
+            id: view_id,
+            view: Ok(view),
         })
     }
 
+    let mut errors = Vec::new();
+
     // the root parent id of sketchware is "root"
-    compile(parsed, "root", 0, &mut 0u32)
+    match compile(parsed, "root", 0, &mut 0u32, &mut errors) {
+        Some(view) if errors.is_empty() => Ok(view),
+        _ => Err(CompileErrors(errors)),
+    }
+}
+
+// the attributes that refer to a resource, along with the views that have them
+const RESOURCE_ATTRIBUTES: &[(&str, ResourceKind, &[&str])] = &[
+    (
+        "image",
+        ResourceKind::Image,
+        &["ImageView", "FloatingActionButton"],
+    ),
+    (
+        "text_font",
+        ResourceKind::Font,
+        &["TextView", "EditText", "CheckBox", "Switch"],
+    ),
+];
+
+/// Checks whether the resources and custom views referred by the attributes of a parsed view and
+/// its children exist, every references to something that doesn't exist are returned as errors.
+pub fn check_resources(
+    parsed: &View,
+    resources: &ResourceNames,
+) -> Result<(), CompileErrors<ViewCompileError>> {
+    fn check(parsed: &View, resources: &ResourceNames, errors: &mut Vec<ViewCompileError>) {
+        for (attribute, kind, views) in RESOURCE_ATTRIBUTES {
+            if !views.contains(&parsed.name.as_str()) {
+                continue;
+            }
+
+            let Some(attr) = parsed
+                .attributes
+                .as_ref()
+                .and_then(|attrs| attrs.get(*attribute))
+            else {
+                continue;
+            };
+
+            // sketchware's own font is always there
+            if *kind == ResourceKind::Font && attr.value == "default_font" {
+                continue;
+            }
+
+            if !resources.contains(*kind, &attr.value) {
+                errors.push(ViewCompileError::UnknownResource {
+                    kind: *kind,
+                    name: attr.value.clone(),
+                    span: attr.value_span.clone(),
+                });
+            }
+        }
+
+        let custom_view = parsed
+            .attributes
+            .as_ref()
+            .and_then(|attrs| attrs.get("custom_view"))
+            .filter(|_| parsed.name == "ListView");
+
+        if let Some(attr) = custom_view {
+            if !resources.custom_views.contains(&attr.value) {
+                errors.push(ViewCompileError::UnknownCustomView {
+                    name: attr.value.clone(),
+                    span: attr.value_span.clone(),
+                });
+            }
+        }
+
+        for child in parsed.children.iter().flat_map(|children| children.iter()) {
+            check(child, resources, errors);
+        }
+    }
+
+    let mut errors = Vec::new();
+    check(parsed, resources, &mut errors);
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(CompileErrors(errors))
+    }
+}
+
+// parses an attribute with `parse` if it's specified, otherwise `default` is used. an error is
+// collected into `errors` and `default` is used in place of the invalid value, so the rest of the
+// attributes can still be checked
+fn parse_attribute<T>(
+    attributes: &mut LinkedHashMap<String, Attribute>,
+    name: &str,
+    default: T,
+    errors: &mut Vec<ViewCompileError>,
+    parse: impl FnOnce(String, Span) -> Result<T, ViewCompileError>,
+) -> T {
+    let Some(Attribute {
+        value, value_span, ..
+    }) = attributes.remove(name)
+    else {
+        return default;
+    };
+
+    parse(value, value_span).unwrap_or_else(|err| {
+        errors.push(err);
+        default
+    })
+}
+
+// parses the height or the width of a view
+fn parse_size(size: String, attr_name: &str, span: Span) -> Result<Size, ViewCompileError> {
+    Ok(match size.as_str() {
+        "match_parent" => Size::MatchParent,
+        "wrap_content" => Size::WrapContent,
+        _ => Size::Fixed(
+            size.parse()
+                .map_err(|err| ViewCompileError::AttributeParseError {
+                    error: AttributeParseError::InvalidIntValue {
+                        attribute_name: attr_name.to_string(),
+                        attribute_value: size,
+                        err,
+                    },
+                    span,
+                })?,
+        ),
+    })
 }
 
 #[derive(Debug, Error)]
 pub enum ViewCompileError {
     #[error("unknown view: `{view_name}`")]
-    UnknownView { view_name: String },
+    UnknownView { view_name: String, span: Span },
 
     #[error("error on compiling a child at index {index}")]
     ChildCompileError {
         index: usize,
         source: Box<ViewCompileError>,
+        span: Span,
+    },
+
+    #[error("error on attribute parsing: {error}")]
+    AttributeParseError {
+        error: AttributeParseError,
+        span: Span,
     },
 
-    #[error("error on attribute parsing: {0}")]
-    AttributeParseError(AttributeParseError),
+    #[error("{kind} `{name}` doesn't exist in the project resources")]
+    UnknownResource {
+        kind: ResourceKind,
+        name: String,
+        span: Span,
+    },
+
+    #[error("custom view `{name}` isn't declared in the manifest")]
+    UnknownCustomView { name: String, span: Span },
+}
+
+impl ViewCompileError {
+    /// Retrieves the span of where this error came from; the view name of an unknown view or a
+    /// child that failed to compile, or the value of an attribute that failed to be parsed
+    pub fn span(&self) -> &Span {
+        match self {
+            ViewCompileError::UnknownView { span, .. }
+            | ViewCompileError::ChildCompileError { span, .. }
+            | ViewCompileError::AttributeParseError { span, .. }
+            | ViewCompileError::UnknownResource { span, .. }
+            | ViewCompileError::UnknownCustomView { span, .. } => span,
+        }
+    }
 }
 
 /// This function maps attributes depending on the view name into the enum [`ViewType`].
@@ -242,554 +361,749 @@ pub enum ViewCompileError {
 /// CheckBoxes
 fn map_view_name_attrs(
     name: String,
-    attributes: &mut HashMap<String, String>,
-) -> Result<ViewType, ViewCompileError> {
-    Ok(match name.as_str() {
+    name_span: Span,
+    attributes: &mut LinkedHashMap<String, Attribute>,
+    errors: &mut Vec<ViewCompileError>,
+) -> Option<ViewType> {
+    Some(match name.as_str() {
         "LinearLayout" => ViewType::LinearLayout {
-            orientation: if let Some(orientation) = attributes.remove("orientation") {
-                match orientation.as_str() {
-                    "vertical" => Orientation::Vertical,
-                    "horizontal" => Orientation::Horizontal,
-                    _ => {
-                        return Err(ViewCompileError::AttributeParseError(
-                            AttributeParseError::InvalidAttributeValue {
-                                attribute_name: "orientation".to_string(),
-                                attribute_value: orientation,
-                                possible_values: vec![
-                                    "vertical".to_string(),
-                                    "horizontal".to_string(),
-                                ],
-                            },
-                        ))
-                    }
-                }
-            } else {
-                Orientation::Vertical
-            }, // default is vertical if no orientation specified
-
-            gravity: if let Some(gravity) = attributes.remove("gravity") {
-                parse_gravity(&*gravity, "gravity")?
-            } else {
-                Gravity(gravity::NONE)
-            },
+            orientation: parse_attribute(
+                attributes,
+                "orientation",
+                Orientation::Vertical,
+                errors,
+                |orientation, value_span| {
+                    Ok(match orientation.as_str() {
+                        "vertical" => Orientation::Vertical,
+                        "horizontal" => Orientation::Horizontal,
+                        _ => {
+                            return Err(ViewCompileError::AttributeParseError {
+                                error: AttributeParseError::InvalidAttributeValue {
+                                    attribute_name: "orientation".to_string(),
+                                    attribute_value: orientation,
+                                    possible_values: vec![
+                                        "vertical".to_string(),
+                                        "horizontal".to_string(),
+                                    ],
+                                },
+                                span: value_span,
+                            })
+                        }
+                    })
+                },
+            ), // default is vertical if no orientation specified
+
+            gravity: parse_attribute(
+                attributes,
+                "gravity",
+                Gravity(gravity::NONE),
+                errors,
+                |gravity, value_span| parse_gravity(&gravity, "gravity", &value_span),
+            ),
         },
         "ScrollView" => ViewType::ScrollView {
-            orientation: if let Some(orientation) = attributes.remove("orientation") {
-                match orientation.as_str() {
-                    "vertical" => Orientation::Vertical,
-                    "horizontal" => Orientation::Horizontal,
-                    _ => {
-                        return Err(ViewCompileError::AttributeParseError(
-                            AttributeParseError::InvalidAttributeValue {
-                                attribute_name: "orientation".to_string(),
-                                attribute_value: orientation,
-                                possible_values: vec![
-                                    "vertical".to_string(),
-                                    "horizontal".to_string(),
-                                ],
-                            },
-                        ))
-                    }
-                }
-            } else {
-                Orientation::Vertical
-            }, // default is vertical if no orientation specified
-
-            gravity: if let Some(gravity) = attributes.remove("gravity") {
-                parse_gravity(&*gravity, "gravity")?
-            } else {
-                Gravity(gravity::NONE)
-            },
+            orientation: parse_attribute(
+                attributes,
+                "orientation",
+                Orientation::Vertical,
+                errors,
+                |orientation, value_span| {
+                    Ok(match orientation.as_str() {
+                        "vertical" => Orientation::Vertical,
+                        "horizontal" => Orientation::Horizontal,
+                        _ => {
+                            return Err(ViewCompileError::AttributeParseError {
+                                error: AttributeParseError::InvalidAttributeValue {
+                                    attribute_name: "orientation".to_string(),
+                                    attribute_value: orientation,
+                                    possible_values: vec![
+                                        "vertical".to_string(),
+                                        "horizontal".to_string(),
+                                    ],
+                                },
+                                span: value_span,
+                            })
+                        }
+                    })
+                },
+            ), // default is vertical if no orientation specified
+
+            gravity: parse_attribute(
+                attributes,
+                "gravity",
+                Gravity(gravity::NONE),
+                errors,
+                |gravity, value_span| parse_gravity(&gravity, "gravity", &value_span),
+            ),
         },
         "Button" => ViewType::Button {
             text: attributes
                 .remove("text")
+                .map(|attr| attr.value)
                 .unwrap_or_else(|| "Button".to_string()),
 
-            text_color: if let Some(text_color) = attributes.remove("text_color") {
-                parse_color(&*text_color, "text_color")?
-            } else {
-                Color::from(0x000000)
-            },
-
-            text_size: if let Some(text_size) = attributes.remove("text_size") {
-                text_size.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidIntValue {
-                        attribute_name: "text_size".to_string(),
-                        attribute_value: text_size,
-                        err,
-                    })
-                })?
-            } else {
-                12
-            },
-
-            text_style: if let Some(text_style) = attributes.remove("text_style") {
-                parse_text_style(&*text_style, "text_style")?
-            } else {
-                TextType::Normal
-            },
+            text_color: parse_attribute(
+                attributes,
+                "text_color",
+                Color::from(0x000000),
+                errors,
+                |text_color, value_span| parse_color(&text_color, "text_color", &value_span),
+            ),
+
+            text_size: parse_attribute(
+                attributes,
+                "text_size",
+                12,
+                errors,
+                |text_size, value_span| {
+                    text_size
+                        .parse()
+                        .map_err(|err| ViewCompileError::AttributeParseError {
+                            error: AttributeParseError::InvalidIntValue {
+                                attribute_name: "text_size".to_string(),
+                                attribute_value: text_size,
+                                err,
+                            },
+                            span: value_span,
+                        })
+                },
+            ),
+
+            text_style: parse_attribute(
+                attributes,
+                "text_style",
+                TextType::Normal,
+                errors,
+                |text_style, value_span| parse_text_style(&text_style, "text_style", &value_span),
+            ),
         },
         "TextView" => ViewType::TextView {
             text: attributes
                 .remove("text")
+                .map(|attr| attr.value)
                 .unwrap_or_else(|| "TextView".to_string()),
 
-            text_color: if let Some(text_color) = attributes.remove("text_color") {
-                parse_color(&*text_color, "text_color")?
-            } else {
-                Color::from(0x000000)
-            },
-
-            text_size: if let Some(text_size) = attributes.remove("text_size") {
-                text_size.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidIntValue {
-                        attribute_name: "text_size".to_string(),
-                        attribute_value: text_size,
-                        err,
-                    })
-                })?
-            } else {
-                12
-            },
-
-            single_line: if let Some(single_line) = attributes.remove("single_line") {
-                single_line.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidBoolValue {
-                        attribute_name: "single_line".to_string(),
-                        attribute_value: single_line,
-                        err,
-                    })
-                })?
-            } else {
-                false
-            },
+            text_color: parse_attribute(
+                attributes,
+                "text_color",
+                Color::from(0x000000),
+                errors,
+                |text_color, value_span| parse_color(&text_color, "text_color", &value_span),
+            ),
+
+            text_size: parse_attribute(
+                attributes,
+                "text_size",
+                12,
+                errors,
+                |text_size, value_span| {
+                    text_size
+                        .parse()
+                        .map_err(|err| ViewCompileError::AttributeParseError {
+                            error: AttributeParseError::InvalidIntValue {
+                                attribute_name: "text_size".to_string(),
+                                attribute_value: text_size,
+                                err,
+                            },
+                            span: value_span,
+                        })
+                },
+            ),
+
+            single_line: parse_attribute(
+                attributes,
+                "single_line",
+                false,
+                errors,
+                |single_line, value_span| {
+                    single_line
+                        .parse()
+                        .map_err(|err| ViewCompileError::AttributeParseError {
+                            error: AttributeParseError::InvalidBoolValue {
+                                attribute_name: "single_line".to_string(),
+                                attribute_value: single_line,
+                                err,
+                            },
+                            span: value_span,
+                        })
+                },
+            ),
 
-            // todo: validation with the resources defined in manifest soon
+            // resources are checked separately, see `check_resources`
             text_font: attributes
-                .remove("text")
+                .remove("text_font")
+                .map(|attr| attr.value)
                 .unwrap_or_else(|| "default_font".to_string()),
 
-            text_style: if let Some(text_style) = attributes.remove("text_style") {
-                parse_text_style(&*text_style, "text_style")?
-            } else {
-                TextType::Normal
-            },
-
-            lines: if let Some(lines) = attributes.remove("lines") {
-                lines.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidIntValue {
-                        attribute_name: "lines".to_string(),
-                        attribute_value: lines,
-                        err,
+            text_style: parse_attribute(
+                attributes,
+                "text_style",
+                TextType::Normal,
+                errors,
+                |text_style, value_span| parse_text_style(&text_style, "text_style", &value_span),
+            ),
+
+            lines: parse_attribute(attributes, "lines", 0, errors, |lines, value_span| {
+                lines
+                    .parse()
+                    .map_err(|err| ViewCompileError::AttributeParseError {
+                        error: AttributeParseError::InvalidIntValue {
+                            attribute_name: "lines".to_string(),
+                            attribute_value: lines,
+                            err,
+                        },
+                        span: value_span,
                     })
-                })?
-            } else {
-                0
-            },
+            }),
         },
         "EditText" => ViewType::EditText {
             text: attributes
                 .remove("text")
+                .map(|attr| attr.value)
                 .unwrap_or_else(|| "EditText".to_string()),
 
-            text_color: if let Some(text_color) = attributes.remove("text_color") {
-                parse_color(&*text_color, "text_color")?
-            } else {
-                Color::from(0x000000)
-            },
-
-            text_size: if let Some(text_size) = attributes.remove("text_size") {
-                text_size.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidIntValue {
-                        attribute_name: "text_size".to_string(),
-                        attribute_value: text_size,
-                        err,
-                    })
-                })?
-            } else {
-                12
-            },
-
-            single_line: if let Some(single_line) = attributes.remove("single_line") {
-                single_line.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidBoolValue {
-                        attribute_name: "single_line".to_string(),
-                        attribute_value: single_line,
-                        err,
-                    })
-                })?
-            } else {
-                false
-            },
+            text_color: parse_attribute(
+                attributes,
+                "text_color",
+                Color::from(0x000000),
+                errors,
+                |text_color, value_span| parse_color(&text_color, "text_color", &value_span),
+            ),
+
+            text_size: parse_attribute(
+                attributes,
+                "text_size",
+                12,
+                errors,
+                |text_size, value_span| {
+                    text_size
+                        .parse()
+                        .map_err(|err| ViewCompileError::AttributeParseError {
+                            error: AttributeParseError::InvalidIntValue {
+                                attribute_name: "text_size".to_string(),
+                                attribute_value: text_size,
+                                err,
+                            },
+                            span: value_span,
+                        })
+                },
+            ),
+
+            single_line: parse_attribute(
+                attributes,
+                "single_line",
+                false,
+                errors,
+                |single_line, value_span| {
+                    single_line
+                        .parse()
+                        .map_err(|err| ViewCompileError::AttributeParseError {
+                            error: AttributeParseError::InvalidBoolValue {
+                                attribute_name: "single_line".to_string(),
+                                attribute_value: single_line,
+                                err,
+                            },
+                            span: value_span,
+                        })
+                },
+            ),
 
-            // todo: validation with the resources defined in manifest soon
+            // resources are checked separately, see `check_resources`
             text_font: attributes
-                .remove("text")
+                .remove("text_font")
+                .map(|attr| attr.value)
                 .unwrap_or_else(|| "default_font".to_string()),
 
-            text_style: if let Some(text_style) = attributes.remove("text_style") {
-                parse_text_style(&*text_style, "text_style")?
-            } else {
-                TextType::Normal
-            },
-
-            lines: if let Some(lines) = attributes.remove("lines") {
-                lines.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidIntValue {
-                        attribute_name: "lines".to_string(),
-                        attribute_value: lines,
-                        err,
+            text_style: parse_attribute(
+                attributes,
+                "text_style",
+                TextType::Normal,
+                errors,
+                |text_style, value_span| parse_text_style(&text_style, "text_style", &value_span),
+            ),
+
+            lines: parse_attribute(attributes, "lines", 0, errors, |lines, value_span| {
+                lines
+                    .parse()
+                    .map_err(|err| ViewCompileError::AttributeParseError {
+                        error: AttributeParseError::InvalidIntValue {
+                            attribute_name: "lines".to_string(),
+                            attribute_value: lines,
+                            err,
+                        },
+                        span: value_span,
                     })
-                })?
-            } else {
-                0
-            },
+            }),
 
-            hint: attributes.remove("hint").unwrap_or_else(|| String::new()),
-
-            hint_color: if let Some(hint_color) = attributes.remove("hint_color") {
-                parse_color(&*hint_color, "hint_color")?
-            } else {
-                Color::from(0x607d8b)
-            }, // #607d8b
-
-            ime_option: if let Some(ime_option) = attributes.remove("ime_option") {
-                match ime_option.as_str() {
-                    "normal" => ImeOption::Normal,
-                    "none" => ImeOption::None,
-                    "go" => ImeOption::Go,
-                    "search" => ImeOption::Search,
-                    "send" => ImeOption::Send,
-                    "next" => ImeOption::Next,
-                    "done" => ImeOption::Done,
-                    _ => {
-                        return Err(ViewCompileError::AttributeParseError(
-                            AttributeParseError::InvalidAttributeValue {
-                                attribute_name: "ime_option".to_string(),
-                                attribute_value: ime_option,
-                                possible_values: vec![
-                                    "normal".to_string(),
-                                    "none".to_string(),
-                                    "go".to_string(),
-                                    "search".to_string(),
-                                    "send".to_string(),
-                                    "next".to_string(),
-                                    "done".to_string(),
-                                ],
-                            },
-                        ))
-                    }
-                }
-            } else {
-                ImeOption::Normal
-            },
+            hint: attributes
+                .remove("hint")
+                .map(|attr| attr.value)
+                .unwrap_or_else(|| String::new()),
+
+            hint_color: parse_attribute(
+                attributes,
+                "hint_color",
+                Color::from(0x607d8b),
+                errors,
+                |hint_color, value_span| parse_color(&hint_color, "hint_color", &value_span),
+            ), // #607d8b
+
+            ime_option: parse_attribute(
+                attributes,
+                "ime_option",
+                ImeOption::Normal,
+                errors,
+                |ime_option, value_span| {
+                    Ok(match ime_option.as_str() {
+                        "normal" => ImeOption::Normal,
+                        "none" => ImeOption::None,
+                        "go" => ImeOption::Go,
+                        "search" => ImeOption::Search,
+                        "send" => ImeOption::Send,
+                        "next" => ImeOption::Next,
+                        "done" => ImeOption::Done,
+                        _ => {
+                            return Err(ViewCompileError::AttributeParseError {
+                                error: AttributeParseError::InvalidAttributeValue {
+                                    attribute_name: "ime_option".to_string(),
+                                    attribute_value: ime_option,
+                                    possible_values: vec![
+                                        "normal".to_string(),
+                                        "none".to_string(),
+                                        "go".to_string(),
+                                        "search".to_string(),
+                                        "send".to_string(),
+                                        "next".to_string(),
+                                        "done".to_string(),
+                                    ],
+                                },
+                                span: value_span,
+                            })
+                        }
+                    })
+                },
+            ),
 
             // see docs/notes.md#Full InputType support?
-            input_type: if let Some(input_type) = attributes.remove("input_type") {
-                // yes i am too lazy to do `|` shayts, it's only used in one item anyway
-                match input_type.as_str() {
-                    "decimal" => InputType::NumberDecimal,
-                    "signed" => InputType::NumberSigned,
-                    "decimal_signed" => InputType::NumberSignedDecimal,
-                    "text" => InputType::Text,
-                    "password" => InputType::Password,
-                    "phone" => InputType::Phone,
-                    _ => {
-                        return Err(ViewCompileError::AttributeParseError(
-                            AttributeParseError::InvalidAttributeValue {
-                                attribute_name: "input_type".to_string(),
-                                attribute_value: input_type,
-                                possible_values: vec![
-                                    "decimal".to_string(),
-                                    "signed".to_string(),
-                                    "decimal_signed".to_string(),
-                                    "text".to_string(),
-                                    "password".to_string(),
-                                    "phone".to_string(),
-                                ],
-                            },
-                        ))
-                    }
-                }
-            } else {
-                InputType::Text
-            },
+            input_type: parse_attribute(
+                attributes,
+                "input_type",
+                InputType::Text,
+                errors,
+                |input_type, value_span| {
+                    Ok(
+                        // yes i am too lazy to do `|` shayts, it's only used in one item anyway
+                        match input_type.as_str() {
+                            "decimal" => InputType::NumberDecimal,
+                            "signed" => InputType::NumberSigned,
+                            "decimal_signed" => InputType::NumberSignedDecimal,
+                            "text" => InputType::Text,
+                            "password" => InputType::Password,
+                            "phone" => InputType::Phone,
+                            _ => {
+                                return Err(ViewCompileError::AttributeParseError {
+                                    error: AttributeParseError::InvalidAttributeValue {
+                                        attribute_name: "input_type".to_string(),
+                                        attribute_value: input_type,
+                                        possible_values: vec![
+                                            "decimal".to_string(),
+                                            "signed".to_string(),
+                                            "decimal_signed".to_string(),
+                                            "text".to_string(),
+                                            "password".to_string(),
+                                            "phone".to_string(),
+                                        ],
+                                    },
+                                    span: value_span,
+                                })
+                            }
+                        },
+                    )
+                },
+            ),
         },
         "ImageView" => ViewType::ImageView {
-            // todo: validation with the resources defined in manifest soon
-            image_res_name: attributes.remove("images").unwrap_or_else(|| String::new()),
-            image_scale_type: if let Some(scale_type) = attributes.remove("scale_type") {
-                match scale_type.as_str() {
-                    "center" => ImageScaleType::Center,
-                    "fit_xy" => ImageScaleType::FitXy,
-                    "fit_start" => ImageScaleType::FitStart,
-                    "fit_end" => ImageScaleType::FitEnd,
-                    "center_crop" => ImageScaleType::CenterCrop,
-                    "center_inside" => ImageScaleType::CenterInside,
-                    _ => {
-                        return Err(ViewCompileError::AttributeParseError(
-                            AttributeParseError::InvalidAttributeValue {
-                                attribute_name: "scale_type".to_string(),
-                                attribute_value: scale_type,
-                                possible_values: vec![
-                                    "center".to_string(),
-                                    "fit_xy".to_string(),
-                                    "fit_start".to_string(),
-                                    "fit_end".to_string(),
-                                    "center_crop".to_string(),
-                                    "center_inside".to_string(),
-                                ],
-                            },
-                        ))
-                    }
-                }
-            } else {
-                ImageScaleType::Center
-            },
+            // resources are checked separately, see `check_resources`
+            image_res_name: attributes
+                .remove("image")
+                .map(|attr| attr.value)
+                .unwrap_or_else(|| String::new()),
+            image_scale_type: parse_attribute(
+                attributes,
+                "scale_type",
+                ImageScaleType::Center,
+                errors,
+                |scale_type, value_span| {
+                    Ok(match scale_type.as_str() {
+                        "center" => ImageScaleType::Center,
+                        "fit_xy" => ImageScaleType::FitXy,
+                        "fit_start" => ImageScaleType::FitStart,
+                        "fit_end" => ImageScaleType::FitEnd,
+                        "center_crop" => ImageScaleType::CenterCrop,
+                        "center_inside" => ImageScaleType::CenterInside,
+                        _ => {
+                            return Err(ViewCompileError::AttributeParseError {
+                                error: AttributeParseError::InvalidAttributeValue {
+                                    attribute_name: "scale_type".to_string(),
+                                    attribute_value: scale_type,
+                                    possible_values: vec![
+                                        "center".to_string(),
+                                        "fit_xy".to_string(),
+                                        "fit_start".to_string(),
+                                        "fit_end".to_string(),
+                                        "center_crop".to_string(),
+                                        "center_inside".to_string(),
+                                    ],
+                                },
+                                span: value_span,
+                            })
+                        }
+                    })
+                },
+            ),
         },
         "WebView" => ViewType::WebView, // literally
         "ProgressBar" => ViewType::ProgressBar {
-            max_progress: if let Some(max_progress) = attributes.remove("max_progress") {
-                max_progress.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidIntValue {
-                        attribute_name: "max_progress".to_string(),
-                        attribute_value: max_progress,
-                        err,
-                    })
-                })?
-            } else {
-                100
-            },
-
-            progress: if let Some(progress) = attributes.remove("progress") {
-                progress.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidIntValue {
-                        attribute_name: "progress".to_string(),
-                        attribute_value: progress,
-                        err,
-                    })
-                })?
-            } else {
-                0
-            },
-
-            indeterminate: if let Some(indeterminate) = attributes.remove("indeterminate") {
-                indeterminate.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidBoolValue {
-                        attribute_name: "indeterminate".to_string(),
-                        attribute_value: indeterminate,
-                        err,
+            max_progress: parse_attribute(
+                attributes,
+                "max_progress",
+                100,
+                errors,
+                |max_progress, value_span| {
+                    max_progress
+                        .parse()
+                        .map_err(|err| ViewCompileError::AttributeParseError {
+                            error: AttributeParseError::InvalidIntValue {
+                                attribute_name: "max_progress".to_string(),
+                                attribute_value: max_progress,
+                                err,
+                            },
+                            span: value_span,
+                        })
+                },
+            ),
+
+            progress: parse_attribute(attributes, "progress", 0, errors, |progress, value_span| {
+                progress
+                    .parse()
+                    .map_err(|err| ViewCompileError::AttributeParseError {
+                        error: AttributeParseError::InvalidIntValue {
+                            attribute_name: "progress".to_string(),
+                            attribute_value: progress,
+                            err,
+                        },
+                        span: value_span,
                     })
-                })?
-            } else {
-                false
-            },
-
-            progress_style: if let Some(progress_style) = attributes.remove("progress_style") {
-                match progress_style.as_str() {
-                    "horizontal" => "?android:progressBarStyleHorizontal",
-                    "circular" | "circle" => "?android:progressBarStyle",
-                    _ => {
-                        return Err(ViewCompileError::AttributeParseError(
-                            AttributeParseError::InvalidAttributeValue {
-                                attribute_name: "progress_style".to_string(),
-                                attribute_value: progress_style,
-                                possible_values: vec![
-                                    "horizontal".to_string(),
-                                    "circular".to_string(),
-                                    "circle".to_string(),
-                                ],
+            }),
+
+            indeterminate: parse_attribute(
+                attributes,
+                "indeterminate",
+                false,
+                errors,
+                |indeterminate, value_span| {
+                    indeterminate
+                        .parse()
+                        .map_err(|err| ViewCompileError::AttributeParseError {
+                            error: AttributeParseError::InvalidBoolValue {
+                                attribute_name: "indeterminate".to_string(),
+                                attribute_value: indeterminate,
+                                err,
                             },
-                        ))
-                    }
-                }
-            } else {
-                "?android:progressBarStyle"
-            }
+                            span: value_span,
+                        })
+                },
+            ),
+
+            progress_style: parse_attribute(
+                attributes,
+                "progress_style",
+                "?android:progressBarStyle",
+                errors,
+                |progress_style, value_span| {
+                    Ok(match progress_style.as_str() {
+                        "horizontal" => "?android:progressBarStyleHorizontal",
+                        "circular" | "circle" => "?android:progressBarStyle",
+                        _ => {
+                            return Err(ViewCompileError::AttributeParseError {
+                                error: AttributeParseError::InvalidAttributeValue {
+                                    attribute_name: "progress_style".to_string(),
+                                    attribute_value: progress_style,
+                                    possible_values: vec![
+                                        "horizontal".to_string(),
+                                        "circular".to_string(),
+                                        "circle".to_string(),
+                                    ],
+                                },
+                                span: value_span,
+                            })
+                        }
+                    })
+                },
+            )
             .to_string(),
         },
         "ListView" => ViewType::ListView {
-            divider_height: if let Some(divider_height) = attributes.remove("divider_height") {
-                divider_height.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidIntValue {
-                        attribute_name: "divider_height".to_string(),
-                        attribute_value: divider_height,
-                        err,
-                    })
-                })?
-            } else {
-                0
-            },
+            divider_height: parse_attribute(
+                attributes,
+                "divider_height",
+                0,
+                errors,
+                |divider_height, value_span| {
+                    divider_height
+                        .parse()
+                        .map_err(|err| ViewCompileError::AttributeParseError {
+                            error: AttributeParseError::InvalidIntValue {
+                                attribute_name: "divider_height".to_string(),
+                                attribute_value: divider_height,
+                                err,
+                            },
+                            span: value_span,
+                        })
+                },
+            ),
 
-            // todo: validation with the resources defined in manifest soon
+            // custom views are checked separately, see `check_resources`
             custom_view: attributes
                 .remove("custom_view")
+                .map(|attr| attr.value)
                 .unwrap_or_else(|| String::new()),
         },
         "Spinner" => ViewType::Spinner {
-            spinner_mode: if let Some(spinner_mode) = attributes.remove("spinner_mode") {
-                match spinner_mode.as_str() {
-                    "dropdown" => SpinnerMode::Dropdown,
-                    "dialog" => SpinnerMode::Dialog,
-                    _ => {
-                        return Err(ViewCompileError::AttributeParseError(
-                            AttributeParseError::InvalidAttributeValue {
-                                attribute_name: "spinner_mode".to_string(),
-                                attribute_value: spinner_mode,
-                                possible_values: vec!["dropdown".to_string(), "dialog".to_string()],
-                            },
-                        ))
-                    }
-                }
-            } else {
-                SpinnerMode::Dropdown
-            },
+            spinner_mode: parse_attribute(
+                attributes,
+                "spinner_mode",
+                SpinnerMode::Dropdown,
+                errors,
+                |spinner_mode, value_span| {
+                    Ok(match spinner_mode.as_str() {
+                        "dropdown" => SpinnerMode::Dropdown,
+                        "dialog" => SpinnerMode::Dialog,
+                        _ => {
+                            return Err(ViewCompileError::AttributeParseError {
+                                error: AttributeParseError::InvalidAttributeValue {
+                                    attribute_name: "spinner_mode".to_string(),
+                                    attribute_value: spinner_mode,
+                                    possible_values: vec![
+                                        "dropdown".to_string(),
+                                        "dialog".to_string(),
+                                    ],
+                                },
+                                span: value_span,
+                            })
+                        }
+                    })
+                },
+            ),
         },
         "CheckBox" => ViewType::CheckBox {
-            checked: if let Some(checked) = attributes.remove("checked") {
-                checked.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidBoolValue {
-                        attribute_name: "checked".to_string(),
-                        attribute_value: checked,
-                        err,
-                    })
-                })?
-            } else {
-                false
-            },
+            checked: parse_attribute(
+                attributes,
+                "checked",
+                false,
+                errors,
+                |checked, value_span| {
+                    checked
+                        .parse()
+                        .map_err(|err| ViewCompileError::AttributeParseError {
+                            error: AttributeParseError::InvalidBoolValue {
+                                attribute_name: "checked".to_string(),
+                                attribute_value: checked,
+                                err,
+                            },
+                            span: value_span,
+                        })
+                },
+            ),
 
             text: attributes
                 .remove("text")
+                .map(|attr| attr.value)
                 .unwrap_or_else(|| "CheckBox".to_string()),
 
-            text_color: if let Some(text_color) = attributes.remove("text_color") {
-                parse_color(&*text_color, "text_color")?
-            } else {
-                Color::from(0x000000)
-            },
-
-            text_size: if let Some(text_size) = attributes.remove("text_size") {
-                text_size.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidIntValue {
-                        attribute_name: "text_size".to_string(),
-                        attribute_value: text_size,
-                        err,
-                    })
-                })?
-            } else {
-                12
-            },
+            text_color: parse_attribute(
+                attributes,
+                "text_color",
+                Color::from(0x000000),
+                errors,
+                |text_color, value_span| parse_color(&text_color, "text_color", &value_span),
+            ),
+
+            text_size: parse_attribute(
+                attributes,
+                "text_size",
+                12,
+                errors,
+                |text_size, value_span| {
+                    text_size
+                        .parse()
+                        .map_err(|err| ViewCompileError::AttributeParseError {
+                            error: AttributeParseError::InvalidIntValue {
+                                attribute_name: "text_size".to_string(),
+                                attribute_value: text_size,
+                                err,
+                            },
+                            span: value_span,
+                        })
+                },
+            ),
 
             text_font: attributes
-                .remove("text")
+                .remove("text_font")
+                .map(|attr| attr.value)
                 .unwrap_or_else(|| "default_font".to_string()),
 
-            text_style: if let Some(text_style) = attributes.remove("text_style") {
-                parse_text_style(&*text_style, "text_style")?
-            } else {
-                TextType::Normal
-            },
+            text_style: parse_attribute(
+                attributes,
+                "text_style",
+                TextType::Normal,
+                errors,
+                |text_style, value_span| parse_text_style(&text_style, "text_style", &value_span),
+            ),
         },
         "Switch" => ViewType::Switch {
-            checked: if let Some(checked) = attributes.remove("checked") {
-                checked.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidBoolValue {
-                        attribute_name: "checked".to_string(),
-                        attribute_value: checked,
-                        err,
-                    })
-                })?
-            } else {
-                false
-            },
+            checked: parse_attribute(
+                attributes,
+                "checked",
+                false,
+                errors,
+                |checked, value_span| {
+                    checked
+                        .parse()
+                        .map_err(|err| ViewCompileError::AttributeParseError {
+                            error: AttributeParseError::InvalidBoolValue {
+                                attribute_name: "checked".to_string(),
+                                attribute_value: checked,
+                                err,
+                            },
+                            span: value_span,
+                        })
+                },
+            ),
 
             text: attributes
                 .remove("text")
+                .map(|attr| attr.value)
                 .unwrap_or_else(|| "Switch".to_string()),
 
-            text_color: if let Some(text_color) = attributes.remove("text_color") {
-                parse_color(&*text_color, "text_color")?
-            } else {
-                Color::from(0x000000)
-            },
-
-            text_size: if let Some(text_size) = attributes.remove("text_size") {
-                text_size.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidIntValue {
-                        attribute_name: "text_size".to_string(),
-                        attribute_value: text_size,
-                        err,
-                    })
-                })?
-            } else {
-                12
-            },
+            text_color: parse_attribute(
+                attributes,
+                "text_color",
+                Color::from(0x000000),
+                errors,
+                |text_color, value_span| parse_color(&text_color, "text_color", &value_span),
+            ),
+
+            text_size: parse_attribute(
+                attributes,
+                "text_size",
+                12,
+                errors,
+                |text_size, value_span| {
+                    text_size
+                        .parse()
+                        .map_err(|err| ViewCompileError::AttributeParseError {
+                            error: AttributeParseError::InvalidIntValue {
+                                attribute_name: "text_size".to_string(),
+                                attribute_value: text_size,
+                                err,
+                            },
+                            span: value_span,
+                        })
+                },
+            ),
 
             text_font: attributes
-                .remove("text")
+                .remove("text_font")
+                .map(|attr| attr.value)
                 .unwrap_or_else(|| "default_font".to_string()),
 
-            text_style: if let Some(text_style) = attributes.remove("text_style") {
-                parse_text_style(&*text_style, "text_style")?
-            } else {
-                TextType::Normal
-            },
+            text_style: parse_attribute(
+                attributes,
+                "text_style",
+                TextType::Normal,
+                errors,
+                |text_style, value_span| parse_text_style(&text_style, "text_style", &value_span),
+            ),
         },
         "SeekBar" => ViewType::SeekBar {
-            max_progress: if let Some(max_progress) = attributes.remove("max_progress") {
-                max_progress.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidIntValue {
-                        attribute_name: "max_progress".to_string(),
-                        attribute_value: max_progress,
-                        err,
-                    })
-                })?
-            } else {
-                100
-            },
-
-            progress: if let Some(progress) = attributes.remove("progress") {
-                progress.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidIntValue {
-                        attribute_name: "progress".to_string(),
-                        attribute_value: progress,
-                        err,
+            max_progress: parse_attribute(
+                attributes,
+                "max_progress",
+                100,
+                errors,
+                |max_progress, value_span| {
+                    max_progress
+                        .parse()
+                        .map_err(|err| ViewCompileError::AttributeParseError {
+                            error: AttributeParseError::InvalidIntValue {
+                                attribute_name: "max_progress".to_string(),
+                                attribute_value: max_progress,
+                                err,
+                            },
+                            span: value_span,
+                        })
+                },
+            ),
+
+            progress: parse_attribute(attributes, "progress", 0, errors, |progress, value_span| {
+                progress
+                    .parse()
+                    .map_err(|err| ViewCompileError::AttributeParseError {
+                        error: AttributeParseError::InvalidIntValue {
+                            attribute_name: "progress".to_string(),
+                            attribute_value: progress,
+                            err,
+                        },
+                        span: value_span,
                     })
-                })?
-            } else {
-                0
-            },
+            }),
         },
         "CalendarView" => ViewType::CalendarView {
-            first_day_of_week: if let Some(first_day_of_the_week) =
-                attributes.remove("first_day_of_the_week")
-            {
-                first_day_of_the_week.parse().map_err(|err| {
-                    ViewCompileError::AttributeParseError(AttributeParseError::InvalidIntValue {
-                        attribute_name: "first_day_of_the_week".to_string(),
-                        attribute_value: first_day_of_the_week,
-                        err,
+            first_day_of_week: parse_attribute(
+                attributes,
+                "first_day_of_the_week",
+                1,
+                errors,
+                |first_day_of_the_week, value_span| {
+                    first_day_of_the_week.parse().map_err(|err| {
+                        ViewCompileError::AttributeParseError {
+                            error: AttributeParseError::InvalidIntValue {
+                                attribute_name: "first_day_of_the_week".to_string(),
+                                attribute_value: first_day_of_the_week,
+                                err,
+                            },
+                            span: value_span,
+                        }
                     })
-                })?
-            } else {
-                1
-            },
+                },
+            ),
         },
         // todo: make this illegal to be placed in regular layout, must be placed in a special place
         //       or something
         "FloatingActionButton" => ViewType::Fab {
-            // todo: validate with resources defined in manifest soon
-            image_res_name: attributes.remove("image").unwrap_or_else(|| "".to_string()),
+            // resources are checked separately, see `check_resources`
+            image_res_name: attributes
+                .remove("image")
+                .map(|attr| attr.value)
+                .unwrap_or_else(|| "".to_string()),
         },
         "AdView" => ViewType::AdView {
             // i have no idea what this is for, i dont use adviews
             adview_size: attributes
                 .remove("adview_size")
+                .map(|attr| attr.value)
                 .unwrap_or_else(|| "".to_string()),
         },
         "MapView" => ViewType::MapView,
-        _ => return Err(ViewCompileError::UnknownView { view_name: name }),
+        _ => {
+            errors.push(ViewCompileError::UnknownView {
+                view_name: name,
+                span: name_span,
+            });
+
+            return None;
+        }
     })
 }
 
@@ -856,12 +1170,17 @@ the item `{attribute_value_item_incompatible}` is incompatible with \
 mod attr_parser {
     use super::AttributeParseError;
     use super::ViewCompileError;
+    use crate::compiler::Span;
     use swrs::color::Color;
     use swrs::parser::view::models::layout::gravity;
     use swrs::parser::view::models::layout::gravity::Gravity;
     use swrs::parser::view::models::text::TextType;
 
-    pub fn parse_gravity(gravity: &str, attr_name: &str) -> Result<Gravity, ViewCompileError> {
+    pub fn parse_gravity(
+        gravity: &str,
+        attr_name: &str,
+        span: &Span,
+    ) -> Result<Gravity, ViewCompileError> {
         let values: Vec<&str> = gravity.split("|").map(|s| s.trim()).collect();
         let mut result = Gravity(gravity::NONE);
 
@@ -872,14 +1191,15 @@ mod attr_parser {
         macro_rules! err_if_taken {
             ($taken_var:ident,$incompatible:expr,$incompatible_with:expr) => {
                 if $taken_var {
-                    return Err(ViewCompileError::AttributeParseError(
-                        AttributeParseError::IncompatibleAttributeValueItem {
+                    return Err(ViewCompileError::AttributeParseError {
+                        error: AttributeParseError::IncompatibleAttributeValueItem {
                             attribute_name: attr_name.to_string(),
                             attribute_value: gravity.to_string(),
                             attribute_value_item_incompatible: $incompatible.to_string(),
                             attribute_value_item_incompatible_with: $incompatible_with.to_string(),
                         },
-                    ));
+                        span: span.clone(),
+                    });
                 }
 
                 $taken_var = true;
@@ -908,8 +1228,8 @@ mod attr_parser {
                     gravity::BOTTOM
                 }
                 other => {
-                    return Err(ViewCompileError::AttributeParseError(
-                        AttributeParseError::InvalidAttributeValueItem {
+                    return Err(ViewCompileError::AttributeParseError {
+                        error: AttributeParseError::InvalidAttributeValueItem {
                             attribute_name: "gravity".to_string(),
                             attribute_value: gravity.to_string(),
                             attribute_value_item: other.to_string(),
@@ -923,7 +1243,8 @@ mod attr_parser {
                                 "bottom".to_string(),
                             ],
                         },
-                    ))
+                        span: span.clone(),
+                    })
                 }
             }
         }
@@ -931,15 +1252,20 @@ mod attr_parser {
         Ok(result)
     }
 
-    pub fn parse_color(color: &str, attr_name: &str) -> Result<Color, ViewCompileError> {
+    pub fn parse_color(
+        color: &str,
+        attr_name: &str,
+        span: &Span,
+    ) -> Result<Color, ViewCompileError> {
         // supports "ffffff" "#ffffff" "ffffffff" "#ffffffff"
         if color.len() < 6 || color.len() > 9 {
-            return Err(ViewCompileError::AttributeParseError(
-                AttributeParseError::InvalidColorValue {
+            return Err(ViewCompileError::AttributeParseError {
+                error: AttributeParseError::InvalidColorValue {
                     attribute_name: attr_name.to_string(),
                     attribute_value: color.to_string(),
                 },
-            ));
+                span: span.clone(),
+            });
         }
 
         Ok(Color::parse_hex(if color.len() % 2 == 0 {
@@ -949,27 +1275,30 @@ mod attr_parser {
             // this does have a # at the start, check it
             if &color.chars().nth(0).unwrap() != &'#' {
                 // what this doesn't start with `#`!?
-                return Err(ViewCompileError::AttributeParseError(
-                    AttributeParseError::InvalidColorValue {
+                return Err(ViewCompileError::AttributeParseError {
+                    error: AttributeParseError::InvalidColorValue {
                         attribute_name: attr_name.to_string(),
                         attribute_value: color.to_string(),
                     },
-                ));
+                    span: span.clone(),
+                });
             }
 
             &color[1..]
         })
-        .map_err(|_| {
-            ViewCompileError::AttributeParseError(AttributeParseError::InvalidColorValue {
+        .map_err(|_| ViewCompileError::AttributeParseError {
+            error: AttributeParseError::InvalidColorValue {
                 attribute_name: attr_name.to_string(),
                 attribute_value: color.to_string(),
-            })
+            },
+            span: span.clone(),
         })?)
     }
 
     pub fn parse_text_style(
         text_style: &str,
         attr_name: &str,
+        span: &Span,
     ) -> Result<TextType, ViewCompileError> {
         let values: Vec<&str> = text_style.split("|").map(|s| s.trim()).collect();
 
@@ -981,14 +1310,15 @@ mod attr_parser {
                 "bold" => bold = true,
                 "italic" => italic = true,
                 other => {
-                    return Err(ViewCompileError::AttributeParseError(
-                        AttributeParseError::InvalidAttributeValueItem {
+                    return Err(ViewCompileError::AttributeParseError {
+                        error: AttributeParseError::InvalidAttributeValueItem {
                             attribute_name: attr_name.to_string(),
                             attribute_value: text_style.to_owned(),
                             attribute_value_item: other.to_string(),
                             possible_value_items: vec!["bold".to_string(), "italic".to_string()],
                         },
-                    ))
+                        span: span.clone(),
+                    })
                 }
             }
         }
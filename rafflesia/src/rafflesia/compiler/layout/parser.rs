@@ -1,28 +1,99 @@
-use crate::compiler::layout::parser::parser::Token;
+use crate::compiler::Span;
 use buffered_lexer::BufferedLexer;
 use logos::Logos;
-use std::collections::HashMap;
+use swrs::LinkedHashMap;
+
+pub use parser::{LayoutParseError, Token};
 
 #[derive(Debug, PartialEq)]
 pub struct View {
     pub name: String,
-    pub attributes: Option<HashMap<String, String>>,
+    pub name_span: Span,
+    // attributes are kept in the order they're written
+    pub attributes: Option<LinkedHashMap<String, Attribute>>,
     pub children: Option<Box<Vec<View>>>,
     pub view_id: Option<String>,
+    pub view_id_span: Option<Span>,
+}
+
+/// The value of an attribute, along with the spans of its key and value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute {
+    pub value: String,
+    pub key_span: Span,
+    pub value_span: Span,
+}
+
+impl Attribute {
+    /// Creates an attribute that doesn't come from a source code
+    pub fn new(value: String) -> Self {
+        Attribute {
+            value,
+            key_span: Span::default(),
+            value_span: Span::default(),
+        }
+    }
+}
+
+/// Parses a layout code, returns the first syntax error found. Use [`parse_layout_recovering`]
+/// to get every syntax errors of the code.
+pub fn parse_layout(raw: &str) -> Result<View, LayoutParseError> {
+    parse_layout_with(&mut lexer(raw))
 }
 
-pub fn parse_layout(raw: &str) -> Result<View, parser::LayoutParseError> {
-    let mut lex: BufferedLexer<'_, Token> = BufferedLexer::new(Token::lexer(raw), Token::Error);
+/// Parses a layout code out of a lexer created with [`lexer`], returns the first syntax error
+/// found. The lexer can then be used to retrieve the comments around the tokens.
+pub fn parse_layout_with(lex: &mut BufferedLexer<'_, Token>) -> Result<View, LayoutParseError> {
+    let view = parse_view(lex);
+    let mut errors = lex.take_errors();
 
+    match view {
+        Some(view) if errors.is_empty() => Ok(view),
+        _ => Err(errors.remove(0)),
+    }
+}
+
+/// Parses a layout code while recovering from syntax errors. An attribute or a child view that
+/// fails to parse is skipped until the next comma or the closing bracket and parsing goes on
+/// from there.
+///
+/// Returns the view if the root view itself could be parsed, along with every syntax errors
+/// found.
+pub fn parse_layout_recovering(raw: &str) -> (Option<View>, Vec<LayoutParseError>) {
+    let mut lex = lexer(raw);
+    let view = parse_view(&mut lex);
+
+    (view, lex.take_errors())
+}
+
+/// Creates the lexer used to parse a layout code. Whitespaces and comments are trivia: the
+/// parser never sees them, but they're kept along with the tokens next to them (see
+/// [`BufferedLexer::lossless_tokens`]).
+pub fn lexer(raw: &str) -> BufferedLexer<'_, Token> {
+    BufferedLexer::new_lossless(
+        Token::lexer(raw),
+        Token::Error,
+        vec![Token::Whitespace, Token::Comment],
+    )
+}
+
+fn parse_view(lex: &mut BufferedLexer<Token>) -> Option<View> {
     // parse it :sunglasses:
-    parser::view(&mut lex)
+    match parser::view(lex) {
+        Ok(view) => Some(view),
+        Err(err) => {
+            lex.report(err);
+            None
+        }
+    }
 }
 
 mod parser {
-    use super::View;
+    use super::{Attribute, View};
+    use crate::compiler::Span;
     use buffered_lexer::{BufferedLexer, SpannedTokenOwned};
     use logos::Logos;
-    use std::collections::HashMap;
+    use swrs::LinkedHashMap;
 
     #[derive(Logos, PartialEq, Debug, Clone)]
     pub enum Token {
@@ -44,27 +115,38 @@ mod parser {
         #[token(",")]
         Comma,
 
-        #[regex("[a-zA-Z_]+")]
+        #[regex("[a-zA-Z_][a-zA-Z0-9_]*")]
         Text,
 
         #[regex(r#""([^"]|\\")*""#)]
         String,
 
+        // trivia, see `lexer`
+        #[regex(r"[ \n\t]+")]
+        Whitespace,
+
+        #[regex(r"//[^\n]*")]
+        Comment,
+
         #[error]
-        #[regex(r"[ \n\t]+", logos::skip)] // whitespace
-        #[regex(r"//[^\n]*\n?", logos::skip)] // comment
         Error,
     }
 
     pub type LayoutParseError = buffered_lexer::error::ParseError<Token, SpannedTokenOwned<Token>>;
 
+    // the pairs of brackets that are skipped over when recovering from an error
+    const NESTING: &[(Token, Token)] = &[
+        (Token::LParentheses, Token::RParentheses),
+        (Token::LBrace, Token::RBrace),
+    ];
+
     // this signature is ridiculous
     pub fn view(lexer: &mut BufferedLexer<Token>) -> Result<View, LayoutParseError> {
         // todo: better error handling, see ariadne
         lexer.start();
 
         // view starts with a text as its name
-        let name = lexer.expect(Token::Text)?.slice;
+        let name = lexer.expect(Token::Text)?;
 
         let attributes = lexer
             .expect_failsafe_wo_eof(Token::LParentheses)?
@@ -84,7 +166,7 @@ mod parser {
 
         // fixme: maybe use a new func for this?
         let view_id = if lexer.expect_failsafe_wo_eof(Token::Colon)?.is_some() {
-            Some(lexer.expect(Token::Text)?.slice.to_string())
+            Some(lexer.expect(Token::Text)?)
         } else {
             None
         };
@@ -92,39 +174,52 @@ mod parser {
         lexer.success();
 
         Ok(View {
-            name,
+            name: name.slice,
+            name_span: name.pos,
             attributes,
             children,
-            view_id,
+            view_id_span: view_id.as_ref().map(|id| id.pos.clone()),
+            view_id: view_id.map(|id| id.slice),
         })
     }
 
     pub fn attributes(
         lexer: &mut BufferedLexer<Token>,
-    ) -> Result<HashMap<String, String>, LayoutParseError> {
+    ) -> Result<LinkedHashMap<String, Attribute>, LayoutParseError> {
         lexer.start();
         lexer.expect(Token::LParentheses)?;
 
-        let mut result = HashMap::new();
+        let mut result = LinkedHashMap::new();
 
         if lexer.expect_failsafe_wo_eof(Token::RParentheses)?.is_some() {
             // welp I guess theres nothing here
+            lexer.success();
             return Ok(result);
         }
 
         // if its not closed then there must be an attribute
-        let first = attribute(lexer)?;
-        result.insert(first.0, first.1);
+        loop {
+            // an attribute that fails to parse is skipped until the next one
+            let depth = lexer.depth();
+
+            match attribute(lexer) {
+                Ok((key, attr)) => {
+                    result.insert(key, attr);
+                }
+                Err(err) => {
+                    lexer.recover(depth, err)?;
+                    lexer.skip_until(&[Token::Comma, Token::RParentheses], NESTING);
+                }
+            }
+
+            if lexer.expect_failsafe_wo_eof(Token::Comma)?.is_none() {
+                break;
+            }
 
-        while lexer.expect_failsafe_wo_eof(Token::Comma)?.is_some() {
             // check if next is a closing parentheses, means this is a trailing comma
             if lexer.expect_peek(Token::RParentheses).is_ok() {
                 break;
             }
-
-            // parse attribute
-            let attr = attribute(lexer)?;
-            result.insert(attr.0, attr.1);
         }
 
         // not a comma, must be a closing parentheses
@@ -136,35 +231,42 @@ mod parser {
 
     pub fn attribute(
         lexer: &mut BufferedLexer<Token>,
-    ) -> Result<(String, String), LayoutParseError> {
+    ) -> Result<(String, Attribute), LayoutParseError> {
         lexer.start();
 
         // attr: value
-        let attr = value(lexer)?;
+        let (attr, key_span) = value(lexer)?;
         lexer.expect(Token::Colon)?;
-        let value = value(lexer)?;
+        let (value, value_span) = value(lexer)?;
 
         lexer.success();
-        Ok((attr, value))
+        Ok((
+            attr,
+            Attribute {
+                value,
+                key_span,
+                value_span,
+            },
+        ))
     }
 
-    pub fn value(lexer: &mut BufferedLexer<Token>) -> Result<String, LayoutParseError> {
+    pub fn value(lexer: &mut BufferedLexer<Token>) -> Result<(String, Span), LayoutParseError> {
         lexer.start();
 
         let res = match lexer.expect_multiple_choices(&vec![Token::Text, Token::String])? {
             SpannedTokenOwned {
                 token: Token::Text,
                 slice,
-                ..
-            } => slice.to_string(),
+                pos,
+            } => (slice.to_string(), pos),
             SpannedTokenOwned {
                 token: Token::String,
                 slice,
-                ..
+                pos,
             } =>
             /* remove the `"` around it */
             {
-                slice[1..slice.len() - 1].to_string()
+                (slice[1..slice.len() - 1].to_string(), pos)
             }
 
             _ => unreachable!(),
@@ -185,20 +287,30 @@ mod parser {
         // check if it already ended :l
         if lexer.expect_failsafe_wo_eof(Token::RBrace)?.is_some() {
             // welp i guess theres nothing here
+            lexer.success();
             return Ok(result);
         }
 
-        let first = view(lexer)?;
-        result.push(first);
+        loop {
+            // a child that fails to parse is skipped until the next one
+            let depth = lexer.depth();
+
+            match view(lexer) {
+                Ok(child) => result.push(child),
+                Err(err) => {
+                    lexer.recover(depth, err)?;
+                    lexer.skip_until(&[Token::Comma, Token::RBrace], NESTING);
+                }
+            }
+
+            if lexer.expect_failsafe_wo_eof(Token::Comma)?.is_none() {
+                break;
+            }
 
-        while lexer.expect_failsafe_wo_eof(Token::Comma)?.is_some() {
             // check if next is a closing brace, means this is a trailing comma
             if lexer.expect_peek(Token::RBrace).is_ok() {
                 break;
             }
-
-            // parse child
-            result.push(view(lexer)?);
         }
 
         lexer.expect(Token::RBrace)?;
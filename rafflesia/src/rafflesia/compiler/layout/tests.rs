@@ -1,9 +1,11 @@
 use super::parser::*;
-use crate::compiler::layout::compile_view_tree;
-use std::collections::HashMap;
+use super::ViewCompileError;
+use crate::compiler::layout::{check_resources, compile_view_tree};
+use crate::compiler::{ResourceKind, ResourceNames};
 use swrs::api::view::flatten_views;
 use swrs::parser::view::models::AndroidView;
 use swrs::parser::Parsable;
+use swrs::LinkedHashMap;
 
 // simple DSL that constructs SWRS's View using a syntax similar to the layout's syntax
 // i just hate how my is IDE complaining about an unused mut AAAHHHHHHH
@@ -28,16 +30,18 @@ macro_rules! view {
         })?
     } => {
         {
-            let mut attributes = HashMap::new();
-            $(attributes.insert(stringify!($attr_name).to_string(), $attr_value.to_string());)*
+            let mut attributes = LinkedHashMap::new();
+            $(attributes.insert(stringify!($attr_name).to_string(), Attribute::new($attr_value.to_string()));)*
             let children = Vec::new();
             $($(children.push($child);)*)?
 
             View {
                 name: stringify!($name).to_string(),
+                name_span: Default::default(),
                 attributes: if attributes.len() == 0 { None } else { Some(attributes) },
                 children: if children.len() == 0 { None } else { Some(Box::new(children)) },
-                view_id: Some(stringify!($id).to_string())
+                view_id: Some(stringify!($id).to_string()),
+                view_id_span: Some(Default::default()),
             }
         }
     };
@@ -62,17 +66,19 @@ macro_rules! view {
         })?
     } => {
         {
-            let mut attributes = HashMap::new();
-            $(attributes.insert(stringify!($attr_name).to_string(), $attr_value.to_string());)*
+            let mut attributes = LinkedHashMap::new();
+            $(attributes.insert(stringify!($attr_name).to_string(), Attribute::new($attr_value.to_string()));)*
             #[allow(unused_mut)]
             let mut children = Vec::new();
             $($(children.push($child);)*)?
 
             View {
                 name: stringify!($name).to_string(),
+                name_span: Default::default(),
                 attributes: if attributes.len() == 0 { None } else { Some(attributes) },
                 children: if children.len() == 0 { None } else { Some(Box::new(children)) },
-                view_id: None
+                view_id: None,
+                view_id_span: None,
             }
         }
     };
@@ -97,8 +103,196 @@ fn parser_simple() {
         }
     };
 
-    // todo: assert
-    assert_eq!(expected, result);
+    assert_eq!(expected, without_spans(result));
+}
+
+// parsed views have spans, views constructed by `view!` don't
+fn without_spans(view: View) -> View {
+    View {
+        name_span: Default::default(),
+        attributes: view.attributes.map(|attributes| {
+            attributes
+                .into_iter()
+                .map(|(name, attribute)| (name, Attribute::new(attribute.value)))
+                .collect()
+        }),
+        children: view
+            .children
+            .map(|children| Box::new(children.into_iter().map(without_spans).collect())),
+        view_id_span: view.view_id_span.map(|_| Default::default()),
+        ..view
+    }
+}
+
+#[test]
+fn parse_spans() {
+    let input = r#"LinearLayout (height: match_parent, text: "hello") {
+    TextView: text1,
+}: linear1"#;
+
+    let result = parse_layout(input).unwrap();
+
+    assert_eq!(&input[result.name_span.clone()], "LinearLayout");
+    assert_eq!(&input[result.view_id_span.clone().unwrap()], "linear1");
+
+    let attributes = result.attributes.as_ref().unwrap();
+    let height = attributes.get("height").unwrap();
+    assert_eq!(&input[height.key_span.clone()], "height");
+    assert_eq!(&input[height.value_span.clone()], "match_parent");
+
+    // spans of strings include their quotes
+    let text = attributes.get("text").unwrap();
+    assert_eq!(&input[text.value_span.clone()], "\"hello\"");
+
+    let child = &result.children.as_ref().unwrap()[0];
+    assert_eq!(&input[child.name_span.clone()], "TextView");
+    assert_eq!(&input[child.view_id_span.clone().unwrap()], "text1");
+}
+
+#[test]
+fn parse_recovering() {
+    let input = r#"LinearLayout (height: match_parent, text "hello", width: wrap_content) {
+    TextView: text1,
+    Button (text: "hi"): ,
+    ImageView (: image1): image1,
+    EditText: edittext1
+}"#;
+
+    let (view, errors) = parse_layout_recovering(input);
+    let view = view.expect("the root view should be parsed");
+
+    let error_slices = errors
+        .iter()
+        .map(|err| match err {
+            LayoutParseError::UnexpectedTokenError { pos, .. } => &input[pos.clone()],
+            _ => panic!("unexpected error {:?}", err),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(error_slices, vec!["\"hello\"", ",", ":"]);
+
+    // the attributes and the children around the errors are still there
+    let attributes = view.attributes.unwrap();
+    assert_eq!(
+        attributes.keys().collect::<Vec<_>>(),
+        vec!["height", "width"]
+    );
+
+    let children = view.children.unwrap();
+    assert_eq!(
+        children
+            .iter()
+            .map(|child| child.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["TextView", "ImageView", "EditText"]
+    );
+
+    // the first error is the one returned by parse_layout
+    assert_eq!(parse_layout(input).unwrap_err(), errors[0]);
+
+    let (view, errors) = parse_layout_recovering("(text: hello)");
+    assert!(view.is_none());
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn compile_error_spans() {
+    let compile = |input: &str| {
+        compile_view_tree(parse_layout(input).unwrap())
+            .expect_err("view should fail to compile")
+            .0
+            .remove(0)
+    };
+
+    let input = "LinearLayout { Unknown: view1 }";
+    let err = compile(input);
+    assert_eq!(&input[err.span().clone()], "Unknown");
+
+    let ViewCompileError::ChildCompileError { source, .. } = err else {
+        panic!("not a child compile error");
+    };
+    assert!(matches!(*source, ViewCompileError::UnknownView { .. }));
+
+    let input = "TextView (text_size: big)";
+    let err = compile(input);
+    assert!(matches!(err, ViewCompileError::AttributeParseError { .. }));
+    assert_eq!(&input[err.span().clone()], "big");
+
+    let input = "LinearLayout (padding: \"-\")";
+    let err = compile(input);
+    assert_eq!(&input[err.span().clone()], "\"-\"");
+
+    let input = "Button (text_color: \"#zzzzzz\")";
+    let err = compile(input);
+    assert_eq!(&input[err.span().clone()], "\"#zzzzzz\"");
+}
+
+#[test]
+fn compile_multiple_errors() {
+    let input = r##"LinearLayout (padding: "-") {
+    Unknown {
+        TextView (text_size: big)
+    },
+    Button (text_color: "#zzzzzz"): button1
+}"##;
+
+    let errors = compile_view_tree(parse_layout(input).unwrap())
+        .expect_err("view should fail to compile")
+        .0;
+
+    // errors on children are wrapped, the spans of the innermost errors are the ones we want
+    let spans = errors
+        .iter()
+        .map(|mut err| {
+            while let ViewCompileError::ChildCompileError { source, .. } = err {
+                err = source.as_ref();
+            }
+
+            &input[err.span().clone()]
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(spans, vec!["\"-\"", "Unknown", "big", "\"#zzzzzz\""]);
+}
+
+#[test]
+fn unknown_resources() {
+    let input = r#"LinearLayout {
+    ImageView (image: logo): image1,
+    ImageView (image: app_icon): image2,
+    TextView (text_font: default_font): text1,
+    LinearLayout {
+        TextView (text_font: comic_sans): text2
+    }
+}"#;
+
+    let resources = ResourceNames {
+        images: ["app_icon".to_string()].into(),
+        ..Default::default()
+    };
+
+    let errors = check_resources(&parse_layout(input).unwrap(), &resources)
+        .expect_err("resources should be missing")
+        .0;
+
+    let found = errors
+        .iter()
+        .map(|err| match err {
+            ViewCompileError::UnknownResource { kind, name, span } => {
+                assert_eq!(&input[span.clone()], name);
+                (*kind, name.as_str())
+            }
+            _ => panic!("not an unknown resource error"),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        found,
+        vec![
+            (ResourceKind::Image, "logo"),
+            (ResourceKind::Font, "comic_sans")
+        ]
+    );
 }
 
 #[test]
@@ -1,3 +1,5 @@
+use crate::compiler::Span;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum VariableType {
     Number,
@@ -11,6 +13,30 @@ pub enum ComplexVariableType {
     List { inner_type: VariableType },
 }
 
+/// The type of a moreblock parameter
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParameterType {
+    Simple(VariableType),
+    Complex(ComplexVariableType),
+    // any kind of view
+    View,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Parameter {
+    pub name: String,
+    pub parameter_type: ParameterType,
+    pub span: Span,
+}
+
+// an argument of a component declaration, e.g. `file: "data"`
+#[derive(Debug, PartialEq, Clone)]
+pub struct ComponentArgument {
+    pub name: String,
+    pub value: String,
+    pub span: Span,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct OuterStatements(pub Vec<OuterStatement>);
 
@@ -21,25 +47,58 @@ pub enum OuterStatement {
         // for later
         // initial_value: Option<Expression>,
         identifier: String,
+        span: Span,
     },
 
     ComplexVariableDeclaration {
         variable_type: ComplexVariableType,
         identifier: String,
+        span: Span,
     },
 
     ActivityEventListener {
         event_name: String,
         body: InnerStatements,
+        span: Span,
     },
 
     ViewEventListener {
         view_id: String,
         event_name: String,
         body: InnerStatements,
+        span: Span,
+    },
+
+    // component_type identifier(arguments)
+    ComponentDeclaration {
+        component_type: String,
+        identifier: String,
+        arguments: Vec<ComponentArgument>,
+        span: Span,
+    },
+
+    // moreblock name(parameters) { body }
+    MoreBlockDeclaration {
+        name: String,
+        parameters: Vec<Parameter>,
+        body: InnerStatements,
+        span: Span,
     },
 }
 
+impl OuterStatement {
+    pub fn span(&self) -> Span {
+        match self {
+            OuterStatement::SimpleVariableDeclaration { span, .. }
+            | OuterStatement::ComplexVariableDeclaration { span, .. }
+            | OuterStatement::ActivityEventListener { span, .. }
+            | OuterStatement::ViewEventListener { span, .. }
+            | OuterStatement::ComponentDeclaration { span, .. }
+            | OuterStatement::MoreBlockDeclaration { span, .. } => span.clone(),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct InnerStatements(pub Vec<InnerStatement>);
 
@@ -47,6 +106,16 @@ pub struct InnerStatements(pub Vec<InnerStatement>);
 pub struct VariableAssignment {
     pub identifier: String,
     pub value: Expression,
+    pub span: Span,
+}
+
+// from[index] = value
+#[derive(Debug, PartialEq, Clone)]
+pub struct IndexAssignment {
+    pub from: Box<Expression>,
+    pub index: Box<Expression>,
+    pub value: Expression,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -54,28 +123,49 @@ pub struct IfStatement {
     pub condition: Expression,
     pub body: InnerStatements,
     pub else_body: Option<InnerStatements>,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct ForeverStatement {
     pub body: InnerStatements,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct RepeatStatement {
     pub condition: Expression,
     pub body: InnerStatements,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum InnerStatement {
     VariableAssignment(VariableAssignment),
+    IndexAssignment(IndexAssignment),
     IfStatement(IfStatement),
     RepeatStatement(RepeatStatement),
     ForeverStatement(ForeverStatement),
-    Break,
-    Continue,
+    Break { span: Span },
+    Continue { span: Span },
     Expression(Expression),
+    RawBlock(RawBlock),
+}
+
+impl InnerStatement {
+    pub fn span(&self) -> Span {
+        match self {
+            InnerStatement::VariableAssignment(VariableAssignment { span, .. })
+            | InnerStatement::IndexAssignment(IndexAssignment { span, .. })
+            | InnerStatement::IfStatement(IfStatement { span, .. })
+            | InnerStatement::RepeatStatement(RepeatStatement { span, .. })
+            | InnerStatement::ForeverStatement(ForeverStatement { span, .. })
+            | InnerStatement::Break { span }
+            | InnerStatement::Continue { span }
+            | InnerStatement::RawBlock(RawBlock { span, .. }) => span.clone(),
+            InnerStatement::Expression(expr) => expr.span(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -107,13 +197,29 @@ pub enum Expression {
         first: Box<Expression>,
         operator: BinaryOperator,
         second: Box<Expression>,
+        span: Span,
     },
     UnaryOp {
         value: Box<Expression>,
         operator: UnaryOperator,
+        span: Span,
     },
     PrimaryExpression(PrimaryExpression),
-    Literal(Literal),
+    Literal {
+        value: Literal,
+        span: Span,
+    },
+}
+
+impl Expression {
+    pub fn span(&self) -> Span {
+        match self {
+            Expression::BinOp { span, .. }
+            | Expression::UnaryOp { span, .. }
+            | Expression::Literal { span, .. } => span.clone(),
+            Expression::PrimaryExpression(primary) => primary.span(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -129,12 +235,14 @@ pub enum PrimaryExpression {
     Index {
         from: Box<Expression>, // using PrimaryExpression would be better
         index: Box<Expression>,
+        span: Span,
     },
     VariableAccess {
         // from.name if Some
         // fixme: there's a better approach to this
         from: Option<Box<Expression>>, // using PrimaryExpression would be better
         name: String,
+        span: Span,
     },
     // this was previously `from(arguments)` but due to block limitations it wont work and we need
     // this less flexible way
@@ -143,8 +251,35 @@ pub enum PrimaryExpression {
         from: Option<Box<Expression>>, // using PrimaryExpression might be better
         name: String,
         arguments: Arguments,
+        span: Span,
     },
+    // #block("opcode", "spec", "type", "color", arguments)
+    RawBlock(RawBlock),
+}
+
+impl PrimaryExpression {
+    pub fn span(&self) -> Span {
+        match self {
+            PrimaryExpression::Index { span, .. }
+            | PrimaryExpression::VariableAccess { span, .. }
+            | PrimaryExpression::Call { span, .. }
+            | PrimaryExpression::RawBlock(RawBlock { span, .. }) => span.clone(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Arguments(pub Vec<Expression>);
+
+/// A block written as-is, used for blocks that doesn't have a rafflesia syntax
+#[derive(Debug, PartialEq, Clone)]
+pub struct RawBlock {
+    pub op_code: String,
+    pub spec: String,
+    pub block_type: String,
+    pub color: String,
+    pub arguments: Arguments,
+    // only raw blocks as statements can have substacks
+    pub sub_stacks: Vec<InnerStatements>,
+    pub span: Span,
+}
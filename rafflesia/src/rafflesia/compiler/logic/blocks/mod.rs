@@ -13,7 +13,9 @@
 // this file is filled with definitions that might not be used in the codebase
 #![allow(dead_code, unused_variables)]
 
-use crate::compiler::logic::blocks::types::{Member, PrimitiveType, Type, TypeData};
+use crate::compiler::logic::blocks::types::{
+    ComplexType, ComponentType, Member, PrimitiveType, Type, TypeData, TypeValue,
+};
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 use swrs::api::block::{
@@ -62,7 +64,7 @@ pub fn not(arg: ArgValue<Boolean>) -> Block {
                 value: arg,
             })
             .build(),
-        block_type: BlockType::Regular,
+        block_type: BlockType::Argument(ArgumentBlockReturnType::Boolean),
     }
 }
 
@@ -408,6 +410,62 @@ pub fn get_var(name: String, arg_type: ArgumentBlockReturnType) -> Block {
     )
 }
 
+pub fn get_arg(name: String, arg_type: ArgumentBlockReturnType) -> Block {
+    Block::new(
+        BlockCategory::MoreBlock,
+        "getArg".to_string(),
+        BlockContent::builder().text(name).build(),
+        BlockType::Argument(arg_type),
+    )
+}
+
+// swrs doesn't have an argument block type for maps, map parameters are only ever used as the menu
+// argument of map blocks
+pub fn get_map_arg(name: String) -> Block {
+    Block::new(
+        BlockCategory::MoreBlock,
+        "getArg".to_string(),
+        BlockContent::builder().text(name).build(),
+        BlockType::Regular,
+    )
+}
+
+pub fn to_number(value: ArgValue<String>) -> Block {
+    Block::new(
+        BlockCategory::Operator,
+        "toNumber".to_string(),
+        BlockContent::builder()
+            .text("toNumber")
+            .arg(Argument::String { name: None, value })
+            .build(),
+        BlockType::Argument(ArgumentBlockReturnType::Number),
+    )
+}
+
+pub fn to_string_with_decimal(value: ArgValue<Number>) -> Block {
+    Block::new(
+        BlockCategory::Operator,
+        "toStringWithDecimal".to_string(),
+        BlockContent::builder()
+            .text("toString")
+            .arg(Argument::Number { name: None, value })
+            .text("with")
+            .text("decimal")
+            .build(),
+        BlockType::Argument(ArgumentBlockReturnType::String),
+    )
+}
+
+// calls a moreblock, its content is the spec of the moreblock with the arguments filled in
+pub fn defined_func(content: BlockContent) -> Block {
+    Block::new(
+        BlockCategory::MoreBlock,
+        "definedFunc".to_string(),
+        content,
+        BlockType::Regular,
+    )
+}
+
 macro_rules! hashmap {
     { $($key:expr => $value:expr),+ } => {
         {
@@ -447,16 +505,719 @@ lazy_static! {
                 )
             }),
             "toStringDec" => method!((vec![]) -> Type::Primitive(PrimitiveType::String); |val, _| {
-                Block::new(
-                    BlockCategory::Operator,
-                    "toStringWithDecimal".to_string(),
+                to_string_with_decimal(val.to_num())
+            })
+        }
+    };
+}
+
+pub fn component_type_data(component: ComponentType) -> &'static TypeData {
+    match component {
+        ComponentType::Intent => &INTENT_TYPE_DATA,
+        ComponentType::SharedPreferences => &SHARED_PREFERENCES_TYPE_DATA,
+        ComponentType::Timer => &TIMER_TYPE_DATA,
+        ComponentType::Dialog => &DIALOG_TYPE_DATA,
+        ComponentType::Calendar => &CALENDAR_TYPE_DATA,
+        ComponentType::Vibrator => &VIBRATOR_TYPE_DATA,
+        ComponentType::RequestNetwork => &REQUEST_NETWORK_TYPE_DATA,
+        ComponentType::FirebaseDB => &FIREBASE_DB_TYPE_DATA,
+    }
+}
+
+// the menu argument of a component block that refers to the component, e.g. `%m.intent`
+fn component_arg(val: TypeValue) -> Argument {
+    let (component_type, id) = val.to_component();
+
+    Argument::Menu {
+        name: component_type.menu_name().to_string(),
+        value: id,
+    }
+}
+
+fn string_arg(value: ArgValue<String>) -> Argument {
+    Argument::String { name: None, value }
+}
+
+fn number_arg(value: ArgValue<Number>) -> Argument {
+    Argument::Number { name: None, value }
+}
+
+fn menu_arg(name: &str, value: ArgValue<String>) -> Argument {
+    Argument::Menu {
+        name: name.to_string(),
+        value,
+    }
+}
+
+fn component_func(op_code: &str, content: BlockContent, block_type: BlockType) -> Block {
+    Block::new(
+        BlockCategory::ComponentFunc,
+        op_code.to_string(),
+        content,
+        block_type,
+    )
+}
+
+const STRING: Type = Type::Primitive(PrimitiveType::String);
+const NUMBER: Type = Type::Primitive(PrimitiveType::Number);
+
+// the `%m.varMap` argument of a map block
+fn map_arg(var_name: ArgValue<String>) -> Argument {
+    menu_arg("varMap", var_name)
+}
+
+fn map_block(op_code: &str, content: BlockContent, block_type: BlockType) -> Block {
+    Block::new(
+        BlockCategory::Variable,
+        op_code.to_string(),
+        content,
+        block_type,
+    )
+}
+
+// sketchware's maps store their values as strings, numbers are converted from and into strings
+fn map_get(map: TypeValue, key: ArgValue<String>) -> Block {
+    let (inner_type, var_name) = map.to_map();
+
+    let block = map_block(
+        "mapGet",
+        BlockContent::builder()
+            .arg(map_arg(var_name))
+            .text("get")
+            .text("key")
+            .arg(string_arg(key))
+            .build(),
+        BlockType::Argument(ArgumentBlockReturnType::String),
+    );
+
+    match inner_type {
+        PrimitiveType::Number => to_number(ArgValue::Block(block)),
+        _ => block,
+    }
+}
+
+fn map_put(map: TypeValue, key: ArgValue<String>, value: TypeValue) -> Block {
+    let (_, var_name) = map.to_map();
+
+    let value = match value {
+        TypeValue::Number(num) => ArgValue::Block(to_string_with_decimal(num)),
+        value => value.to_str(),
+    };
+
+    map_block(
+        "mapPut",
+        BlockContent::builder()
+            .arg(map_arg(var_name))
+            .text("put")
+            .text("key")
+            .arg(string_arg(key))
+            .text("value")
+            .arg(string_arg(value))
+            .build(),
+        BlockType::Regular,
+    )
+}
+
+fn map_type_data(inner_type: PrimitiveType) -> TypeData {
+    let value_type = Type::Primitive(inner_type);
+
+    let mut index: HashMap<Type, fn([TypeValue; 2]) -> Block> = HashMap::new();
+    index.insert(STRING, |[map, key]| map_get(map, key.to_str()));
+
+    TypeData {
+        index,
+        members: hashmap! {
+            "get" => method!((vec![STRING]) -> value_type; |val, mut args| {
+                map_get(val, args.remove(0).to_str())
+            }),
+            "put" => method!((vec![STRING, value_type]) -> Type::Void; |val, mut args| {
+                let key = args.remove(0).to_str();
+                map_put(val, key, args.remove(0))
+            }),
+            "containsKey" => method!((vec![STRING]) -> Type::Primitive(PrimitiveType::Boolean); |val, mut args| {
+                map_block(
+                    "mapContainKey",
                     BlockContent::builder()
-                        .text("toString")
-                        .arg(Argument::Number { name: None, value: val.to_num() })
+                        .arg(map_arg(val.to_map().1))
+                        .text("contains")
+                        .text("key")
+                        .arg(string_arg(args.remove(0).to_str()))
+                        .build(),
+                    BlockType::Argument(ArgumentBlockReturnType::Boolean),
+                )
+            }),
+            "remove" => method!((vec![STRING]) -> Type::Void; |val, mut args| {
+                map_block(
+                    "mapRemoveKey",
+                    BlockContent::builder()
+                        .arg(map_arg(val.to_map().1))
+                        .text("remove")
+                        .text("key")
+                        .arg(string_arg(args.remove(0).to_str()))
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            "size" => method!((vec![]) -> NUMBER; |val, _| {
+                map_block(
+                    "mapSize",
+                    BlockContent::builder()
+                        .arg(map_arg(val.to_map().1))
+                        .text("size")
+                        .build(),
+                    BlockType::Argument(ArgumentBlockReturnType::Number),
+                )
+            }),
+            "clear" => method!((vec![]) -> Type::Void; |val, _| {
+                map_block(
+                    "mapClear",
+                    BlockContent::builder()
+                        .arg(map_arg(val.to_map().1))
+                        .text("clear")
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            // puts every keys of the map into the given list
+            "keys" => method!((vec![Type::Complex(ComplexType::List { inner_type: PrimitiveType::String })]) -> Type::Void; |val, mut args| {
+                map_block(
+                    "mapGetAllKeys",
+                    BlockContent::builder()
+                        .arg(map_arg(val.to_map().1))
+                        .text("getAllKeys")
+                        .text("to")
+                        .arg(menu_arg("listStr", args.remove(0).to_list().1))
+                        .build(),
+                    BlockType::Regular,
+                )
+            })
+        },
+    }
+}
+
+// the fields and methods of maps
+lazy_static! {
+    pub static ref STRING_MAP_TYPE_DATA: TypeData = map_type_data(PrimitiveType::String);
+    pub static ref NUMBER_MAP_TYPE_DATA: TypeData = map_type_data(PrimitiveType::Number);
+}
+
+// the `%m.listInt` or `%m.listStr` argument of a list block, blocks that work on any kind of list
+// take a `%m.list` instead
+fn list_arg(inner_type: PrimitiveType, var_name: ArgValue<String>) -> Argument {
+    menu_arg(
+        match inner_type {
+            PrimitiveType::Number => "listInt",
+            _ => "listStr",
+        },
+        var_name,
+    )
+}
+
+fn any_list_arg(list: TypeValue) -> Argument {
+    menu_arg("list", list.to_list().1)
+}
+
+// an item of a list, its argument type depends on what the list stores
+fn list_item_arg(inner_type: PrimitiveType, value: TypeValue) -> Argument {
+    match inner_type {
+        PrimitiveType::Number => number_arg(value.to_num()),
+        _ => string_arg(value.to_str()),
+    }
+}
+
+// list blocks that depend on the inner type have an `Int` or `Str` suffix, like `addListInt`
+fn list_op_code(op_code: &str, inner_type: PrimitiveType) -> String {
+    match inner_type {
+        PrimitiveType::Number => format!("{op_code}Int"),
+        _ => format!("{op_code}Str"),
+    }
+}
+
+fn list_item_type(inner_type: PrimitiveType) -> ArgumentBlockReturnType {
+    match inner_type {
+        PrimitiveType::Number => ArgumentBlockReturnType::Number,
+        _ => ArgumentBlockReturnType::String,
+    }
+}
+
+fn list_block(op_code: String, content: BlockContent, block_type: BlockType) -> Block {
+    Block::new(BlockCategory::List, op_code, content, block_type)
+}
+
+fn list_get(list: TypeValue, index: ArgValue<Number>) -> Block {
+    let (inner_type, var_name) = list.to_list();
+
+    list_block(
+        list_op_code("getAtList", inner_type),
+        BlockContent::builder()
+            .text("get")
+            .text("at")
+            .arg(number_arg(index))
+            .text("of")
+            .arg(list_arg(inner_type, var_name))
+            .build(),
+        BlockType::Argument(list_item_type(inner_type)),
+    )
+}
+
+fn list_type_data(inner_type: PrimitiveType) -> TypeData {
+    let item_type = Type::Primitive(inner_type);
+
+    let mut index: HashMap<Type, fn([TypeValue; 2]) -> Block> = HashMap::new();
+    index.insert(NUMBER, |[list, index]| list_get(list, index.to_num()));
+
+    TypeData {
+        index,
+        members: hashmap! {
+            "add" => method!((vec![item_type]) -> Type::Void; |val, mut args| {
+                let (inner_type, var_name) = val.to_list();
+
+                list_block(
+                    list_op_code("addList", inner_type),
+                    BlockContent::builder()
+                        .text("add")
+                        .arg(list_item_arg(inner_type, args.remove(0)))
+                        .text("to")
+                        .arg(list_arg(inner_type, var_name))
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            "insert" => method!((vec![NUMBER, item_type]) -> Type::Void; |val, mut args| {
+                let (inner_type, var_name) = val.to_list();
+                let index = args.remove(0).to_num();
+
+                list_block(
+                    list_op_code("insertList", inner_type),
+                    BlockContent::builder()
+                        .text("insert")
+                        .arg(list_item_arg(inner_type, args.remove(0)))
+                        .text("at")
+                        .arg(number_arg(index))
+                        .text("to")
+                        .arg(list_arg(inner_type, var_name))
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            "get" => method!((vec![NUMBER]) -> item_type; |val, mut args| {
+                list_get(val, args.remove(0).to_num())
+            }),
+            "set" => method!((vec![NUMBER, item_type]) -> Type::Void; |val, mut args| {
+                let (inner_type, var_name) = val.to_list();
+                let index = args.remove(0).to_num();
+
+                list_block(
+                    list_op_code("setList", inner_type),
+                    BlockContent::builder()
+                        .text("set")
+                        .arg(list_item_arg(inner_type, args.remove(0)))
+                        .text("at")
+                        .arg(number_arg(index))
+                        .text("of")
+                        .arg(list_arg(inner_type, var_name))
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            "remove" => method!((vec![NUMBER]) -> Type::Void; |val, mut args| {
+                list_block(
+                    "deleteList".to_string(),
+                    BlockContent::builder()
+                        .text("delete")
+                        .text("at")
+                        .arg(number_arg(args.remove(0).to_num()))
+                        .text("of")
+                        .arg(any_list_arg(val))
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            "length" => method!((vec![]) -> NUMBER; |val, _| {
+                list_block(
+                    "lengthList".to_string(),
+                    BlockContent::builder()
+                        .text("length")
+                        .text("of")
+                        .arg(any_list_arg(val))
+                        .build(),
+                    BlockType::Argument(ArgumentBlockReturnType::Number),
+                )
+            }),
+            "contains" => method!((vec![item_type]) -> Type::Primitive(PrimitiveType::Boolean); |val, mut args| {
+                let (inner_type, var_name) = val.to_list();
+                let item = list_item_arg(inner_type, args.remove(0));
+
+                list_block(
+                    list_op_code("containList", inner_type),
+                    BlockContent::builder()
+                        .arg(list_arg(inner_type, var_name))
+                        .text("contains")
+                        .arg(item)
+                        .build(),
+                    BlockType::Argument(ArgumentBlockReturnType::Boolean),
+                )
+            }),
+            "indexOf" => method!((vec![item_type]) -> NUMBER; |val, mut args| {
+                let (inner_type, var_name) = val.to_list();
+
+                list_block(
+                    list_op_code("indexList", inner_type),
+                    BlockContent::builder()
+                        .text("index")
+                        .arg(list_item_arg(inner_type, args.remove(0)))
+                        .text("in")
+                        .arg(list_arg(inner_type, var_name))
+                        .build(),
+                    BlockType::Argument(ArgumentBlockReturnType::Number),
+                )
+            }),
+            "clear" => method!((vec![]) -> Type::Void; |val, _| {
+                list_block(
+                    "clearList".to_string(),
+                    BlockContent::builder()
+                        .text("clear")
+                        .arg(any_list_arg(val))
+                        .build(),
+                    BlockType::Regular,
+                )
+            })
+        },
+    }
+}
+
+// the fields and methods of lists
+lazy_static! {
+    pub static ref NUMBER_LIST_TYPE_DATA: TypeData = list_type_data(PrimitiveType::Number);
+    pub static ref STRING_LIST_TYPE_DATA: TypeData = list_type_data(PrimitiveType::String);
+}
+
+// the methods of each components
+lazy_static! {
+    pub static ref INTENT_TYPE_DATA: TypeData = TypeData {
+        index: HashMap::new(),
+        members: hashmap! {
+            "setAction" => method!((vec![STRING]) -> Type::Void; |val, mut args| {
+                component_func(
+                    "intentSetAction",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("setAction")
+                        .arg(menu_arg("intentAction", args.remove(0).to_str()))
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            "setData" => method!((vec![STRING]) -> Type::Void; |val, mut args| {
+                component_func(
+                    "intentSetData",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("setData")
+                        .arg(string_arg(args.remove(0).to_str()))
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            "setScreen" => method!((vec![STRING]) -> Type::Void; |val, mut args| {
+                component_func(
+                    "intentSetScreen",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("setScreen")
+                        .arg(menu_arg("activity", args.remove(0).to_str()))
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            "putExtra" => method!((vec![STRING, STRING]) -> Type::Void; |val, mut args| {
+                let key = args.remove(0).to_str();
+                let value = args.remove(0).to_str();
+
+                component_func(
+                    "intentPutExtra",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("putExtra")
+                        .text("key")
+                        .arg(string_arg(key))
+                        .text("value")
+                        .arg(string_arg(value))
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            "start" => method!((vec![]) -> Type::Void; |val, _| {
+                component_func(
+                    "startActivity",
+                    BlockContent::builder()
+                        .text("StartActivity")
+                        .arg(component_arg(val))
+                        .build(),
+                    BlockType::Regular,
+                )
+            })
+        }
+    };
+
+    pub static ref SHARED_PREFERENCES_TYPE_DATA: TypeData = TypeData {
+        index: HashMap::new(),
+        members: hashmap! {
+            "get" => method!((vec![STRING]) -> STRING; |val, mut args| {
+                component_func(
+                    "fileGetData",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("getData")
+                        .text("key")
+                        .arg(string_arg(args.remove(0).to_str()))
+                        .build(),
+                    BlockType::Argument(ArgumentBlockReturnType::String),
+                )
+            }),
+            "set" => method!((vec![STRING, STRING]) -> Type::Void; |val, mut args| {
+                let key = args.remove(0).to_str();
+                let value = args.remove(0).to_str();
+
+                component_func(
+                    "fileSetData",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("setData")
+                        .text("key")
+                        .arg(string_arg(key))
+                        .text("value")
+                        .arg(string_arg(value))
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            "remove" => method!((vec![STRING]) -> Type::Void; |val, mut args| {
+                component_func(
+                    "fileRemoveData",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("removeData")
+                        .text("key")
+                        .arg(string_arg(args.remove(0).to_str()))
+                        .build(),
+                    BlockType::Regular,
+                )
+            })
+        }
+    };
+
+    pub static ref TIMER_TYPE_DATA: TypeData = TypeData {
+        index: HashMap::new(),
+        members: hashmap! {
+            "cancel" => method!((vec![]) -> Type::Void; |val, _| {
+                component_func(
+                    "timerCancel",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("cancel")
+                        .build(),
+                    BlockType::Regular,
+                )
+            })
+        }
+    };
+
+    pub static ref DIALOG_TYPE_DATA: TypeData = TypeData {
+        index: HashMap::new(),
+        members: hashmap! {
+            "setTitle" => method!((vec![STRING]) -> Type::Void; |val, mut args| {
+                component_func(
+                    "dialogSetTitle",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("set")
+                        .text("title")
+                        .arg(string_arg(args.remove(0).to_str()))
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            "setMessage" => method!((vec![STRING]) -> Type::Void; |val, mut args| {
+                component_func(
+                    "dialogSetMessage",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("set")
+                        .text("message")
+                        .arg(string_arg(args.remove(0).to_str()))
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            "show" => method!((vec![]) -> Type::Void; |val, _| {
+                component_func(
+                    "dialogShow",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("show")
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            "dismiss" => method!((vec![]) -> Type::Void; |val, _| {
+                component_func(
+                    "dialogDismiss",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("dismiss")
+                        .build(),
+                    BlockType::Regular,
+                )
+            })
+        }
+    };
+
+    pub static ref CALENDAR_TYPE_DATA: TypeData = TypeData {
+        index: HashMap::new(),
+        members: hashmap! {
+            "now" => method!((vec![]) -> Type::Void; |val, _| {
+                component_func(
+                    "calendarGetNow",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("getNow")
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            // the field is one of java's Calendar fields, e.g. "Calendar.YEAR"
+            "add" => method!((vec![STRING, NUMBER]) -> Type::Void; |val, mut args| {
+                let field = args.remove(0).to_str();
+                let value = args.remove(0).to_num();
+
+                component_func(
+                    "calendarAdd",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("add")
+                        .arg(menu_arg("calendarField", field))
+                        .text("value")
+                        .arg(number_arg(value))
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            "set" => method!((vec![STRING, NUMBER]) -> Type::Void; |val, mut args| {
+                let field = args.remove(0).to_str();
+                let value = args.remove(0).to_num();
+
+                component_func(
+                    "calendarSet",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("set")
+                        .arg(menu_arg("calendarField", field))
+                        .text("value")
+                        .arg(number_arg(value))
+                        .build(),
+                    BlockType::Regular,
+                )
+            }),
+            "format" => method!((vec![STRING]) -> STRING; |val, mut args| {
+                component_func(
+                    "calendarFormat",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("Format")
+                        .arg(string_arg(args.remove(0).to_str()))
+                        .build(),
+                    BlockType::Argument(ArgumentBlockReturnType::String),
+                )
+            }),
+            "getTime" => method!((vec![]) -> NUMBER; |val, _| {
+                component_func(
+                    "calendarGetTime",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("getTime")
+                        .build(),
+                    BlockType::Argument(ArgumentBlockReturnType::Number),
+                )
+            }),
+            "setTime" => method!((vec![NUMBER]) -> Type::Void; |val, mut args| {
+                component_func(
+                    "calendarSetTime",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("setTime")
+                        .arg(number_arg(args.remove(0).to_num()))
+                        .build(),
+                    BlockType::Regular,
+                )
+            })
+        }
+    };
+
+    pub static ref VIBRATOR_TYPE_DATA: TypeData = TypeData {
+        index: HashMap::new(),
+        members: hashmap! {
+            "vibrate" => method!((vec![NUMBER]) -> Type::Void; |val, mut args| {
+                component_func(
+                    "vibratorAction",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("vibrate")
+                        .text("for")
+                        .arg(number_arg(args.remove(0).to_num()))
+                        .text("ms")
+                        .build(),
+                    BlockType::Regular,
+                )
+            })
+        }
+    };
+
+    pub static ref REQUEST_NETWORK_TYPE_DATA: TypeData = TypeData {
+        index: HashMap::new(),
+        members: hashmap! {
+            // net.start("GET", "https://example.com", "tag")
+            "start" => method!((vec![STRING, STRING, STRING]) -> Type::Void; |val, mut args| {
+                let method = args.remove(0).to_str();
+                let url = args.remove(0).to_str();
+                let tag = args.remove(0).to_str();
+
+                component_func(
+                    "requestnetworkStartRequestNetwork",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("start")
+                        .text("network")
+                        .text("request")
+                        .text("to")
+                        .text("method")
+                        .arg(menu_arg("method", method))
+                        .text("to")
+                        .text("url")
+                        .arg(string_arg(url))
                         .text("with")
-                        .text("decimal")
+                        .text("tag")
+                        .arg(string_arg(tag))
                         .build(),
-                    BlockType::Argument(ArgumentBlockReturnType::String)
+                    BlockType::Regular,
+                )
+            })
+        }
+    };
+
+    pub static ref FIREBASE_DB_TYPE_DATA: TypeData = TypeData {
+        index: HashMap::new(),
+        members: hashmap! {
+            "remove" => method!((vec![STRING]) -> Type::Void; |val, mut args| {
+                component_func(
+                    "firebaseDelete",
+                    BlockContent::builder()
+                        .arg(component_arg(val))
+                        .text("delete")
+                        .text("key")
+                        .arg(string_arg(args.remove(0).to_str()))
+                        .build(),
+                    BlockType::Regular,
                 )
             })
         }
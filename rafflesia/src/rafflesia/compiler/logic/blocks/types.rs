@@ -6,6 +6,7 @@ use swrs::api::block::{
     ArgValue, Argument, ArgumentBlockReturnType, Block, BlockCategory, BlockContent, BlockType,
     ListItem,
 };
+use swrs::api::component::ComponentKind;
 use swrs::api::view::{View, ViewType as SWRSViewType};
 use swrs::parser::logic::list_variable::ListVariable as SWRSListVariable;
 use swrs::parser::logic::variable::{Variable as SWRSVariable, VariableType as SWRSVariableType};
@@ -31,8 +32,10 @@ pub enum PrimitiveType {
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum ComplexType {
-    List { inner_type: PrimitiveType }, // todo: restrict to only Number and String
-    Map,                                // todo: map
+    // lists and maps can only store numbers and strings
+    List { inner_type: PrimitiveType },
+    // sketchware's maps are untyped, the inner type is only enforced by rafflesia
+    Map { inner_type: PrimitiveType },
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -54,6 +57,9 @@ pub enum ViewType {
     Fab,
     AdView,
     MapView,
+
+    // any kind of view, used on moreblock parameters
+    View,
 }
 
 impl FromStr for ViewType {
@@ -78,6 +84,7 @@ impl FromStr for ViewType {
             "Fab" => ViewType::Fab, // "Fab" might not be valid as a typename
             "AdView" => ViewType::AdView,
             "MapView" => ViewType::MapView,
+            "View" => ViewType::View,
             _ => return Err(()),
         })
     }
@@ -103,6 +110,7 @@ impl ToString for ViewType {
             ViewType::Fab => "Fab", // "Fab" might not be valid as a typename
             ViewType::AdView => "AdView",
             ViewType::MapView => "MapView",
+            ViewType::View => "View",
         }
         .to_string()
     }
@@ -133,9 +141,156 @@ impl ViewType {
     }
 }
 
+impl Type {
+    // checks whether a value of type `other` can be given where this type is expected, any view
+    // can be given to `View`
+    pub fn accepts(&self, other: &Type) -> bool {
+        match (self, other) {
+            (Type::View(ViewType::View), Type::View(_)) => true,
+            (this, other) => this == other,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum ComponentType {
-    // todo: components
+    Intent,
+    SharedPreferences,
+    Timer,
+    Dialog,
+    Calendar,
+    Vibrator,
+    RequestNetwork,
+    FirebaseDB,
+}
+
+impl FromStr for ComponentType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "intent" => ComponentType::Intent,
+            "sharedpreferences" => ComponentType::SharedPreferences,
+            "timer" => ComponentType::Timer,
+            "dialog" => ComponentType::Dialog,
+            "calendar" => ComponentType::Calendar,
+            "vibrator" => ComponentType::Vibrator,
+            "requestnetwork" => ComponentType::RequestNetwork,
+            "firebasedb" => ComponentType::FirebaseDB,
+            _ => return Err(()),
+        })
+    }
+}
+
+impl ToString for ComponentType {
+    fn to_string(&self) -> String {
+        match self {
+            ComponentType::Intent => "intent",
+            ComponentType::SharedPreferences => "sharedpreferences",
+            ComponentType::Timer => "timer",
+            ComponentType::Dialog => "dialog",
+            ComponentType::Calendar => "calendar",
+            ComponentType::Vibrator => "vibrator",
+            ComponentType::RequestNetwork => "requestnetwork",
+            ComponentType::FirebaseDB => "firebasedb",
+        }
+        .to_string()
+    }
+}
+
+impl ComponentType {
+    // the arguments that must be given when declaring this component
+    pub fn arguments(&self) -> &'static [&'static str] {
+        match self {
+            ComponentType::SharedPreferences => &["file"],
+            ComponentType::FirebaseDB => &["path"],
+            _ => &[],
+        }
+    }
+
+    // creates the swrs component out of the arguments of its declaration, the arguments are in
+    // the same order as [`ComponentType::arguments`]
+    pub fn to_swrs_component(self, mut arguments: Vec<String>) -> ComponentKind {
+        match self {
+            ComponentType::Intent => ComponentKind::Intent,
+            ComponentType::SharedPreferences => ComponentKind::SharedPreferences {
+                file_name: arguments.remove(0),
+            },
+            ComponentType::Timer => ComponentKind::Timer,
+            ComponentType::Dialog => ComponentKind::Dialog,
+            ComponentType::Calendar => ComponentKind::Calendar,
+            ComponentType::Vibrator => ComponentKind::Vibrator,
+            ComponentType::RequestNetwork => ComponentKind::RequestNetwork,
+            ComponentType::FirebaseDB => ComponentKind::FirebaseDB {
+                path: arguments.remove(0),
+            },
+        }
+    }
+
+    // the id of this component's type on sketchware, used on the component events
+    pub fn swrs_id(&self) -> u8 {
+        match self {
+            ComponentType::Intent => 1,
+            ComponentType::SharedPreferences => 2,
+            ComponentType::Calendar => 3,
+            ComponentType::Vibrator => 4,
+            ComponentType::Timer => 5,
+            ComponentType::FirebaseDB => 6,
+            ComponentType::Dialog => 7,
+            ComponentType::RequestNetwork => 17,
+        }
+    }
+
+    // returns the parameters of an event of this component, None if this component doesn't have
+    // the event
+    pub fn event_parameters(&self, event_name: &str) -> Option<Vec<(String, Type)>> {
+        const STRING: Type = Type::Primitive(PrimitiveType::String);
+        const NUMBER: Type = Type::Primitive(PrimitiveType::Number);
+        const MAP: Type = Type::Complex(ComplexType::Map {
+            inner_type: PrimitiveType::String,
+        });
+
+        let parameters: &[(&str, Type)] = match (self, event_name) {
+            (ComponentType::RequestNetwork, "onResponse") => {
+                &[("tag", STRING), ("response", STRING)]
+            }
+            (ComponentType::RequestNetwork, "onErrorResponse") => {
+                &[("tag", STRING), ("message", STRING)]
+            }
+            (ComponentType::FirebaseDB, "onChildAdded" | "onChildChanged" | "onChildRemoved") => {
+                &[("childKey", STRING), ("childValue", MAP)]
+            }
+            (ComponentType::FirebaseDB, "onCancelled") => {
+                &[("errorCode", NUMBER), ("errorMessage", STRING)]
+            }
+            (ComponentType::Dialog, "onPositiveClick" | "onNegativeClick" | "onNeutralClick") => {
+                &[]
+            }
+            (ComponentType::Timer, "onTick") => &[],
+            _ => return None,
+        };
+
+        Some(
+            parameters
+                .iter()
+                .map(|(name, typ)| (name.to_string(), *typ))
+                .collect(),
+        )
+    }
+
+    // the menu name sketchware uses to refer to this component on its blocks, e.g. `%m.intent`
+    pub fn menu_name(&self) -> &'static str {
+        match self {
+            ComponentType::Intent => "intent",
+            ComponentType::SharedPreferences => "file",
+            ComponentType::Timer => "timer",
+            ComponentType::Dialog => "dialog",
+            ComponentType::Calendar => "calendar",
+            ComponentType::Vibrator => "vibrator",
+            ComponentType::RequestNetwork => "requestnetwork",
+            ComponentType::FirebaseDB => "firebase",
+        }
+    }
 }
 
 impl Type {
@@ -155,10 +310,8 @@ impl Type {
                 Type::View(ViewType::from_str(type_name).ok()?)
             }
 
-            ArgumentBlockReturnType::Component { type_name } =>
-            // Type::Component(ComponentType::from_str(&type_name).ok?)
-            {
-                todo!("components")
+            ArgumentBlockReturnType::Component { type_name } => {
+                Type::Component(ComponentType::from_str(type_name).ok()?)
             }
         })
     }
@@ -176,7 +329,8 @@ impl Type {
                     PrimitiveType::Boolean => unreachable!(),
                 },
             },
-            Type::Complex(ComplexType::Map) => todo!("map"),
+            // maps are referenced by their names, they don't have an argument block
+            Type::Complex(ComplexType::Map { .. }) => unreachable!("maps don't have arg blocks"),
             Type::View(view) =>
             // fixme: uhhh i think this might not work on some views
             {
@@ -184,7 +338,9 @@ impl Type {
                     type_name: view.to_string(),
                 }
             }
-            Type::Component(_) => todo!("components"),
+            Type::Component(component) => ArgumentBlockReturnType::Component {
+                type_name: component.to_string(),
+            },
         }
     }
 }
@@ -201,8 +357,9 @@ pub enum TypeValue {
         var_name: ArgValue<String>,
     },
     Map {
+        inner_type: PrimitiveType,
         var_name: ArgValue<String>,
-    }, // todo: map
+    },
     View {
         view_type: ViewType,
         id: ArgValue<String>,
@@ -222,7 +379,9 @@ impl TypeValue {
             TypeValue::List { inner_type, .. } => Type::Complex(ComplexType::List {
                 inner_type: *inner_type,
             }),
-            TypeValue::Map { .. } => Type::Complex(ComplexType::Map),
+            TypeValue::Map { inner_type, .. } => Type::Complex(ComplexType::Map {
+                inner_type: *inner_type,
+            }),
             TypeValue::View { view_type, .. } => Type::View(*view_type),
             TypeValue::Component { component_type, .. } => Type::Component(*component_type),
         }
@@ -255,8 +414,8 @@ impl TypeValue {
         List { inner_type, var_name } => (inner_type, var_name)
     });
 
-    type_value_func!(map to_map -> ArgValue<String> {
-        Map { var_name } => var_name
+    type_value_func!(map to_map -> (PrimitiveType, ArgValue<String>) {
+        Map { inner_type, var_name } => (inner_type, var_name)
     });
 
     type_value_func!(view to_view -> (ViewType, ArgValue<String>) {
@@ -274,6 +433,9 @@ impl TypeValue {
 pub struct Definitions<'a> {
     // type can only be Type::Primitive and Type::Complex
     variables: LinkedHashMap<String, Type>,
+    more_blocks: LinkedHashMap<String, MoreBlockDefinition>,
+    // the parameters of the moreblock whose body is being compiled
+    parameters: LinkedHashMap<String, Type>,
     layout_ref: &'a View,
 }
 
@@ -281,10 +443,45 @@ impl<'a> Definitions<'a> {
     pub fn new(layout_ref: &'a View) -> Self {
         Self {
             variables: Default::default(),
+            more_blocks: Default::default(),
+            parameters: Default::default(),
             layout_ref,
         }
     }
 
+    // returns None when the moreblock name is already used by another moreblock or a global
+    // function
+    pub fn add_more_block(&mut self, more_block: MoreBlockDefinition) -> Option<String> {
+        if self.more_blocks.contains_key(&more_block.name) {
+            return None;
+        }
+        if GLOBAL_FUNCTIONS.contains_key(&more_block.name) {
+            return None;
+        }
+
+        let name = more_block.name.clone();
+        self.more_blocks.insert(name.clone(), more_block);
+
+        Some(name)
+    }
+
+    pub fn get_more_block(&self, name: &str) -> Option<&MoreBlockDefinition> {
+        self.more_blocks.get(name)
+    }
+
+    // creates the definitions used to compile the body of a moreblock or an event, its
+    // parameters are only accessible inside of it
+    pub fn with_parameters(&self, parameters: &[(String, Type)]) -> Definitions<'a> {
+        let mut definitions = self.clone();
+        definitions.parameters = parameters.iter().cloned().collect();
+        definitions
+    }
+
+    // parameters shadow variables with the same name
+    pub fn get_parameter(&self, name: &str) -> Option<Type> {
+        self.parameters.get(name).copied()
+    }
+
     // returns None when the variable name is already used
     pub fn add_variable(&mut self, name: String, typ: Type) -> Option<String> {
         if self.variables.contains_key(&name) {
@@ -316,16 +513,21 @@ impl<'a> Definitions<'a> {
             Type::Primitive(PrimitiveType::String) => None, // todo
             Type::Primitive(PrimitiveType::Number) => Some(super::NUMBER_TYPE_DATA.deref()),
             Type::Primitive(PrimitiveType::Boolean) => None, // todo
-            Type::Complex(ComplexType::Map) => None,         // todo
+            Type::Complex(ComplexType::Map {
+                inner_type: PrimitiveType::String,
+            }) => Some(super::STRING_MAP_TYPE_DATA.deref()),
+            Type::Complex(ComplexType::Map {
+                inner_type: PrimitiveType::Number,
+            }) => Some(super::NUMBER_MAP_TYPE_DATA.deref()),
             Type::Complex(ComplexType::List {
                 inner_type: PrimitiveType::String,
-            }) => None, // todo
+            }) => Some(super::STRING_LIST_TYPE_DATA.deref()),
             Type::Complex(ComplexType::List {
                 inner_type: PrimitiveType::Number,
-            }) => None, // todo
-            Type::View(_) => None,                           // todo
-            Type::Component(_) => None,                      // todo
-            _ => panic!("list cant have bool inner type"),
+            }) => Some(super::NUMBER_LIST_TYPE_DATA.deref()),
+            Type::View(_) => None, // todo
+            Type::Component(component) => Some(super::component_type_data(component)),
+            _ => panic!("lists and maps cant have bool inner type"),
         }
     }
 
@@ -374,7 +576,16 @@ impl<'a> Definitions<'a> {
                     );
                 }
 
-                Type::Complex(ComplexType::Map) => todo!(),
+                Type::Complex(ComplexType::Map { .. }) => {
+                    variables.insert(
+                        name.clone(),
+                        SWRSVariable {
+                            name,
+                            r#type: SWRSVariableType::HashMap,
+                        },
+                    );
+                }
+
                 _ => (),
             }
         }
@@ -514,6 +725,109 @@ impl GlobalFunction {
     }
 }
 
+// a moreblock declared on the logic code
+#[derive(Debug, Clone)]
+pub struct MoreBlockDefinition {
+    pub name: String,
+    pub parameters: Vec<(String, Type)>,
+}
+
+impl MoreBlockDefinition {
+    // the spec of this moreblock, e.g. `greet %s.name %m.view.target`
+    pub fn spec(&self) -> String {
+        let mut spec = vec![self.name.clone()];
+
+        for (name, typ) in self.parameters.iter() {
+            spec.push(match typ {
+                Type::Primitive(PrimitiveType::Number) => format!("%d.{name}"),
+                Type::Primitive(PrimitiveType::String) => format!("%s.{name}"),
+                Type::Primitive(PrimitiveType::Boolean) => format!("%b.{name}"),
+                // unwrap: parameters can only be primitives, lists, maps and views
+                typ => format!("%m.{}", parameter_menu_name(name, typ).unwrap()),
+            });
+        }
+
+        spec.join(" ")
+    }
+
+    // generates a `definedFunc` block that calls this moreblock
+    pub fn generate(&self, args: Vec<TypeValue>) -> Result<Block, GenerateError> {
+        let expected = self
+            .parameters
+            .iter()
+            .map(|(_, typ)| *typ)
+            .collect::<Vec<Type>>();
+        let args_types = args.iter().map(|arg| arg.as_type()).collect::<Vec<Type>>();
+
+        if args_types.len() != expected.len() {
+            return Err(GenerateError::InvalidArgumentCount {
+                expected,
+                got: args_types,
+            });
+        }
+
+        for index in 0..args_types.len() {
+            if !expected[index].accepts(&args_types[index]) {
+                return Err(GenerateError::InvalidArgumentType {
+                    expected,
+                    got: args_types,
+                    index,
+                });
+            }
+        }
+
+        let mut content = BlockContent::builder().text(&self.name);
+
+        for ((name, typ), arg) in self.parameters.iter().zip(args) {
+            content = content.arg(match arg {
+                TypeValue::Number(value) => Argument::Number {
+                    name: Some(name.clone()),
+                    value,
+                },
+                TypeValue::String(value) => Argument::String {
+                    name: Some(name.clone()),
+                    value,
+                },
+                TypeValue::Boolean(value) => Argument::Boolean {
+                    name: Some(name.clone()),
+                    value,
+                },
+                TypeValue::List {
+                    var_name: value, ..
+                }
+                | TypeValue::Map {
+                    var_name: value, ..
+                }
+                | TypeValue::View { id: value, .. }
+                | TypeValue::Component { id: value, .. } => Argument::Menu {
+                    // unwrap: the argument types are checked above
+                    name: parameter_menu_name(name, typ).unwrap(),
+                    value,
+                },
+            });
+        }
+
+        Ok(super::defined_func(content.build()))
+    }
+}
+
+// the name of the menu of a moreblock parameter that is passed by reference, e.g. `listInt.items`
+fn parameter_menu_name(name: &str, typ: &Type) -> Option<String> {
+    let kind = match typ {
+        Type::Complex(ComplexType::List {
+            inner_type: PrimitiveType::Number,
+        }) => "listInt",
+        Type::Complex(ComplexType::List {
+            inner_type: PrimitiveType::String,
+        }) => "listStr",
+        Type::Complex(ComplexType::Map { .. }) => "varMap",
+        Type::View(_) => "view",
+        _ => return None,
+    };
+
+    Some(format!("{kind}.{name}"))
+}
+
 #[derive(Debug, Error, Clone)]
 pub enum GenerateError {
     #[error("invalid arguments given: expected {} total arguments, got {}", .expected.len(), .got.len())]
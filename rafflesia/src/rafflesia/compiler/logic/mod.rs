@@ -1,21 +1,29 @@
 use std::fmt::Debug;
-use swrs::api::block::{ArgValue, ArgumentBlockReturnType, Block, BlockType, Blocks};
+use std::str::FromStr;
+use swrs::api::block::{
+    ArgValue, Argument, ArgumentBlockReturnType, Block, BlockContent, BlockControl, BlockType,
+    Blocks, ListItem,
+};
 use swrs::api::component::ComponentKind;
 use swrs::api::screen::Event;
 use swrs::api::screen::{EventType, MoreBlock};
 use swrs::api::view::View;
+use swrs::color::Color;
 use swrs::parser::logic::list_variable::ListVariable;
 use swrs::parser::logic::variable::Variable;
 use swrs::LinkedHashMap;
 use thiserror::Error;
 
 use crate::compiler::logic::ast::{
-    BinaryOperator, ComplexVariableType, Expression, InnerStatement, InnerStatements, Literal,
-    OuterStatement, OuterStatements, PrimaryExpression, UnaryOperator, VariableType,
+    Arguments, BinaryOperator, ComplexVariableType, ComponentArgument, Expression, IndexAssignment,
+    InnerStatement, InnerStatements, Literal, OuterStatement, OuterStatements, ParameterType,
+    PrimaryExpression, RawBlock, UnaryOperator, VariableType,
 };
 use crate::compiler::logic::blocks::types::{
-    ComplexType, Definitions, GenerateError, Member, PrimitiveType, Type, TypeValue,
+    ComplexType, ComponentType, Definitions, GenerateError, Member, MoreBlockDefinition,
+    PrimitiveType, Type, TypeValue, ViewType,
 };
+use crate::compiler::{CompileErrors, Span};
 
 pub mod ast;
 mod blocks;
@@ -24,24 +32,28 @@ pub mod parser;
 #[cfg(test)]
 mod tests;
 
-// todo: add positions in AST
 // todo: a custom result handling system similar to error-stack
 
-/// Compiles a logic AST into blocks
+/// Compiles a logic AST into blocks.
+///
+/// Compilation doesn't stop at the first error; statements that failed to compile are skipped and
+/// every error found throughout the code are returned.
 pub fn compile_logic(
     statements: OuterStatements,
     attached_layout: &View,
-) -> Result<LogicCompileResult, LogicCompileError> {
+) -> Result<LogicCompileResult, CompileErrors<LogicCompileError>> {
     let mut definitions = Definitions::new(attached_layout);
-    let /* mut */ more_blocks = LinkedHashMap::new();
-    let /* mut */ components = LinkedHashMap::new();
+    let mut more_blocks = Vec::new();
+    let mut components = LinkedHashMap::new();
     let mut events = Vec::new();
+    let mut errors = Vec::new();
 
     for outer_statement in statements.0 {
         match outer_statement {
             OuterStatement::SimpleVariableDeclaration {
                 variable_type,
                 identifier,
+                ..
             } => {
                 definitions.add_variable(
                     identifier,
@@ -56,16 +68,23 @@ pub fn compile_logic(
             OuterStatement::ComplexVariableDeclaration {
                 variable_type,
                 identifier,
+                span,
             } => {
-                // fixme: apparently you cant set types on map, perhaps we could add a some
-                //        kind of type safety layer on rafflesia so maps are "typed"
-
                 // fixme: list maps :weary:
 
-                definitions.add_variable(identifier, complex_variable_type_to_type(variable_type));
+                match complex_variable_type_to_type(variable_type, &span) {
+                    Ok(typ) => {
+                        definitions.add_variable(identifier, typ);
+                    }
+                    Err(err) => errors.push(err),
+                }
             }
 
-            OuterStatement::ActivityEventListener { event_name, body } => {
+            OuterStatement::ActivityEventListener {
+                event_name,
+                body,
+                span,
+            } => {
                 events.push((
                     Event {
                         name: event_name,
@@ -73,6 +92,7 @@ pub fn compile_logic(
                         code: Blocks::new(), // will be compiled later
                     },
                     body,
+                    span,
                 ));
             }
 
@@ -80,7 +100,10 @@ pub fn compile_logic(
                 view_id,
                 event_name,
                 body,
+                span,
             } => {
+                // this might be a component listener, it will be resolved later when every
+                // components have been declared
                 events.push((
                     Event {
                         name: event_name,
@@ -88,22 +111,139 @@ pub fn compile_logic(
                         code: Blocks::new(), // will be compiled later
                     },
                     body,
+                    span,
                 ));
             }
+
+            OuterStatement::ComponentDeclaration {
+                component_type,
+                identifier,
+                arguments,
+                span,
+            } => match compile_component(&component_type, arguments, &span) {
+                Ok((component_type, kind)) => {
+                    let typ = Type::Component(component_type);
+
+                    if definitions.add_variable(identifier.clone(), typ).is_none() {
+                        errors.push(LogicCompileError::VariableAlreadyExists {
+                            existing_var_type: definitions
+                                .get_var(&identifier)
+                                .unwrap_or(Type::Void),
+                            var_name: identifier,
+                            var_type: typ,
+                            span,
+                        });
+                        continue;
+                    }
+
+                    components.insert(identifier, kind);
+                }
+                Err(err) => errors.push(err),
+            },
+
+            OuterStatement::MoreBlockDeclaration {
+                name,
+                parameters,
+                body,
+                span,
+            } => {
+                let mut more_block = MoreBlockDefinition {
+                    name: name.clone(),
+                    parameters: Vec::new(),
+                };
+
+                for parameter in parameters {
+                    let typ =
+                        match parameter_type_to_type(parameter.parameter_type, &parameter.span) {
+                            Ok(typ) => typ,
+                            Err(err) => {
+                                errors.push(err);
+                                continue;
+                            }
+                        };
+
+                    if let Some((_, existing)) = more_block
+                        .parameters
+                        .iter()
+                        .find(|(name, _)| *name == parameter.name)
+                    {
+                        errors.push(LogicCompileError::VariableAlreadyExists {
+                            var_name: parameter.name,
+                            var_type: typ,
+                            existing_var_type: *existing,
+                            span: parameter.span,
+                        });
+                        continue;
+                    }
+
+                    more_block.parameters.push((parameter.name, typ));
+                }
+
+                if definitions.add_more_block(more_block.clone()).is_none() {
+                    errors.push(LogicCompileError::MoreBlockAlreadyExists { name, span });
+                    continue;
+                }
+
+                more_blocks.push((more_block, body));
+            }
         }
     }
 
-    // compile the events' blocks now that we have access to all of the variables
+    // compile the moreblocks' and events' blocks now that we have access to all of the variables
+    // and moreblocks
+    let more_blocks = more_blocks
+        .into_iter()
+        .map(|(more_block, body)| {
+            let spec = BlockContent::parse_wo_params(&more_block.spec())
+                // expect: the spec is made out of identifiers
+                .expect("invalid moreblock spec");
+            let code = compile_inner_statements(
+                body,
+                &definitions.with_parameters(&more_block.parameters),
+                &mut errors,
+            );
+
+            (
+                more_block.name.clone(),
+                MoreBlock {
+                    id: more_block.name,
+                    spec,
+                    code,
+                },
+            )
+        })
+        .collect();
+
     let events = events
         .into_iter()
-        .map(|(event, body)| {
-            Ok(Event {
+        .filter_map(|(event, body, span)| {
+            let (event_type, parameters) =
+                match resolve_event_type(event.event_type, &event.name, &definitions, span) {
+                    Ok(resolved) => resolved,
+                    Err(err) => {
+                        errors.push(err);
+                        return None;
+                    }
+                };
+
+            // event parameters are accessed the same way as moreblock parameters
+            let code = compile_inner_statements(
+                body,
+                &definitions.with_parameters(&parameters),
+                &mut errors,
+            );
+
+            Some(Event {
                 name: event.name,
-                event_type: event.event_type,
-                code: compile_inner_statements(body, &definitions)?,
+                event_type,
+                code,
             })
         })
-        .collect::<Result<_, LogicCompileError>>()?;
+        .collect();
+
+    if !errors.is_empty() {
+        return Err(CompileErrors(errors));
+    }
 
     let (variables, list_variables) = definitions.deconstruct();
 
@@ -116,92 +256,158 @@ pub fn compile_logic(
     })
 }
 
+// statements that failed to compile are skipped and their errors are collected into `errors`, so
+// the rest of the statements can still be checked
 fn compile_inner_statements(
     stmts: InnerStatements,
     definitions: &Definitions,
-) -> Result<Blocks, LogicCompileError> {
+    errors: &mut Vec<LogicCompileError>,
+) -> Blocks {
     let mut result = Vec::new();
 
     for statement in stmts.0 {
-        match statement {
-            InnerStatement::VariableAssignment(var_assign) => {
-                let var = definitions.get_var(&var_assign.identifier).ok_or_else(|| {
-                    LogicCompileError::VariableDoesntExist {
-                        identifier: var_assign.identifier.clone(),
-                    }
-                })?;
-
-                // only primitive types can be assigned to a value
-                // todo: maybe allow complex types as well? that'd be a cool feature
-                let var_type = match var {
-                    Type::Primitive(primitive_type) => primitive_type,
-                    _ => {
-                        return Err(LogicCompileError::UnAssignableVariable {
-                            identifier: var_assign.identifier.clone(),
-                            variable_type: Type::Void,
-                        })
-                    }
-                };
+        match compile_inner_statement(statement, definitions, errors) {
+            Ok(block) => result.push(block),
+            Err(err) => errors.push(err),
+        }
+    }
 
-                let value = compile_expression(var_assign.value, &definitions)?;
+    Blocks(result)
+}
 
-                result.push(match var_type {
-                    PrimitiveType::Boolean => {
-                        blocks::set_var_boolean(var_assign.identifier, value.to_bool_arg()?)
-                    }
-                    PrimitiveType::Number => {
-                        blocks::set_var_int(var_assign.identifier, value.to_num_arg()?)
-                    }
-                    PrimitiveType::String => {
-                        blocks::set_var_string(var_assign.identifier, value.to_str_arg()?)
-                    }
+fn compile_inner_statement(
+    statement: InnerStatement,
+    definitions: &Definitions,
+    errors: &mut Vec<LogicCompileError>,
+) -> Result<Block, LogicCompileError> {
+    Ok(match statement {
+        InnerStatement::VariableAssignment(var_assign) => {
+            // moreblock parameters are read-only
+            if let Some(typ) = definitions.get_parameter(&var_assign.identifier) {
+                return Err(LogicCompileError::UnAssignableVariable {
+                    identifier: var_assign.identifier,
+                    variable_type: typ,
+                    span: var_assign.span,
                 });
             }
 
-            InnerStatement::IfStatement(if_stmt) => {
-                let condition =
-                    compile_expression(if_stmt.condition, &definitions)?.to_bool_arg()?;
+            let var = definitions.get_var(&var_assign.identifier).ok_or_else(|| {
+                LogicCompileError::VariableDoesntExist {
+                    identifier: var_assign.identifier.clone(),
+                    span: var_assign.span.clone(),
+                }
+            })?;
+
+            // only primitive types can be assigned to a value
+            // todo: maybe allow complex types as well? that'd be a cool feature
+            let var_type = match var {
+                Type::Primitive(primitive_type) => primitive_type,
+                other => {
+                    return Err(LogicCompileError::UnAssignableVariable {
+                        identifier: var_assign.identifier.clone(),
+                        variable_type: other,
+                        span: var_assign.span,
+                    })
+                }
+            };
 
-                let body = compile_inner_statements(if_stmt.body, &definitions)?;
-                let else_body = if_stmt
-                    .else_body
-                    .map(|else_body| compile_inner_statements(else_body, &definitions))
-                    .transpose()?;
+            let value_span = var_assign.value.span();
+            let value = compile_expression(var_assign.value, definitions)?;
 
-                result.push(match else_body {
-                    None => blocks::r#if(condition, body),
-                    Some(else_body) => blocks::if_else(condition, body, else_body),
-                });
+            match var_type {
+                PrimitiveType::Boolean => {
+                    blocks::set_var_boolean(var_assign.identifier, value.to_bool_arg(&value_span)?)
+                }
+                PrimitiveType::Number => {
+                    blocks::set_var_int(var_assign.identifier, value.to_num_arg(&value_span)?)
+                }
+                PrimitiveType::String => {
+                    blocks::set_var_string(var_assign.identifier, value.to_str_arg(&value_span)?)
+                }
             }
+        }
 
-            InnerStatement::RepeatStatement(repeat_stmt) => {
-                let value =
-                    compile_expression(repeat_stmt.condition, &definitions)?.to_num_arg()?;
+        // `from[index] = value` is a call to `from.put(index, value)` on maps and to
+        // `from.set(index, value)` on lists, their types are checked by the methods
+        InnerStatement::IndexAssignment(IndexAssignment {
+            from,
+            index,
+            value,
+            span,
+        }) => {
+            let from_span = from.span();
+            let from_value = compile_expression(*from.clone(), definitions)?;
+            let typ = from_value.get_type(&from_span)?.ok_or_else(|| {
+                LogicCompileError::RegularBlockAsAnyArg {
+                    block: from_value.clone().expect_block(&from_span).unwrap(),
+                    span: from_span.clone(),
+                }
+            })?;
 
-                let body = compile_inner_statements(repeat_stmt.body, &definitions)?;
+            let method = match typ {
+                Type::Complex(ComplexType::Map { .. }) => "put",
+                Type::Complex(ComplexType::List { .. }) => "set",
+                typ => return Err(LogicCompileError::IndexCannotBeAssigned { typ, span }),
+            };
 
-                result.push(blocks::repeat(value, body));
-            }
+            let call = Expression::PrimaryExpression(PrimaryExpression::Call {
+                from: Some(from),
+                name: method.to_string(),
+                arguments: Arguments(vec![*index, value]),
+                span: span.clone(),
+            });
 
-            InnerStatement::ForeverStatement(forever_stmt) => {
-                let body = compile_inner_statements(forever_stmt.body, &definitions)?;
+            compile_expression(call, definitions)?.expect_block(&span)?
+        }
 
-                result.push(blocks::forever(body));
-            }
+        InnerStatement::IfStatement(if_stmt) => {
+            let condition_span = if_stmt.condition.span();
+            let condition = compile_expression(if_stmt.condition, definitions)
+                .and_then(|condition| condition.to_bool_arg(&condition_span));
 
-            InnerStatement::Break => result.push(blocks::r#break()),
-            InnerStatement::Continue => result.push(blocks::r#continue()),
-            InnerStatement::Expression(expr) => {
-                result.push(compile_expression(expr, &definitions)?.expect_block()?)
+            // the bodies are compiled even if the condition fails to, to check them for errors
+            let body = compile_inner_statements(if_stmt.body, definitions, errors);
+            let else_body = if_stmt
+                .else_body
+                .map(|else_body| compile_inner_statements(else_body, definitions, errors));
+
+            match else_body {
+                None => blocks::r#if(condition?, body),
+                Some(else_body) => blocks::if_else(condition?, body, else_body),
             }
         }
-    }
 
-    Ok(Blocks(result))
+        InnerStatement::RepeatStatement(repeat_stmt) => {
+            let condition_span = repeat_stmt.condition.span();
+            let value = compile_expression(repeat_stmt.condition, definitions)
+                .and_then(|value| value.to_num_arg(&condition_span));
+
+            let body = compile_inner_statements(repeat_stmt.body, definitions, errors);
+
+            blocks::repeat(value?, body)
+        }
+
+        InnerStatement::ForeverStatement(forever_stmt) => blocks::forever(
+            compile_inner_statements(forever_stmt.body, definitions, errors),
+        ),
+
+        InnerStatement::Break { .. } => blocks::r#break(),
+        InnerStatement::Continue { .. } => blocks::r#continue(),
+        InnerStatement::Expression(expr) => {
+            let span = expr.span();
+            compile_expression(expr, definitions)?.expect_block(&span)?
+        }
+        InnerStatement::RawBlock(raw_block) => {
+            let span = raw_block.span.clone();
+            ExprValue::from_block(compile_raw_block(raw_block, definitions, errors)?)
+                .expect_block(&span)?
+        }
+    })
 }
 
 // the return value of [`compile_expression`], can either be a regular block, an argument block or
-// a literal
+// a literal. its conversion functions take the span of the expression it came from to be put on
+// the errors
 #[derive(Debug, Clone)]
 enum ExprValue {
     // a regular freestanding block, has a type of BlockType::Regular
@@ -211,130 +417,135 @@ enum ExprValue {
     ArgBlock(Block),
     // a literal value
     Literal(Literal),
+    // a map, maps don't have argument blocks, they're referenced by their variable name instead
+    Map {
+        inner_type: PrimitiveType,
+        var_name: ArgValue<String>,
+    },
 }
 
 impl ExprValue {
-    fn to_num_arg(self) -> Result<ArgValue<f64>, LogicCompileError> {
+    fn to_num_arg(self, span: &Span) -> Result<ArgValue<f64>, LogicCompileError> {
         Ok(match self {
             ExprValue::Block(block) => {
                 return Err(LogicCompileError::RegularBlockAsArg {
                     block,
                     expected_arg_type: Type::Primitive(PrimitiveType::Number),
+                    span: span.clone(),
                 })
             }
-            ExprValue::ArgBlock(block) => {
-                match block.block_type {
-                    BlockType::Argument(ArgumentBlockReturnType::Number) => ArgValue::Block(block),
-                    other => Err(LogicCompileError::TypeError {
-                        expected: Type::Primitive(PrimitiveType::Number),
-
-                        // expect n panic: it's an error if ArgBlock is given a block type other
-                        //                 than arg blocks
-                        got: if let BlockType::Argument(arg) = other {
-                            Type::from_arg_block(&arg)
-                                .expect(&*format!("Invalid block argument: {:?}", arg))
-                        } else {
-                            panic!("ArgBlock() is given a block with type {:?}", other)
-                        },
-                    })?,
-                }
-            }
+            ExprValue::ArgBlock(block) => match block.block_type {
+                BlockType::Argument(ArgumentBlockReturnType::Number) => ArgValue::Block(block),
+                _ => Err(LogicCompileError::TypeError {
+                    expected: Type::Primitive(PrimitiveType::Number),
+                    got: arg_block_type(&block, span)?,
+                    span: span.clone(),
+                })?,
+            },
             ExprValue::Literal(literal) => match literal {
                 Literal::Number(num) => ArgValue::Value(num),
                 Literal::Boolean(_) => Err(LogicCompileError::TypeError {
                     expected: Type::Primitive(PrimitiveType::Number),
                     got: Type::Primitive(PrimitiveType::Boolean),
+                    span: span.clone(),
                 })?,
                 Literal::String(_) => Err(LogicCompileError::TypeError {
                     expected: Type::Primitive(PrimitiveType::Number),
                     got: Type::Primitive(PrimitiveType::String),
+                    span: span.clone(),
                 })?,
             },
+            ExprValue::Map { inner_type, .. } => Err(LogicCompileError::TypeError {
+                expected: Type::Primitive(PrimitiveType::Number),
+                got: Type::Complex(ComplexType::Map { inner_type }),
+                span: span.clone(),
+            })?,
         })
     }
 
-    fn to_bool_arg(self) -> Result<ArgValue<bool>, LogicCompileError> {
+    fn to_bool_arg(self, span: &Span) -> Result<ArgValue<bool>, LogicCompileError> {
         Ok(match self {
             ExprValue::Block(block) => {
                 return Err(LogicCompileError::RegularBlockAsArg {
                     block,
                     expected_arg_type: Type::Primitive(PrimitiveType::Boolean),
+                    span: span.clone(),
                 })
             }
-            ExprValue::ArgBlock(block) => {
-                match block.block_type {
-                    BlockType::Argument(ArgumentBlockReturnType::Boolean) => ArgValue::Block(block),
-                    other => Err(LogicCompileError::TypeError {
-                        expected: Type::Primitive(PrimitiveType::Boolean),
-
-                        // expect n panic: it's an error if ArgBlock is given a block type other
-                        //                 than arg blocks
-                        got: if let BlockType::Argument(arg) = other {
-                            Type::from_arg_block(&arg)
-                                .expect(&*format!("Invalid block argument: {:?}", arg))
-                        } else {
-                            panic!("ArgBlock() is given a block with type {:?}", other)
-                        },
-                    })?,
-                }
-            }
+            ExprValue::ArgBlock(block) => match block.block_type {
+                BlockType::Argument(ArgumentBlockReturnType::Boolean) => ArgValue::Block(block),
+                _ => Err(LogicCompileError::TypeError {
+                    expected: Type::Primitive(PrimitiveType::Boolean),
+                    got: arg_block_type(&block, span)?,
+                    span: span.clone(),
+                })?,
+            },
             ExprValue::Literal(literal) => match literal {
                 Literal::Number(_) => Err(LogicCompileError::TypeError {
                     expected: Type::Primitive(PrimitiveType::Boolean),
                     got: Type::Primitive(PrimitiveType::Number),
+                    span: span.clone(),
                 })?,
                 Literal::Boolean(bool) => ArgValue::Value(bool),
                 Literal::String(_) => Err(LogicCompileError::TypeError {
                     expected: Type::Primitive(PrimitiveType::Boolean),
                     got: Type::Primitive(PrimitiveType::Number),
+                    span: span.clone(),
                 })?,
             },
+            ExprValue::Map { inner_type, .. } => Err(LogicCompileError::TypeError {
+                expected: Type::Primitive(PrimitiveType::Boolean),
+                got: Type::Complex(ComplexType::Map { inner_type }),
+                span: span.clone(),
+            })?,
         })
     }
 
-    fn to_str_arg(self) -> Result<ArgValue<String>, LogicCompileError> {
+    fn to_str_arg(self, span: &Span) -> Result<ArgValue<String>, LogicCompileError> {
         Ok(match self {
             ExprValue::Block(block) => {
                 return Err(LogicCompileError::RegularBlockAsArg {
                     block,
                     expected_arg_type: Type::Primitive(PrimitiveType::String),
+                    span: span.clone(),
                 })
             }
-            ExprValue::ArgBlock(block) => {
-                match block.block_type {
-                    BlockType::Argument(ArgumentBlockReturnType::String) => ArgValue::Block(block),
-                    other => Err(LogicCompileError::TypeError {
-                        expected: Type::Primitive(PrimitiveType::String),
-
-                        // expect n panic: it's an error if ArgBlock is given a block type other
-                        //                 than arg blocks
-                        got: if let BlockType::Argument(arg) = other {
-                            Type::from_arg_block(&arg)
-                                .expect(&*format!("Invalid block argument: {:?}", arg))
-                        } else {
-                            panic!("ArgBlock() is given a block with type {:?}", other)
-                        },
-                    })?,
-                }
-            }
+            ExprValue::ArgBlock(block) => match block.block_type {
+                BlockType::Argument(ArgumentBlockReturnType::String) => ArgValue::Block(block),
+                _ => Err(LogicCompileError::TypeError {
+                    expected: Type::Primitive(PrimitiveType::String),
+                    got: arg_block_type(&block, span)?,
+                    span: span.clone(),
+                })?,
+            },
             ExprValue::Literal(literal) => match literal {
                 Literal::Number(_) => Err(LogicCompileError::TypeError {
                     expected: Type::Primitive(PrimitiveType::String),
                     got: Type::Primitive(PrimitiveType::Number),
+                    span: span.clone(),
                 })?,
                 Literal::Boolean(_) => Err(LogicCompileError::TypeError {
                     expected: Type::Primitive(PrimitiveType::String),
                     got: Type::Primitive(PrimitiveType::Boolean),
+                    span: span.clone(),
                 })?,
                 Literal::String(str) => ArgValue::Value(str),
             },
+            ExprValue::Map { inner_type, .. } => Err(LogicCompileError::TypeError {
+                expected: Type::Primitive(PrimitiveType::String),
+                got: Type::Complex(ComplexType::Map { inner_type }),
+                span: span.clone(),
+            })?,
         })
     }
 
-    fn to_type_value(self) -> Result<TypeValue, LogicCompileError> {
+    fn to_type_value(self, span: &Span) -> Result<TypeValue, LogicCompileError> {
         Ok(match self {
             ExprValue::Block(block) => {
-                return Err(LogicCompileError::RegularBlockAsAnyArg { block })
+                return Err(LogicCompileError::RegularBlockAsAnyArg {
+                    block,
+                    span: span.clone(),
+                })
             }
             ExprValue::ArgBlock(block) => match block.block_type {
                 BlockType::Argument(ArgumentBlockReturnType::Number) => {
@@ -346,13 +557,56 @@ impl ExprValue {
                 BlockType::Argument(ArgumentBlockReturnType::String) => {
                     TypeValue::String(ArgValue::Block(block))
                 }
-                other => panic!("ArgBlock() cannot have a type other than Argument: {other:?}"),
+                BlockType::Argument(ArgumentBlockReturnType::List { inner_type }) => {
+                    TypeValue::List {
+                        inner_type: match inner_type {
+                            ListItem::Number => PrimitiveType::Number,
+                            ListItem::String => PrimitiveType::String,
+                        },
+                        var_name: reference_arg(block),
+                    }
+                }
+                BlockType::Argument(ArgumentBlockReturnType::Component { ref type_name }) => {
+                    TypeValue::Component {
+                        component_type: ComponentType::from_str(type_name).map_err(|_| {
+                            LogicCompileError::InvalidArgBlockType {
+                                block_type: block.block_type.clone(),
+                                span: span.clone(),
+                            }
+                        })?,
+                        id: reference_arg(block),
+                    }
+                }
+                BlockType::Argument(ArgumentBlockReturnType::View { ref type_name }) => {
+                    TypeValue::View {
+                        view_type: ViewType::from_str(type_name).map_err(|_| {
+                            LogicCompileError::InvalidArgBlockType {
+                                block_type: block.block_type.clone(),
+                                span: span.clone(),
+                            }
+                        })?,
+                        id: reference_arg(block),
+                    }
+                }
+                block_type => {
+                    return Err(LogicCompileError::InvalidArgBlockType {
+                        block_type,
+                        span: span.clone(),
+                    })
+                }
             },
             ExprValue::Literal(literal) => match literal {
                 Literal::Number(num) => TypeValue::Number(ArgValue::Value(num)),
                 Literal::Boolean(bool) => TypeValue::Boolean(ArgValue::Value(bool)),
                 Literal::String(str) => TypeValue::String(ArgValue::Value(str)),
             },
+            ExprValue::Map {
+                inner_type,
+                var_name,
+            } => TypeValue::Map {
+                inner_type,
+                var_name,
+            },
         })
     }
 
@@ -365,31 +619,35 @@ impl ExprValue {
     }
 
     // expects a block, otherwise return an `Err(LogicCompileError::DanglingLiteral)`
-    fn expect_block(self) -> Result<Block, LogicCompileError> {
+    fn expect_block(self, span: &Span) -> Result<Block, LogicCompileError> {
         match self {
             ExprValue::Block(block) => Ok(block),
-            ExprValue::ArgBlock(block) => Err(LogicCompileError::DanglingArgBlock { block }),
-            ExprValue::Literal(literal) => Err(LogicCompileError::DanglingLiteral { literal }),
+            ExprValue::ArgBlock(block) => Err(LogicCompileError::DanglingArgBlock {
+                block,
+                span: span.clone(),
+            }),
+            ExprValue::Literal(literal) => Err(LogicCompileError::DanglingLiteral {
+                literal,
+                span: span.clone(),
+            }),
+            ExprValue::Map { .. } => Err(LogicCompileError::DanglingMap { span: span.clone() }),
         }
     }
 
     // gets the type of this expression value. returns None when its a block
-    fn get_type(&self) -> Option<Type> {
-        Some(match self {
-            ExprValue::Block(_) => return None,
-            ExprValue::ArgBlock(block) => {
-                if let BlockType::Argument(arg) = &block.block_type {
-                    Type::from_arg_block(arg).expect(&*format!("Invalid arg block: {:?}", arg))
-                } else {
-                    panic!("ArgBlock cannot have a type other than Argument")
-                }
-            }
+    fn get_type(&self, span: &Span) -> Result<Option<Type>, LogicCompileError> {
+        Ok(Some(match self {
+            ExprValue::Block(_) => return Ok(None),
+            ExprValue::ArgBlock(block) => arg_block_type(block, span)?,
             ExprValue::Literal(literal) => match literal {
                 Literal::Number(_) => Type::Primitive(PrimitiveType::Number),
                 Literal::Boolean(_) => Type::Primitive(PrimitiveType::Boolean),
                 Literal::String(_) => Type::Primitive(PrimitiveType::String),
             },
-        })
+            ExprValue::Map { inner_type, .. } => Type::Complex(ComplexType::Map {
+                inner_type: *inner_type,
+            }),
+        }))
     }
 }
 
@@ -402,64 +660,95 @@ fn compile_expression(
             first,
             operator,
             second,
+            ..
         } => {
+            let (first_span, second_span) = (first.span(), second.span());
             let first = compile_expression(*first, &definitions)?;
             let second = compile_expression(*second, &definitions)?;
 
             let block = match operator {
-                BinaryOperator::Or => blocks::or(first.to_bool_arg()?, second.to_bool_arg()?),
-                BinaryOperator::And => blocks::and(first.to_bool_arg()?, second.to_bool_arg()?),
-                BinaryOperator::LT => blocks::lt(first.to_num_arg()?, second.to_num_arg()?),
-                BinaryOperator::LTE => blocks::lte(first.to_num_arg()?, second.to_num_arg()?),
-                BinaryOperator::GT => blocks::gt(first.to_num_arg()?, second.to_num_arg()?),
-                BinaryOperator::GTE => blocks::gte(first.to_num_arg()?, second.to_num_arg()?),
-                BinaryOperator::EQ => blocks::eq(first.to_num_arg()?, second.to_num_arg()?),
-                BinaryOperator::Plus => blocks::plus(first.to_num_arg()?, second.to_num_arg()?),
-                BinaryOperator::Minus => blocks::minus(first.to_num_arg()?, second.to_num_arg()?),
-                BinaryOperator::Multiply => {
-                    blocks::multiply(first.to_num_arg()?, second.to_num_arg()?)
+                BinaryOperator::Or | BinaryOperator::And => {
+                    let first = first.to_bool_arg(&first_span)?;
+                    let second = second.to_bool_arg(&second_span)?;
+
+                    match operator {
+                        BinaryOperator::Or => blocks::or(first, second),
+                        _ => blocks::and(first, second),
+                    }
+                }
+
+                operator => {
+                    let first = first.to_num_arg(&first_span)?;
+                    let second = second.to_num_arg(&second_span)?;
+
+                    match operator {
+                        BinaryOperator::LT => blocks::lt(first, second),
+                        BinaryOperator::LTE => blocks::lte(first, second),
+                        BinaryOperator::GT => blocks::gt(first, second),
+                        BinaryOperator::GTE => blocks::gte(first, second),
+                        BinaryOperator::EQ => blocks::eq(first, second),
+                        BinaryOperator::Plus => blocks::plus(first, second),
+                        BinaryOperator::Minus => blocks::minus(first, second),
+                        BinaryOperator::Multiply => blocks::multiply(first, second),
+                        BinaryOperator::Divide => blocks::divide(first, second),
+                        BinaryOperator::Power => blocks::power(first, second),
+                        BinaryOperator::Or | BinaryOperator::And => unreachable!(),
+                    }
                 }
-                BinaryOperator::Divide => blocks::divide(first.to_num_arg()?, second.to_num_arg()?),
-                BinaryOperator::Power => blocks::power(first.to_num_arg()?, second.to_num_arg()?),
             };
 
             ExprValue::ArgBlock(block)
         }
 
-        Expression::UnaryOp { value, operator } => {
+        Expression::UnaryOp {
+            value, operator, ..
+        } => {
+            let value_span = value.span();
             let value = compile_expression(*value, &definitions)?;
 
+            // negative numbers are parsed as an unary minus, they should be a value instead of
+            // being `value * -1`
+            if let (UnaryOperator::Minus, ExprValue::Literal(Literal::Number(num))) =
+                (&operator, &value)
+            {
+                return Ok(ExprValue::Literal(Literal::Number(-num)));
+            }
+
             ExprValue::ArgBlock(match operator {
-                UnaryOperator::Not => blocks::not(value.to_bool_arg()?),
-                UnaryOperator::Minus => blocks::minus_unary(value.to_num_arg()?),
-                UnaryOperator::Plus => blocks::plus_unary(value.to_num_arg()?),
+                UnaryOperator::Not => blocks::not(value.to_bool_arg(&value_span)?),
+                UnaryOperator::Minus => blocks::minus_unary(value.to_num_arg(&value_span)?),
+                UnaryOperator::Plus => blocks::plus_unary(value.to_num_arg(&value_span)?),
             })
         }
 
         Expression::PrimaryExpression(prim) => {
             match prim {
-                PrimaryExpression::Index { from, index } => {
+                PrimaryExpression::Index { from, index, span } => {
+                    let (from_span, index_span) = (from.span(), index.span());
                     let from = compile_expression(*from, &definitions)?;
                     let index_val = compile_expression(*index, &definitions)?;
 
                     // get the types
-                    let typ =
-                        from.get_type()
-                            .ok_or_else(|| LogicCompileError::RegularBlockAsAnyArg {
-                                block: from.clone().expect_block().unwrap(),
-                            })?;
+                    let typ = from.get_type(&from_span)?.ok_or_else(|| {
+                        LogicCompileError::RegularBlockAsAnyArg {
+                            block: from.clone().expect_block(&from_span).unwrap(),
+                            span: from_span.clone(),
+                        }
+                    })?;
 
-                    let index_val_type =
-                        from.get_type()
-                            .ok_or_else(|| LogicCompileError::RegularBlockAsAnyArg {
-                                block: index_val.clone().expect_block().unwrap(),
-                            })?;
+                    let index_val_type = index_val.get_type(&index_span)?.ok_or_else(|| {
+                        LogicCompileError::RegularBlockAsAnyArg {
+                            block: index_val.clone().expect_block(&index_span).unwrap(),
+                            span: index_span.clone(),
+                        }
+                    })?;
 
                     // get the type data of this type that's getting indexed
                     let type_data = Definitions::get_type_data(typ).ok_or_else(|| {
                         LogicCompileError::CannotBeIndexed {
                             typ,
                             index_type: index_val_type,
+                            span: span.clone(),
                         }
                     })?;
 
@@ -468,22 +757,25 @@ fn compile_expression(
                         LogicCompileError::CannotBeIndexed {
                             typ,
                             index_type: index_val_type,
+                            span: span.clone(),
                         }
                     })?;
 
                     // generate using it i guess
                     ExprValue::from_block(index_gen([
-                        from.to_type_value()?,
-                        index_val.to_type_value()?,
+                        from.to_type_value(&from_span)?,
+                        index_val.to_type_value(&index_span)?,
                     ]))
                 }
 
-                PrimaryExpression::VariableAccess { from, name } => {
+                PrimaryExpression::VariableAccess { from, name, span } => {
                     if let Some(from) = from {
+                        let from_span = from.span();
                         let val = compile_expression(*from, &definitions)?;
-                        let typ = val.get_type().ok_or_else(|| {
+                        let typ = val.get_type(&from_span)?.ok_or_else(|| {
                             LogicCompileError::RegularBlockAsAnyArg {
-                                block: val.clone().expect_block().unwrap(),
+                                block: val.clone().expect_block(&from_span).unwrap(),
+                                span: from_span.clone(),
                             }
                         })?;
 
@@ -492,6 +784,7 @@ fn compile_expression(
                             LogicCompileError::MemberDoesntExist {
                                 name: name.clone(),
                                 typ,
+                                span: span.clone(),
                             }
                         })?;
 
@@ -499,28 +792,54 @@ fn compile_expression(
                             LogicCompileError::MemberDoesntExist {
                                 name: name.clone(),
                                 typ,
+                                span: span.clone(),
                             }
                         })?;
 
                         let block = if matches!(member, Member::Field { .. }) {
                             // generate it!
-                            member.field_gen(val.to_type_value()?)?
+                            member
+                                .field_gen(val.to_type_value(&from_span)?)
+                                .map_err(|error| LogicCompileError::GenerateError {
+                                    error,
+                                    span: span.clone(),
+                                })?
                         } else {
                             return Err(LogicCompileError::MethodMustBeCalled {
                                 method_name: name,
                                 typ,
+                                span,
                             });
                         };
 
                         ExprValue::from_block(block)
+                    } else if let Some(typ) = definitions.get_parameter(&name) {
+                        match typ {
+                            Type::Complex(ComplexType::Map { inner_type }) => ExprValue::Map {
+                                inner_type,
+                                var_name: ArgValue::Block(blocks::get_map_arg(name)),
+                            },
+                            typ => {
+                                ExprValue::ArgBlock(blocks::get_arg(name, typ.to_arg_block_type()))
+                            }
+                        }
                     } else {
                         let var = definitions.get_var(&name).ok_or_else(|| {
                             LogicCompileError::VariableDoesntExist {
                                 identifier: name.clone(),
+                                span,
                             }
                         })?;
 
-                        ExprValue::ArgBlock(blocks::get_var(name, var.to_arg_block_type()))
+                        match var {
+                            Type::Complex(ComplexType::Map { inner_type }) => ExprValue::Map {
+                                inner_type,
+                                var_name: ArgValue::Value(name),
+                            },
+                            var => {
+                                ExprValue::ArgBlock(blocks::get_var(name, var.to_arg_block_type()))
+                            }
+                        }
                     }
                 }
 
@@ -528,14 +847,17 @@ fn compile_expression(
                     from,
                     name,
                     arguments,
+                    span,
                 } => {
                     // compile arguments expressions
                     let args = arguments
                         .0
                         .into_iter()
                         .map(|expr| {
+                            let span = expr.span();
+
                             compile_expression(expr, &definitions)
-                                .map(|val| val.to_type_value())
+                                .map(|val| val.to_type_value(&span))
                                 // .flatten() but on steroids
                                 .and_then(std::convert::identity)
                         })
@@ -544,10 +866,12 @@ fn compile_expression(
                     if let Some(from) = from {
                         // calling a method
                         // resolve this expression and get its type
+                        let from_span = from.span();
                         let from = compile_expression(*from, &definitions)?;
-                        let typ = from.get_type().ok_or_else(|| {
+                        let typ = from.get_type(&from_span)?.ok_or_else(|| {
                             LogicCompileError::RegularBlockAsAnyArg {
-                                block: from.clone().expect_block().unwrap(),
+                                block: from.clone().expect_block(&from_span).unwrap(),
+                                span: from_span.clone(),
                             }
                         })?;
 
@@ -556,6 +880,7 @@ fn compile_expression(
                             LogicCompileError::MemberDoesntExist {
                                 name: name.clone(),
                                 typ,
+                                span: span.clone(),
                             }
                         })?;
 
@@ -563,33 +888,307 @@ fn compile_expression(
                             LogicCompileError::MemberDoesntExist {
                                 name: name.clone(),
                                 typ,
+                                span: span.clone(),
                             }
                         })?;
 
                         let block = if matches!(member, Member::Method { .. }) {
                             // generate it!
-                            member.method_gen(from.to_type_value()?, args)?
+                            member
+                                .method_gen(from.to_type_value(&from_span)?, args)
+                                .map_err(|error| LogicCompileError::GenerateError {
+                                    error,
+                                    span: span.clone(),
+                                })?
                         } else {
                             return Err(LogicCompileError::FieldCannotBeCalled {
                                 field_name: name,
                                 typ,
+                                span,
                             });
                         };
 
                         ExprValue::from_block(block)
+                    } else if let Some(global_func) = Definitions::get_global_func(&name) {
+                        ExprValue::from_block(
+                            global_func.generate(args).map_err(|error| {
+                                LogicCompileError::GenerateError { error, span }
+                            })?,
+                        )
                     } else {
-                        // global function
-                        let global_func = Definitions::get_global_func(&name).ok_or_else(|| {
-                            LogicCompileError::GlobalFunctionDoesntExist { name: name.clone() }
+                        // calling a moreblock
+                        let more_block = definitions.get_more_block(&name).ok_or_else(|| {
+                            LogicCompileError::GlobalFunctionDoesntExist {
+                                name: name.clone(),
+                                span: span.clone(),
+                            }
                         })?;
 
-                        ExprValue::from_block(global_func.generate(args)?)
+                        ExprValue::from_block(
+                            more_block.generate(args).map_err(|error| {
+                                LogicCompileError::GenerateError { error, span }
+                            })?,
+                        )
                     }
                 }
+
+                PrimaryExpression::RawBlock(raw_block) => {
+                    // an expression can only have one error, the first error in its substacks
+                    // is used
+                    let mut errors = Vec::new();
+                    let block = compile_raw_block(raw_block, definitions, &mut errors)?;
+
+                    if let Some(err) = errors.into_iter().next() {
+                        return Err(err);
+                    }
+
+                    ExprValue::from_block(block)
+                }
             }
         }
 
-        Expression::Literal(literal) => ExprValue::Literal(literal),
+        Expression::Literal { value, .. } => ExprValue::Literal(value),
+    })
+}
+
+// lists, views and components are passed around by their names, a variable getter is turned into
+// the name of the variable it's getting, other blocks (like a moreblock parameter) are kept as-is
+fn reference_arg(block: Block) -> ArgValue<String> {
+    if block.op_code == "getVar" {
+        ArgValue::Value(block.content.to_string())
+    } else {
+        ArgValue::Block(block)
+    }
+}
+
+// gets the type of an argument block, its block type could be one that doesn't have a type (like a
+// view or component type that doesn't exist), in which case an error is returned
+fn arg_block_type(block: &Block, span: &Span) -> Result<Type, LogicCompileError> {
+    match &block.block_type {
+        BlockType::Argument(arg) => Type::from_arg_block(arg),
+        _ => None,
+    }
+    .ok_or_else(|| LogicCompileError::InvalidArgBlockType {
+        block_type: block.block_type.clone(),
+        span: span.clone(),
+    })
+}
+
+// compiles a raw block as-is, its arguments are matched with the arguments in its spec
+fn compile_raw_block(
+    raw_block: RawBlock,
+    definitions: &Definitions,
+    errors: &mut Vec<LogicCompileError>,
+) -> Result<Block, LogicCompileError> {
+    let block_type = parse_raw_block_type(&raw_block.block_type).ok_or_else(|| {
+        LogicCompileError::InvalidRawBlockType {
+            block_type: raw_block.block_type.clone(),
+            span: raw_block.span.clone(),
+        }
+    })?;
+
+    let color = u32::from_str_radix(raw_block.color.trim_start_matches('#'), 16)
+        .map(Color::from)
+        .map_err(|_| LogicCompileError::InvalidRawBlockColor {
+            color: raw_block.color.clone(),
+            span: raw_block.span.clone(),
+        })?;
+
+    let expected = raw_block
+        .spec
+        .split_whitespace()
+        .filter(|word| parse_spec_arg(word).is_some())
+        .count();
+
+    if expected != raw_block.arguments.0.len() {
+        return Err(LogicCompileError::RawBlockArgumentsMismatch {
+            op_code: raw_block.op_code,
+            expected,
+            got: raw_block.arguments.0.len(),
+            span: raw_block.span,
+        });
+    }
+
+    let mut arguments = raw_block.arguments.0.into_iter();
+    let mut content = BlockContent::builder();
+
+    for word in raw_block.spec.split_whitespace() {
+        let spec_arg = match parse_spec_arg(word) {
+            Some(spec_arg) => spec_arg,
+            None => {
+                content = content.text(word);
+                continue;
+            }
+        };
+
+        // unwrap: the amount of arguments has been checked above
+        let argument = arguments.next().unwrap();
+        let span = argument.span();
+        let value = compile_expression(argument, definitions)?;
+
+        content = content.arg(match spec_arg {
+            SpecArg::String(name) => Argument::String {
+                name,
+                value: raw_arg(
+                    value,
+                    PrimitiveType::String,
+                    &span,
+                    |literal| match literal {
+                        Literal::String(str) => Some(str),
+                        _ => None,
+                    },
+                )?,
+            },
+            SpecArg::Number(name) => Argument::Number {
+                name,
+                value: raw_arg(
+                    value,
+                    PrimitiveType::Number,
+                    &span,
+                    |literal| match literal {
+                        Literal::Number(num) => Some(num),
+                        _ => None,
+                    },
+                )?,
+            },
+            SpecArg::Boolean(name) => Argument::Boolean {
+                name,
+                value: raw_arg(
+                    value,
+                    PrimitiveType::Boolean,
+                    &span,
+                    |literal| match literal {
+                        Literal::Boolean(bool) => Some(bool),
+                        _ => None,
+                    },
+                )?,
+            },
+            SpecArg::Menu(name) => Argument::Menu {
+                name,
+                value: raw_arg(
+                    value,
+                    PrimitiveType::String,
+                    &span,
+                    |literal| match literal {
+                        Literal::String(str) => Some(str),
+                        _ => None,
+                    },
+                )?,
+            },
+        });
+    }
+
+    // empty substacks are stored as None
+    let mut sub_stacks = raw_block.sub_stacks.into_iter().map(|sub_stack| {
+        let blocks = compile_inner_statements(sub_stack, definitions, errors);
+
+        if blocks.0.is_empty() {
+            None
+        } else {
+            Some(blocks)
+        }
+    });
+
+    Ok(Block {
+        sub_stack1: sub_stacks.next().flatten(),
+        sub_stack2: sub_stacks.next().flatten(),
+        color,
+        op_code: raw_block.op_code,
+        content: content.build(),
+        block_type,
+    })
+}
+
+// an argument inside a block spec, e.g. `%s` or `%m.varInt`
+enum SpecArg {
+    String(Option<String>),
+    Number(Option<String>),
+    Boolean(Option<String>),
+    Menu(String),
+}
+
+fn parse_spec_arg(word: &str) -> Option<SpecArg> {
+    let (kind, name) = match word.split_once('.') {
+        Some((kind, name)) => (kind, Some(name.to_string())),
+        None => (word, None),
+    };
+
+    Some(match (kind, name) {
+        ("%s", name) => SpecArg::String(name),
+        ("%d", name) => SpecArg::Number(name),
+        ("%b", name) => SpecArg::Boolean(name),
+        ("%m", Some(name)) => SpecArg::Menu(name),
+        _ => return None,
+    })
+}
+
+// converts an expression value into an argument of a raw block. argument blocks of any type are
+// accepted since we can't know what type the raw block is expecting
+fn raw_arg<T>(
+    value: ExprValue,
+    expected: PrimitiveType,
+    span: &Span,
+    from_literal: impl FnOnce(Literal) -> Option<T>,
+) -> Result<ArgValue<T>, LogicCompileError> {
+    match value {
+        ExprValue::Block(block) => Err(LogicCompileError::RegularBlockAsArg {
+            block,
+            expected_arg_type: Type::Primitive(expected),
+            span: span.clone(),
+        }),
+        ExprValue::ArgBlock(block) => Ok(ArgValue::Block(block)),
+        ExprValue::Map { inner_type, .. } => Err(LogicCompileError::TypeError {
+            expected: Type::Primitive(expected),
+            got: Type::Complex(ComplexType::Map { inner_type }),
+            span: span.clone(),
+        }),
+        ExprValue::Literal(literal) => {
+            // unwrap: literals always have a type
+            let got = ExprValue::Literal(literal.clone()).get_type(span)?.unwrap();
+
+            from_literal(literal)
+                .map(ArgValue::Value)
+                .ok_or_else(|| LogicCompileError::TypeError {
+                    expected: Type::Primitive(expected),
+                    got,
+                    span: span.clone(),
+                })
+        }
+    }
+}
+
+// parses the type of a raw block, e.g. `regular`, `number`, `one_nest` or `view<TextView>`
+fn parse_raw_block_type(block_type: &str) -> Option<BlockType> {
+    Some(match block_type {
+        "regular" => BlockType::Regular,
+        "one_nest" => BlockType::Control(BlockControl::OneNest),
+        "two_nest" => BlockType::Control(BlockControl::TwoNest),
+        "ending" => BlockType::Control(BlockControl::EndingBlock),
+        "boolean" => BlockType::Argument(ArgumentBlockReturnType::Boolean),
+        "number" => BlockType::Argument(ArgumentBlockReturnType::Number),
+        "string" => BlockType::Argument(ArgumentBlockReturnType::String),
+        "list<number>" => BlockType::Argument(ArgumentBlockReturnType::List {
+            inner_type: ListItem::Number,
+        }),
+        "list<string>" => BlockType::Argument(ArgumentBlockReturnType::List {
+            inner_type: ListItem::String,
+        }),
+        other => {
+            let (name, type_name) = other.strip_suffix('>')?.split_once('<')?;
+
+            // the type name must be known, it's turned into a type when the block is used
+            BlockType::Argument(match name {
+                "view" if ViewType::from_str(type_name).is_ok() => ArgumentBlockReturnType::View {
+                    type_name: type_name.to_string(),
+                },
+                "component" if ComponentType::from_str(type_name).is_ok() => {
+                    ArgumentBlockReturnType::Component {
+                        type_name: type_name.to_string(),
+                    }
+                }
+                _ => return None,
+            })
+        }
     })
 }
 
@@ -601,19 +1200,120 @@ fn variable_type_to_type(typ: VariableType) -> Type {
     })
 }
 
-fn complex_variable_type_to_type(typ: ComplexVariableType) -> Type {
-    Type::Complex(match typ {
-        ComplexVariableType::Map { .. } => ComplexType::Map,
-        ComplexVariableType::List { inner_type } => ComplexType::List {
-            inner_type: if let Type::Primitive(primitive_type) = variable_type_to_type(inner_type) {
-                primitive_type
-            } else {
-                unreachable!()
-            },
+// turns the listeners of components into component events and checks whether the component has
+// the event, returns the event type along with the parameters of the event
+fn resolve_event_type(
+    event_type: EventType,
+    event_name: &str,
+    definitions: &Definitions,
+    span: Span,
+) -> Result<(EventType, Vec<(String, Type)>), LogicCompileError> {
+    let EventType::ViewEvent { id } = event_type else {
+        return Ok((event_type, vec![]));
+    };
+
+    let Some(Type::Component(component_type)) = definitions.get_var(&id) else {
+        return Ok((EventType::ViewEvent { id }, vec![]));
+    };
+
+    let parameters = component_type.event_parameters(event_name).ok_or_else(|| {
+        LogicCompileError::ComponentEventDoesntExist {
+            component_type: component_type.to_string(),
+            event_name: event_name.to_string(),
+            span,
+        }
+    })?;
+
+    Ok((
+        EventType::ComponentEvent {
+            id,
+            component_type: component_type.swrs_id(),
         },
+        parameters,
+    ))
+}
+
+// resolves the type of a component declaration and checks its arguments
+fn compile_component(
+    component_type: &str,
+    arguments: Vec<ComponentArgument>,
+    span: &Span,
+) -> Result<(ComponentType, ComponentKind), LogicCompileError> {
+    let typ = ComponentType::from_str(component_type).map_err(|_| {
+        LogicCompileError::UnknownComponentType {
+            component_type: component_type.to_string(),
+            span: span.clone(),
+        }
+    })?;
+
+    let expected = typ.arguments();
+
+    if let Some(argument) = arguments
+        .iter()
+        .find(|argument| !expected.contains(&argument.name.as_str()))
+    {
+        return Err(LogicCompileError::UnknownComponentArgument {
+            component_type: component_type.to_string(),
+            argument: argument.name.clone(),
+            span: argument.span.clone(),
+        });
+    }
+
+    // the arguments are ordered the same as the expected arguments
+    let values = expected
+        .iter()
+        .map(|name| {
+            arguments
+                .iter()
+                .find(|argument| argument.name == *name)
+                .map(|argument| argument.value.clone())
+                .ok_or_else(|| LogicCompileError::MissingComponentArgument {
+                    component_type: component_type.to_string(),
+                    argument: name.to_string(),
+                    span: span.clone(),
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((typ, typ.to_swrs_component(values)))
+}
+
+fn parameter_type_to_type(typ: ParameterType, span: &Span) -> Result<Type, LogicCompileError> {
+    Ok(match typ {
+        ParameterType::Simple(typ) => variable_type_to_type(typ),
+        ParameterType::Complex(typ) => complex_variable_type_to_type(typ, span)?,
+        ParameterType::View => Type::View(ViewType::View),
     })
 }
 
+// lists and maps can only store numbers and strings
+fn complex_variable_type_to_type(
+    typ: ComplexVariableType,
+    span: &Span,
+) -> Result<Type, LogicCompileError> {
+    let (inner_type, is_map) = match typ {
+        ComplexVariableType::Map { inner_type } => (inner_type, true),
+        ComplexVariableType::List { inner_type } => (inner_type, false),
+    };
+
+    let inner_type = match variable_type_to_type(inner_type) {
+        Type::Primitive(PrimitiveType::Boolean) => {
+            return Err(LogicCompileError::InvalidInnerType {
+                typ: Type::Primitive(PrimitiveType::Boolean),
+                span: span.clone(),
+            })
+        }
+        Type::Primitive(primitive_type) => primitive_type,
+        _ => unreachable!(),
+    };
+
+    Ok(Type::Complex(if is_map {
+        ComplexType::Map { inner_type }
+    } else {
+        ComplexType::List { inner_type }
+    }))
+}
+
 #[derive(Debug)]
 pub struct LogicCompileResult {
     pub variables: LinkedHashMap<String, Variable>,
@@ -630,6 +1330,7 @@ pub enum LogicCompileError {
         // todo: change to a simpler type lol
         expected: Type,
         got: Type,
+        span: Span,
     },
 
     #[error(
@@ -640,50 +1341,156 @@ pub enum LogicCompileError {
         var_name: String,
         var_type: Type,
         existing_var_type: Type,
+        span: Span,
     },
 
     #[error("variable {identifier} doesn't exist")]
-    VariableDoesntExist { identifier: String },
+    VariableDoesntExist { identifier: String, span: Span },
 
     #[error("variable {identifier} with type {variable_type:?} can't be assigned to a value")]
     UnAssignableVariable {
         identifier: String,
         variable_type: Type,
+        span: Span,
     },
 
     #[error("the function {name} doesn't exist in the global scope")]
-    GlobalFunctionDoesntExist { name: String },
+    GlobalFunctionDoesntExist { name: String, span: Span },
+
+    #[error("unknown component type `{component_type}`")]
+    UnknownComponentType { component_type: String, span: Span },
+
+    #[error("component `{component_type}` doesn't have an argument named `{argument}`")]
+    UnknownComponentArgument {
+        component_type: String,
+        argument: String,
+        span: Span,
+    },
+
+    #[error("component `{component_type}` requires the argument `{argument}`")]
+    MissingComponentArgument {
+        component_type: String,
+        argument: String,
+        span: Span,
+    },
+
+    #[error("component `{component_type}` doesn't have an event named `{event_name}`")]
+    ComponentEventDoesntExist {
+        component_type: String,
+        event_name: String,
+        span: Span,
+    },
+
+    #[error("a moreblock or a global function named {name} already exists")]
+    MoreBlockAlreadyExists { name: String, span: Span },
 
     #[error("the member named {name} doesn't exist in the type {typ:?}")]
-    MemberDoesntExist { name: String, typ: Type },
+    MemberDoesntExist { name: String, typ: Type, span: Span },
 
     #[error("type {typ:?} cannot be indexed with {index_type:?}")]
-    CannotBeIndexed { typ: Type, index_type: Type },
+    CannotBeIndexed {
+        typ: Type,
+        index_type: Type,
+        span: Span,
+    },
+
+    #[error("an index of type {typ:?} cannot be assigned to a value")]
+    IndexCannotBeAssigned { typ: Type, span: Span },
 
     #[error("field {field_name} of variable type {typ:?} cannot be called as a function")]
-    FieldCannotBeCalled { field_name: String, typ: Type },
+    FieldCannotBeCalled {
+        field_name: String,
+        typ: Type,
+        span: Span,
+    },
 
     #[error(
         "method {method_name} of variable type {typ:?} must be called and cannot be accessed as\
     a field"
     )]
-    MethodMustBeCalled { method_name: String, typ: Type },
+    MethodMustBeCalled {
+        method_name: String,
+        typ: Type,
+        span: Span,
+    },
 
     #[error("a void-returning expression can't be used as a argument. expected an arg block with type {expected_arg_type:?}")]
     RegularBlockAsArg {
         block: Block,
         expected_arg_type: Type,
+        span: Span,
     },
 
     #[error("a void-returning expression can't be used as a argument.")]
-    RegularBlockAsAnyArg { block: Block },
+    RegularBlockAsAnyArg { block: Block, span: Span },
 
     #[error("dangling argument block as a statement")]
-    DanglingArgBlock { block: Block },
+    DanglingArgBlock { block: Block, span: Span },
 
     #[error("dangling literal as a statement")]
-    DanglingLiteral { literal: Literal },
+    DanglingLiteral { literal: Literal, span: Span },
+
+    #[error("dangling map as a statement")]
+    DanglingMap { span: Span },
+
+    #[error("lists and maps can only store numbers and strings, got {typ:?}")]
+    InvalidInnerType { typ: Type, span: Span },
+
+    #[error("argument block has an invalid type {block_type:?}")]
+    InvalidArgBlockType { block_type: BlockType, span: Span },
+
+    #[error("invalid raw block type `{block_type}`")]
+    InvalidRawBlockType { block_type: String, span: Span },
+
+    #[error("invalid raw block color `{color}`")]
+    InvalidRawBlockColor { color: String, span: Span },
+
+    #[error("raw block with opcode `{op_code}` expects {expected} arguments, got {got}")]
+    RawBlockArgumentsMismatch {
+        op_code: String,
+        expected: usize,
+        got: usize,
+        span: Span,
+    },
+
+    #[error("generate error: {error}")]
+    GenerateError {
+        #[source]
+        error: GenerateError,
+        span: Span,
+    },
+}
 
-    #[error("generate error: {0}")]
-    GenerateError(#[from] GenerateError),
+impl LogicCompileError {
+    /// Retrieves the span on the source code where this error came from
+    pub fn span(&self) -> &Span {
+        match self {
+            LogicCompileError::TypeError { span, .. }
+            | LogicCompileError::VariableAlreadyExists { span, .. }
+            | LogicCompileError::VariableDoesntExist { span, .. }
+            | LogicCompileError::UnAssignableVariable { span, .. }
+            | LogicCompileError::GlobalFunctionDoesntExist { span, .. }
+            | LogicCompileError::MoreBlockAlreadyExists { span, .. }
+            | LogicCompileError::UnknownComponentType { span, .. }
+            | LogicCompileError::UnknownComponentArgument { span, .. }
+            | LogicCompileError::MissingComponentArgument { span, .. }
+            | LogicCompileError::ComponentEventDoesntExist { span, .. }
+            | LogicCompileError::MemberDoesntExist { span, .. }
+            | LogicCompileError::CannotBeIndexed { span, .. }
+            | LogicCompileError::IndexCannotBeAssigned { span, .. }
+            | LogicCompileError::FieldCannotBeCalled { span, .. }
+            | LogicCompileError::MethodMustBeCalled { span, .. }
+            | LogicCompileError::RegularBlockAsArg { span, .. }
+            | LogicCompileError::RegularBlockAsAnyArg { span, .. }
+            | LogicCompileError::DanglingArgBlock { span, .. }
+            | LogicCompileError::DanglingLiteral { span, .. }
+            | LogicCompileError::DanglingMap { span }
+            | LogicCompileError::InvalidInnerType { span, .. }
+            | LogicCompileError::InvalidArgBlockType { span, .. }
+            | LogicCompileError::InvalidRawBlockType { span, .. }
+            | LogicCompileError::InvalidRawBlockColor { span, .. }
+            | LogicCompileError::RawBlockArgumentsMismatch { span, .. }
+            | LogicCompileError::GenerateError { span, .. } => span,
+        }
+    }
 }
@@ -4,10 +4,41 @@ use buffered_lexer::error::ParseError;
 use buffered_lexer::{BufferedLexer, SpannedTokenOwned};
 use logos::Logos;
 
+/// Parses a logic code, returns the first syntax error found. Use [`parse_logic_recovering`] to
+/// get every syntax errors of the code.
 pub fn parse_logic(raw: &str) -> LogicParseResult<OuterStatements> {
-    let mut lex: BufferedLexer<'_, Token> = BufferedLexer::new(Token::lexer(raw), Token::Error);
+    parse_logic_with(&mut lexer(raw))
+}
+
+/// Parses a logic code out of a lexer created with [`lexer`], returns the first syntax error
+/// found. The lexer can then be used to retrieve the comments around the tokens.
+pub fn parse_logic_with(lex: &mut BufferedLexer<'_, Token>) -> LogicParseResult<OuterStatements> {
+    let statements = outer_statements(lex);
+    let mut errors = lex.take_errors();
 
-    outer_statements(&mut lex)
+    if errors.is_empty() {
+        Ok(statements)
+    } else {
+        Err(errors.remove(0))
+    }
+}
+
+/// Parses a logic code while recovering from syntax errors. A statement that fails to parse is
+/// skipped until the next newline or closing brace and parsing goes on from there.
+///
+/// Returns the statements that were successfully parsed, along with every syntax errors found.
+pub fn parse_logic_recovering(raw: &str) -> (OuterStatements, Vec<LogicParseError>) {
+    let mut lex = lexer(raw);
+    let statements = outer_statements(&mut lex);
+
+    (statements, lex.take_errors())
+}
+
+/// Creates the lexer used to parse a logic code. Comments are trivia: the parser never sees
+/// them, but they're kept along with the tokens next to them (see
+/// [`BufferedLexer::lossless_tokens`]).
+pub fn lexer(raw: &str) -> BufferedLexer<'_, Token> {
+    BufferedLexer::new_lossless(Token::lexer(raw), Token::Error, vec![Token::Comment])
 }
 
 #[derive(Logos, PartialEq, Debug, Clone)]
@@ -45,6 +76,8 @@ pub enum Token {
 
     #[token(".")]
     DOT,
+    #[token(":")]
+    Colon,
 
     // boolean operators
     #[token("!")]
@@ -95,13 +128,17 @@ pub enum Token {
     #[token("continue")]
     Continue,
 
+    // raw blocks
+    #[token("#block")]
+    RawBlock,
+
     // literals
     #[token("true")]
     True,
     #[token("false")]
     False,
 
-    #[regex(r#""([^"]|\\")*""#)]
+    #[regex(r#""([^"\\]|\\.)*""#)]
     String,
     #[regex("[0-9]+(?:\\.[0-9]+)?")]
     Number,
@@ -109,9 +146,12 @@ pub enum Token {
     #[regex(r"[a-zA-Z_][a-zA-Z0-9_]*")]
     Identifier,
 
+    // a trivia, see `lexer`
+    #[regex(r"//[^\n]*")]
+    Comment,
+
     #[error]
     #[regex(r"[ \t]+", logos::skip)] // whitespace
-    #[regex(r"//[^\n]*\n?", logos::skip)] // comment
     Error,
 }
 
@@ -127,35 +167,65 @@ type Lexer<'a> = BufferedLexer<'a, Token>;
 // please tell me what is your approach to doing this, I feel like there's a waaay better way
 // of doing this.
 
-fn outer_statements(lex: &mut Lexer) -> LogicParseResult<OuterStatements> {
+// the pairs of brackets that are skipped over when synchronizing
+const NESTING: &[(Token, Token)] = &[
+    (Token::LParen, Token::RParen),
+    (Token::LBracket, Token::RBracket),
+    (Token::LBrace, Token::RBrace),
+];
+
+// syntax errors are recorded on the lexer and the statements are skipped until the next line
+fn outer_statements(lex: &mut Lexer) -> OuterStatements {
     lex.start();
     let mut statements = OuterStatements(vec![]);
 
     loop {
-        // break if we've reached EOF, but propagate lexer errors
-        if let Err(err) = lex.peek() {
-            match err {
+        // skip any newlines, break if we've reached EOF, but record lexer errors
+        match lex.peek() {
+            Ok(SpannedTokenOwned {
+                token: Token::Newline,
+                ..
+            }) => {
+                lex.next().unwrap();
+                continue;
+            }
+            Ok(_) => (),
+            Err(err) => match err {
                 ParseError::EOF { .. } => break,
-                ParseError::LexerError { .. } => return Err(err),
+                ParseError::LexerError { .. } => {
+                    // the invalid token is already skipped by the lexer
+                    lex.report(err);
+                    continue;
+                }
                 _ => unreachable!(),
-            }
+            },
         }
 
-        // skip any newlines
-        while let Some(_) = lex.expect_failsafe_wo_eof(Token::Newline)? {}
+        let depth = lex.depth();
+
+        match outer_statement(lex) {
+            Ok(statement) => statements.0.push(statement),
+            Err(err) => {
+                if let Err(err) = lex.recover(depth, err) {
+                    lex.report(err);
+                    break;
+                }
 
-        statements.0.push(outer_statement(lex)?);
+                // stray closing braces are skipped along with the rest of the line
+                lex.skip_until(&[Token::Newline], NESTING);
+            }
+        }
     }
 
     lex.success();
-    Ok(statements)
+    statements
 }
 
 fn outer_statement(lex: &mut Lexer) -> LogicParseResult<OuterStatement> {
     lex.start();
 
     let res = match lex.expect_peek_multiple_choices(
-        // expects a type, complex type, or an event identifier
+        // expects a type, complex type, a moreblock, or an event identifier or a component type
         &[
             Token::NumberType,
             Token::StringType,
@@ -165,6 +235,10 @@ fn outer_statement(lex: &mut Lexer) -> LogicParseResult<OuterStatement> {
             Token::Identifier,
         ],
     )? {
+        SpannedTokenOwned {
+            token: Token::Identifier,
+            ..
+        } if is_more_block_declaration(lex) => more_block_declaration(lex),
         SpannedTokenOwned {
             token: Token::Identifier,
             ..
@@ -214,16 +288,19 @@ fn simple_variable_type(lex: &mut Lexer) -> LogicParseResult<VariableType> {
 fn outer_variable_declaration(lex: &mut Lexer) -> LogicParseResult<OuterStatement> {
     lex.start();
 
+    let start = lex.peek()?.pos.start;
+
     // get the type
     let variable_type = simple_variable_type(lex)?;
 
     // next is the identifier
-    let identifier = lex.expect(Token::Identifier)?.slice;
+    let identifier = lex.expect(Token::Identifier)?;
 
     lex.success();
     Ok(OuterStatement::SimpleVariableDeclaration {
         variable_type,
-        identifier,
+        identifier: identifier.slice,
+        span: start..identifier.pos.end,
     })
 }
 
@@ -236,7 +313,8 @@ fn outer_complex_variable_declaration(lex: &mut Lexer) -> LogicParseResult<Outer
     }
 
     // get the type
-    let cx_var_tok_type = match lex.expect_multiple_choices(&[Token::MapType, Token::ListType])? {
+    let type_tok = lex.expect_multiple_choices(&[Token::MapType, Token::ListType])?;
+    let cx_var_tok_type = match type_tok {
         SpannedTokenOwned {
             token: Token::MapType,
             ..
@@ -257,7 +335,7 @@ fn outer_complex_variable_declaration(lex: &mut Lexer) -> LogicParseResult<Outer
     lex.expect(Token::GT)?;
 
     // next is the identifier
-    let identifier = lex.expect(Token::Identifier)?.slice;
+    let identifier = lex.expect(Token::Identifier)?;
 
     lex.success();
     Ok(OuterStatement::ComplexVariableDeclaration {
@@ -265,7 +343,8 @@ fn outer_complex_variable_declaration(lex: &mut Lexer) -> LogicParseResult<Outer
             ComplexVariableTokenType::Map => ComplexVariableType::Map { inner_type },
             ComplexVariableTokenType::List => ComplexVariableType::List { inner_type },
         },
-        identifier,
+        identifier: identifier.slice,
+        span: type_tok.pos.start..identifier.pos.end,
     })
 }
 
@@ -273,7 +352,7 @@ fn outer_event_definition(lex: &mut Lexer) -> LogicParseResult<OuterStatement> {
     // this is where the fun begins
     lex.start();
 
-    let name = lex.expect(Token::Identifier)?.slice;
+    let name = lex.expect(Token::Identifier)?;
 
     // check if there is a dot after an identifier (means that it's a view listener)
     if let Some(_) = lex.expect_failsafe_wo_eof(Token::DOT)? {
@@ -285,13 +364,25 @@ fn outer_event_definition(lex: &mut Lexer) -> LogicParseResult<OuterStatement> {
 
         let statements = inner_statements(lex)?;
 
-        lex.expect(Token::RBrace)?;
+        let r_brace = lex.expect(Token::RBrace)?;
 
         lex.success();
         Ok(OuterStatement::ViewEventListener {
-            view_id: name,
+            view_id: name.slice,
             event_name,
             body: statements,
+            span: name.pos.start..r_brace.pos.end,
+        })
+    } else if let Some(identifier) = lex.expect_failsafe_wo_eof(Token::Identifier)? {
+        // two identifiers, it's a component declaration
+        let (arguments, end) = component_arguments(lex)?;
+
+        lex.success();
+        Ok(OuterStatement::ComponentDeclaration {
+            component_type: name.slice,
+            identifier: identifier.slice,
+            arguments,
+            span: name.pos.start..end.unwrap_or(identifier.pos.end),
         })
     } else {
         // parse the body of this event
@@ -300,31 +391,200 @@ fn outer_event_definition(lex: &mut Lexer) -> LogicParseResult<OuterStatement> {
 
         let statements = inner_statements(lex)?;
 
-        lex.expect(Token::RBrace)?;
+        let r_brace = lex.expect(Token::RBrace)?;
 
         lex.success();
         Ok(OuterStatement::ActivityEventListener {
-            event_name: name,
+            event_name: name.slice,
             body: statements,
+            span: name.pos.start..r_brace.pos.end,
         })
     }
 }
 
-fn inner_statements(lex: &mut Lexer) -> LogicParseResult<InnerStatements> {
+// parses the optional `(name: "value", ...)` of a component declaration, returns the end of the
+// closing parenthesis if there is one
+fn component_arguments(
+    lex: &mut Lexer,
+) -> LogicParseResult<(Vec<ComponentArgument>, Option<usize>)> {
     lex.start();
-    let mut statements = InnerStatements(vec![]);
+
+    if lex.expect_failsafe_wo_eof(Token::LParen)?.is_none() {
+        lex.success();
+        return Ok((vec![], None));
+    }
+
+    let mut arguments = vec![];
+
+    if let Some(r_paren) = lex.expect_failsafe_wo_eof(Token::RParen)? {
+        lex.success();
+        return Ok((arguments, Some(r_paren.pos.end)));
+    }
 
     loop {
-        // skip any newlines
-        while let Some(_) = lex.expect_failsafe_wo_eof(Token::Newline)? {}
+        let name = lex.expect(Token::Identifier)?;
+        lex.expect(Token::Colon)?;
+        let value = lex.expect(Token::String)?;
+
+        arguments.push(ComponentArgument {
+            name: name.slice,
+            value: string_value(&value.slice),
+            span: name.pos.start..value.pos.end,
+        });
 
-        // check if we've reached the end (a closing brace)
-        let res = buffered_lexer::propagate_non_recoverable!(lex.expect_peek(Token::RBrace));
-        if res.is_ok() {
+        if lex.expect_failsafe_wo_eof(Token::Comma)?.is_none() {
             break;
         }
+    }
+
+    let end = lex.expect(Token::RParen)?.pos.end;
+
+    lex.success();
+    Ok((arguments, Some(end)))
+}
+
+// `moreblock` is only a keyword when it's followed by the name of the moreblock, otherwise it's
+// a regular identifier (e.g. a view id)
+fn is_more_block_declaration(lex: &mut Lexer) -> bool {
+    lex.start();
+
+    let is_declaration = matches!(lex.next(), Ok(token) if token.slice == "moreblock")
+        && matches!(lex.next(), Ok(token) if token.token == Token::Identifier);
+
+    lex.restore();
+    is_declaration
+}
+
+fn more_block_declaration(lex: &mut Lexer) -> LogicParseResult<OuterStatement> {
+    lex.start();
+
+    // moreblock name(parameter: type, ...) { inner_statements }
+    // the `moreblock` keyword is an identifier, see `is_more_block_declaration`
+    let start = lex.expect(Token::Identifier)?.pos.start;
+    let name = lex.expect(Token::Identifier)?.slice;
+
+    lex.expect(Token::LParen)?;
+    let mut parameters = vec![];
+
+    if lex.expect_failsafe_wo_eof(Token::RParen)?.is_none() {
+        parameters.push(parameter(lex)?);
+
+        while lex.expect_failsafe_wo_eof(Token::Comma)?.is_some() {
+            parameters.push(parameter(lex)?);
+        }
 
-        statements.0.push(inner_statement(lex)?);
+        lex.expect(Token::RParen)?;
+    }
+
+    lex.expect(Token::LBrace)?;
+    let body = inner_statements(lex)?;
+    let end = lex.expect(Token::RBrace)?.pos.end;
+
+    lex.success();
+    Ok(OuterStatement::MoreBlockDeclaration {
+        name,
+        parameters,
+        body,
+        span: start..end,
+    })
+}
+
+fn parameter(lex: &mut Lexer) -> LogicParseResult<Parameter> {
+    lex.start();
+
+    // name: type
+    let name = lex.expect(Token::Identifier)?;
+    lex.expect(Token::Colon)?;
+
+    const PARAMETER_TYPES: &[Token] = &[
+        Token::NumberType,
+        Token::StringType,
+        Token::BooleanType,
+        Token::MapType,
+        Token::ListType,
+    ];
+
+    // `view` isn't a keyword, it's an identifier that's only a type in here
+    let type_tok =
+        lex.expect_multiple_choices(&[PARAMETER_TYPES, &[Token::Identifier]].concat())?;
+
+    let mut end = type_tok.pos.end;
+
+    let parameter_type = match type_tok.token {
+        Token::NumberType => ParameterType::Simple(VariableType::Number),
+        Token::StringType => ParameterType::Simple(VariableType::String),
+        Token::BooleanType => ParameterType::Simple(VariableType::Boolean),
+        Token::Identifier if type_tok.slice == "view" => ParameterType::View,
+        Token::Identifier => {
+            return Err(ParseError::UnexpectedTokenError {
+                expected: Some(PARAMETER_TYPES.to_vec()),
+                pos: type_tok.pos.clone(),
+                unexpected_token: type_tok,
+            })
+        }
+        Token::MapType | Token::ListType => {
+            lex.expect(Token::LT)?;
+            let inner_type = simple_variable_type(lex)?;
+            end = lex.expect(Token::GT)?.pos.end;
+
+            ParameterType::Complex(if type_tok.token == Token::MapType {
+                ComplexVariableType::Map { inner_type }
+            } else {
+                ComplexVariableType::List { inner_type }
+            })
+        }
+        _ => unreachable!(),
+    };
+
+    lex.success();
+    Ok(Parameter {
+        name: name.slice,
+        parameter_type,
+        span: name.pos.start..end,
+    })
+}
+
+// syntax errors of the statements are recorded on the lexer and the statements are skipped until
+// the next line or the closing brace, only an EOF is returned as an error since the block can't
+// be closed
+fn inner_statements(lex: &mut Lexer) -> LogicParseResult<InnerStatements> {
+    lex.start();
+    let mut statements = InnerStatements(vec![]);
+
+    loop {
+        // skip any newlines, then check if we've reached the end (a closing brace)
+        match lex.peek() {
+            Ok(SpannedTokenOwned {
+                token: Token::Newline,
+                ..
+            }) => {
+                lex.next().unwrap();
+                continue;
+            }
+            Ok(SpannedTokenOwned {
+                token: Token::RBrace,
+                ..
+            }) => break,
+            Ok(_) => (),
+            Err(err @ ParseError::EOF { .. }) => {
+                return Err(err.map_eof_expected(|| vec![Token::RBrace]))
+            }
+            Err(err) => {
+                // the invalid token is already skipped by the lexer
+                lex.report(err);
+                continue;
+            }
+        }
+
+        let depth = lex.depth();
+
+        match inner_statement(lex) {
+            Ok(statement) => statements.0.push(statement),
+            Err(err) => {
+                lex.recover(depth, err)?;
+                lex.skip_until(&[Token::Newline, Token::RBrace], NESTING);
+            }
+        }
     }
 
     lex.success();
@@ -350,28 +610,46 @@ fn inner_statement(lex: &mut Lexer) -> LogicParseResult<InnerStatement> {
         SpannedTokenOwned {
             token: Token::Break,
             ..
-        } => {
-            lex.next().unwrap();
-            InnerStatement::Break
-        }
+        } => InnerStatement::Break {
+            span: lex.next().unwrap().pos.clone(),
+        },
 
         SpannedTokenOwned {
             token: Token::Continue,
             ..
-        } => {
-            lex.next().unwrap();
-            InnerStatement::Continue
-        }
+        } => InnerStatement::Continue {
+            span: lex.next().unwrap().pos.clone(),
+        },
+
+        SpannedTokenOwned {
+            token: Token::RawBlock,
+            ..
+        } => InnerStatement::RawBlock(raw_block_statement(lex)?),
 
         SpannedTokenOwned { .. } => {
             // can either be variable assignment or an expression (that can be a function or
             // something)
             // this is confusing af
+
+            // the statements above are alternatives too, so they're listed as expected if this
+            // turns out to not be an expression either
+            lex.record_expected(&[
+                Token::If,
+                Token::Repeat,
+                Token::Forever,
+                Token::Break,
+                Token::Continue,
+                Token::RawBlock,
+            ]);
+
             let expr = expression(lex)?;
 
             // expression will think that this is just a variable access
-            if let Expression::PrimaryExpression(PrimaryExpression::VariableAccess { from, name }) =
-                expr
+            if let Expression::PrimaryExpression(PrimaryExpression::VariableAccess {
+                from,
+                name,
+                span,
+            }) = expr
             {
                 if let None = from {
                     // regular name = value statement
@@ -380,16 +658,37 @@ fn inner_statement(lex: &mut Lexer) -> LogicParseResult<InnerStatement> {
 
                     InnerStatement::VariableAssignment(VariableAssignment {
                         identifier: name,
+                        span: span.start..value.span().end,
                         value,
                     })
                 } else {
                     // todo: implement from.name = value
                     InnerStatement::Expression(Expression::PrimaryExpression(
-                        PrimaryExpression::VariableAccess { from, name },
+                        PrimaryExpression::VariableAccess { from, name, span },
+                    ))
+                }
+            } else if let Expression::PrimaryExpression(PrimaryExpression::Index {
+                from,
+                index,
+                span,
+            }) = expr
+            {
+                // from[index] = value, or just an index if there isn't an equal sign
+                if lex.expect_failsafe_wo_eof(Token::EQ)?.is_some() {
+                    let value = expression(lex)?;
+
+                    InnerStatement::IndexAssignment(IndexAssignment {
+                        from,
+                        index,
+                        span: span.start..value.span().end,
+                        value,
+                    })
+                } else {
+                    InnerStatement::Expression(Expression::PrimaryExpression(
+                        PrimaryExpression::Index { from, index, span },
                     ))
                 }
             } else {
-                // todo: be able to do stuff like list[index] = value
                 InnerStatement::Expression(expr)
             }
         }
@@ -404,18 +703,18 @@ fn if_statement(lex: &mut Lexer) -> LogicParseResult<IfStatement> {
     lex.start();
 
     // if expr { inner_statements }
-    lex.expect(Token::If)?;
+    let start = lex.expect(Token::If)?.pos.start;
     let condition = expression(lex)?;
 
     lex.expect(Token::LBrace)?;
     let body = inner_statements(lex)?;
-    lex.expect(Token::RBrace)?;
+    let mut end = lex.expect(Token::RBrace)?.pos.end;
 
     // check if there is an else
     let else_body = if let Some(_) = lex.expect_failsafe_wo_eof(Token::Else)? {
         lex.expect(Token::LBrace)?;
         let else_body = inner_statements(lex)?;
-        lex.expect(Token::RBrace)?;
+        end = lex.expect(Token::RBrace)?.pos.end;
 
         Some(else_body)
     } else {
@@ -427,34 +726,121 @@ fn if_statement(lex: &mut Lexer) -> LogicParseResult<IfStatement> {
         condition,
         body,
         else_body,
+        span: start..end,
     })
 }
 
 fn repeat_statement(lex: &mut Lexer) -> LogicParseResult<RepeatStatement> {
     lex.start();
 
-    lex.expect(Token::Repeat)?;
+    let start = lex.expect(Token::Repeat)?.pos.start;
     let condition = expression(lex)?;
 
     lex.expect(Token::LBrace)?;
     let body = inner_statements(lex)?;
-    lex.expect(Token::RBrace)?;
+    let end = lex.expect(Token::RBrace)?.pos.end;
 
     lex.success();
-    Ok(RepeatStatement { condition, body })
+    Ok(RepeatStatement {
+        condition,
+        body,
+        span: start..end,
+    })
 }
 
 fn forever_statement(lex: &mut Lexer) -> LogicParseResult<ForeverStatement> {
     lex.start();
 
-    lex.expect(Token::Forever)?;
+    let start = lex.expect(Token::Forever)?.pos.start;
 
     lex.expect(Token::LBrace)?;
     let body = inner_statements(lex)?;
-    lex.expect(Token::RBrace)?;
+    let end = lex.expect(Token::RBrace)?.pos.end;
 
     lex.success();
-    Ok(ForeverStatement { body })
+    Ok(ForeverStatement {
+        body,
+        span: start..end,
+    })
+}
+
+fn raw_block_statement(lex: &mut Lexer) -> LogicParseResult<RawBlock> {
+    lex.start();
+
+    let mut raw_block = raw_block(lex)?;
+
+    // #block(..) { substack1 } { substack2 }
+    while raw_block.sub_stacks.len() < 2 && lex.expect_failsafe_wo_eof(Token::LBrace)?.is_some() {
+        raw_block.sub_stacks.push(inner_statements(lex)?);
+        raw_block.span.end = lex.expect(Token::RBrace)?.pos.end;
+    }
+
+    lex.success();
+    Ok(raw_block)
+}
+
+fn raw_block(lex: &mut Lexer) -> LogicParseResult<RawBlock> {
+    lex.start();
+
+    // #block("opcode", "spec", "type", "color", arguments)
+    let start = lex.expect(Token::RawBlock)?.pos.start;
+    lex.expect(Token::LParen)?;
+
+    let op_code = string_literal(lex)?;
+    lex.expect(Token::Comma)?;
+    let spec = string_literal(lex)?;
+    lex.expect(Token::Comma)?;
+    let block_type = string_literal(lex)?;
+    lex.expect(Token::Comma)?;
+    let color = string_literal(lex)?;
+
+    let arguments = if lex.expect_failsafe_wo_eof(Token::Comma)?.is_some() {
+        arguments(lex)?
+    } else {
+        Arguments(vec![])
+    };
+
+    let end = lex.expect(Token::RParen)?.pos.end;
+
+    lex.success();
+    Ok(RawBlock {
+        op_code,
+        spec,
+        block_type,
+        color,
+        arguments,
+        sub_stacks: vec![],
+        span: start..end,
+    })
+}
+
+fn string_literal(lex: &mut Lexer) -> LogicParseResult<String> {
+    Ok(string_value(&lex.expect(Token::String)?.slice))
+}
+
+// the value of a string token without its quotes. `\"` is a quote and `\\` is a backslash, other
+// escapes are kept as-is since they're java's, like `\n`
+fn string_value(slice: &str) -> String {
+    let mut value = String::new();
+    let mut chars = slice[1..slice.len() - 1].chars();
+
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            value.push(char);
+            continue;
+        }
+
+        match chars.next() {
+            Some(escaped @ ('"' | '\\')) => value.push(escaped),
+            Some(other) => {
+                value.push('\\');
+                value.push(other);
+            }
+            None => value.push('\\'),
+        }
+    }
+
+    value
 }
 
 fn expression(lex: &mut Lexer) -> LogicParseResult<Expression> {
@@ -508,6 +894,7 @@ fn boolean_expression(lex: &mut Lexer) -> LogicParseResult<Expression> {
         let second_branch = comparison_expression(lex)?;
 
         result = Expression::BinOp {
+            span: result.span().start..second_branch.span().end,
             first: Box::new(result),
             operator,
             second: Box::new(second_branch),
@@ -522,11 +909,12 @@ fn comparison_expression(lex: &mut Lexer) -> LogicParseResult<Expression> {
     lex.start();
 
     // "!" comparison-expression
-    if buffered_lexer::propagate_non_recoverable!(lex.expect_peek(Token::Not)).is_ok() {
+    if let Some(not) = lex.expect_failsafe_wo_eof(Token::Not)? {
         let expr = arithmetic_expression(lex)?;
 
         lex.success();
         return Ok(Expression::UnaryOp {
+            span: not.pos.start..expr.span().end,
             value: Box::new(expr),
             operator: UnaryOperator::Not,
         });
@@ -552,6 +940,7 @@ fn comparison_expression(lex: &mut Lexer) -> LogicParseResult<Expression> {
         let second_branch = comparison_expression(lex)?;
 
         result = Expression::BinOp {
+            span: result.span().start..second_branch.span().end,
             first: Box::new(result),
             operator,
             second: Box::new(second_branch),
@@ -576,6 +965,7 @@ fn arithmetic_expression(lex: &mut Lexer) -> LogicParseResult<Expression> {
         let second_branch = term(lex)?;
 
         result = Expression::BinOp {
+            span: result.span().start..second_branch.span().end,
             first: Box::new(result),
             operator,
             second: Box::new(second_branch),
@@ -600,6 +990,7 @@ fn term(lex: &mut Lexer) -> LogicParseResult<Expression> {
         let second_branch = factor(lex)?;
 
         result = Expression::BinOp {
+            span: result.span().start..second_branch.span().end,
             first: Box::new(result),
             operator,
             second: Box::new(second_branch),
@@ -613,21 +1004,23 @@ fn term(lex: &mut Lexer) -> LogicParseResult<Expression> {
 fn factor(lex: &mut Lexer) -> LogicParseResult<Expression> {
     lex.start();
 
-    if lex.expect_failsafe_wo_eof(Token::Plus)?.is_some() {
+    if let Some(plus) = lex.expect_failsafe_wo_eof(Token::Plus)? {
         let factor = factor(lex)?;
 
         lex.success();
         return Ok(Expression::UnaryOp {
+            span: plus.pos.start..factor.span().end,
             value: Box::new(factor),
             operator: UnaryOperator::Plus,
         });
     }
 
-    if lex.expect_failsafe_wo_eof(Token::Minus)?.is_some() {
+    if let Some(minus) = lex.expect_failsafe_wo_eof(Token::Minus)? {
         let factor = factor(lex)?;
 
         lex.success();
         return Ok(Expression::UnaryOp {
+            span: minus.pos.start..factor.span().end,
             value: Box::new(factor),
             operator: UnaryOperator::Minus,
         });
@@ -646,6 +1039,7 @@ fn power(lex: &mut Lexer) -> LogicParseResult<Expression> {
 
         lex.success();
         Expression::BinOp {
+            span: primary.span().start..power.span().end,
             first: Box::new(primary),
             operator: BinaryOperator::Power,
             second: Box::new(power),
@@ -673,15 +1067,17 @@ fn primary(lex: &mut Lexer) -> LogicParseResult<Expression> {
                 let ident = lex.expect(Token::Identifier)?;
 
                 result = Expression::PrimaryExpression(PrimaryExpression::VariableAccess {
+                    span: result.span().start..ident.pos.end,
                     from: Some(Box::new(result)),
                     name: ident.slice
                 })
             }
             SpannedTokenOwned { token: Token::LBracket, .. } => {
                 let index_expr = expression(lex)?;
-                lex.expect(Token::RBracket)?;
+                let r_bracket = lex.expect(Token::RBracket)?;
 
                 result = Expression::PrimaryExpression(PrimaryExpression::Index {
+                    span: result.span().start..r_bracket.pos.end,
                     from: Box::new(result),
                     index: Box::new(index_expr)
                 })
@@ -691,16 +1087,16 @@ fn primary(lex: &mut Lexer) -> LogicParseResult<Expression> {
             if matches!(result, Expression::PrimaryExpression(PrimaryExpression::VariableAccess { .. }))
             => {
                 let arguments = arguments(lex)?;
-                lex.expect(Token::RParen)?;
+                let r_paren = lex.expect(Token::RParen)?;
 
                 // only accept if the result is a variable access
                 // then we convert that variable access into a call
 
                 if let Expression::PrimaryExpression(
-                    PrimaryExpression::VariableAccess { from, name }
+                    PrimaryExpression::VariableAccess { from, name, span }
                 ) = result {
                     result = Expression::PrimaryExpression(
-                        PrimaryExpression::Call { from, name, arguments }
+                        PrimaryExpression::Call { from, name, arguments, span: span.start..r_paren.pos.end }
                     )
                 } else { unreachable!() }
             }
@@ -716,6 +1112,7 @@ fn arguments(lex: &mut Lexer) -> LogicParseResult<Arguments> {
     lex.start();
 
     if buffered_lexer::propagate_non_recoverable!(lex.expect_peek(Token::RParen)).is_ok() {
+        lex.success();
         return Ok(Arguments(vec![]));
     }
 
@@ -747,29 +1144,32 @@ fn atom(lex: &mut Lexer) -> LogicParseResult<Expression> {
         Token::False,
         Token::True,
         Token::LParen,
+        Token::RawBlock,
     ])? {
         SpannedTokenOwned {
             token: Token::Identifier,
             slice,
-            ..
+            pos,
         } => {
             lex.success();
             Ok(Expression::PrimaryExpression(
                 PrimaryExpression::VariableAccess {
                     from: None,
                     name: slice,
+                    span: pos,
                 },
             ))
         }
         SpannedTokenOwned {
             token: Token::String,
             slice,
-            ..
+            pos,
         } => {
             lex.success();
-            Ok(Expression::Literal(Literal::String(
-                slice[1..slice.len() - 1].to_string(),
-            )))
+            Ok(Expression::Literal {
+                value: Literal::String(string_value(&slice)),
+                span: pos,
+            })
         }
         SpannedTokenOwned {
             token: Token::Number,
@@ -783,20 +1183,32 @@ fn atom(lex: &mut Lexer) -> LogicParseResult<Expression> {
             })?;
 
             lex.success();
-            Ok(Expression::Literal(Literal::Number(num)))
+            Ok(Expression::Literal {
+                value: Literal::Number(num),
+                span: pos,
+            })
         }
         SpannedTokenOwned {
             token: Token::False,
+            pos,
             ..
         } => {
             lex.success();
-            Ok(Expression::Literal(Literal::Boolean(false)))
+            Ok(Expression::Literal {
+                value: Literal::Boolean(false),
+                span: pos,
+            })
         }
         SpannedTokenOwned {
-            token: Token::True, ..
+            token: Token::True,
+            pos,
+            ..
         } => {
             lex.success();
-            Ok(Expression::Literal(Literal::Boolean(true)))
+            Ok(Expression::Literal {
+                value: Literal::Boolean(true),
+                span: pos,
+            })
         }
         SpannedTokenOwned {
             token: Token::LParen,
@@ -806,6 +1218,16 @@ fn atom(lex: &mut Lexer) -> LogicParseResult<Expression> {
 
             Ok(group(lex)?)
         }
+        SpannedTokenOwned {
+            token: Token::RawBlock,
+            ..
+        } => {
+            lex.restore();
+
+            Ok(Expression::PrimaryExpression(PrimaryExpression::RawBlock(
+                raw_block(lex)?,
+            )))
+        }
         _ => unreachable!(),
     }
 }
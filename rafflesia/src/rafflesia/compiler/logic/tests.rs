@@ -1,4 +1,14 @@
-use super::parser::parse_logic;
+use super::ast::{Expression, InnerStatement, OuterStatement, ParameterType};
+use super::blocks::types::{ComplexType, GenerateError, PrimitiveType, Type};
+use super::parser::{parse_logic, parse_logic_recovering, Token};
+use super::{compile_logic, LogicCompileError};
+use crate::compiler::layout::compile_view_tree;
+use crate::compiler::layout::parser::parse_layout;
+use buffered_lexer::error::ParseError;
+use swrs::api::block::{ArgValue, Argument, ArgumentBlockReturnType, BlockType};
+use swrs::api::component::ComponentKind;
+use swrs::api::screen::EventType;
+use swrs::parser::logic::variable::VariableType;
 
 #[test]
 fn parse_1() {
@@ -130,3 +140,1015 @@ onCreate {
 
     println!("{:?}", ast);
 }
+
+#[test]
+fn parse_spans() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let code = r#"
+number a
+
+onCreate {
+    a = 10 + b.length()
+}
+"#
+    .trim();
+
+    let ast = parse_logic(code).expect("failed to parse code");
+
+    assert_eq!(&code[ast.0[0].span()], "number a");
+    assert_eq!(&code[ast.0[1].span()], &code[10..]);
+
+    let OuterStatement::ActivityEventListener { body, .. } = &ast.0[1] else {
+        panic!("not an activity event listener");
+    };
+
+    let InnerStatement::VariableAssignment(assignment) = &body.0[0] else {
+        panic!("not a variable assignment");
+    };
+
+    assert_eq!(&code[assignment.span.clone()], "a = 10 + b.length()");
+    assert_eq!(&code[assignment.value.span()], "10 + b.length()");
+
+    let Expression::BinOp { first, second, .. } = &assignment.value else {
+        panic!("not a binary operation");
+    };
+
+    assert_eq!(&code[first.span()], "10");
+    assert_eq!(&code[second.span()], "b.length()");
+}
+
+#[test]
+fn parse_recovering() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let code = r#"
+number a
+number = 5
+string b
+
+onCreate {
+    a = )
+    if a > 1 {
+        a = 5 +
+        b = "hello"
+    }
+    toast(b)
+}
+
+button1.onClick x {
+    toast("unreachable")
+}
+
+button2.onClick {
+    a = (a * 2
+    toast(b)
+}
+"#
+    .trim();
+
+    let (ast, errors) = parse_logic_recovering(code);
+
+    let error_tokens = errors
+        .iter()
+        .map(|err| match err {
+            ParseError::UnexpectedTokenError { pos, .. } => &code[pos.clone()],
+            _ => panic!("unexpected error {:?}", err),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(error_tokens, vec!["=", ")", "\n", "x", "\n"]);
+
+    // the statements around the errors are still there
+    let statements = ast
+        .0
+        .iter()
+        .map(|stmt| &code[stmt.span()])
+        .collect::<Vec<_>>();
+    assert_eq!(statements[..2], ["number a", "string b"]);
+    assert_eq!(statements.len(), 4);
+
+    let OuterStatement::ActivityEventListener { body, .. } = &ast.0[2] else {
+        panic!("not an activity event listener");
+    };
+
+    let InnerStatement::IfStatement(if_statement) = &body.0[0] else {
+        panic!("not an if statement");
+    };
+
+    assert_eq!(body.0.len(), 2);
+    assert_eq!(&code[if_statement.body.0[0].span()], "b = \"hello\"");
+
+    // the first error is the one returned by parse_logic
+    assert_eq!(parse_logic(code).unwrap_err(), errors[0]);
+}
+
+#[test]
+fn parse_contextual_keywords() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    // `view` and `moreblock` are only keywords where a type or a declaration is expected
+    let code = r#"
+moreblock show(view: view, moreblock: string) {
+    view = moreblock
+}
+
+moreblock.onClick {
+    toast(view)
+}
+"#
+    .trim();
+
+    let ast = parse_logic(code).expect("failed to parse code");
+
+    let OuterStatement::MoreBlockDeclaration {
+        name, parameters, ..
+    } = &ast.0[0]
+    else {
+        panic!("not a moreblock declaration");
+    };
+
+    assert_eq!(name, "show");
+    assert_eq!(parameters[0].name, "view");
+    assert_eq!(parameters[0].parameter_type, ParameterType::View);
+    assert_eq!(parameters[1].name, "moreblock");
+
+    let OuterStatement::ViewEventListener { view_id, .. } = &ast.0[1] else {
+        panic!("not a view event listener");
+    };
+
+    assert_eq!(view_id, "moreblock");
+
+    // any other identifier isn't a type
+    assert!(parse_logic("moreblock show(target: widget) {\n}").is_err());
+}
+
+#[test]
+fn parse_error_expected_tokens() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let expected = |code: &str| match parse_logic(code).unwrap_err() {
+        ParseError::UnexpectedTokenError {
+            expected: Some(expected),
+            ..
+        } => expected,
+        err => panic!("unexpected error {:?}", err),
+    };
+
+    // every kinds of statements are listed, not just the expression that was tried last
+    let tokens = expected("onCreate {\n    = 5\n}");
+    for token in [
+        Token::Identifier,
+        Token::If,
+        Token::Repeat,
+        Token::Break,
+        Token::Number,
+    ] {
+        assert!(tokens.contains(&token), "{:?} is not expected", token);
+    }
+
+    let tokens = expected("onCreate {\n    a = 5 +\n}");
+    assert!(tokens.contains(&Token::Number));
+    assert!(!tokens.contains(&Token::If));
+}
+
+#[test]
+fn compile_error_spans() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let compile = |code: &str| {
+        compile_logic(parse_logic(code).expect("failed to parse code"), &layout)
+            .expect_err("code should fail to compile")
+            .0
+            .remove(0)
+    };
+
+    let code = "number a\nonCreate {\n    a = 5 + unknown\n}";
+    let err = compile(code);
+    assert!(matches!(err, LogicCompileError::VariableDoesntExist { .. }));
+    assert_eq!(&code[err.span().clone()], "unknown");
+
+    let code = "number a\nonCreate {\n    a = \"hello\" * 2\n}";
+    let err = compile(code);
+    assert!(matches!(err, LogicCompileError::TypeError { .. }));
+    assert_eq!(&code[err.span().clone()], "\"hello\"");
+
+    let code = "string s\nonCreate {\n    toast(s.idk)\n}";
+    let err = compile(code);
+    assert!(matches!(err, LogicCompileError::MemberDoesntExist { .. }));
+    assert_eq!(&code[err.span().clone()], "s.idk");
+}
+
+#[test]
+fn compile_multiple_errors() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let code = r#"number a
+onCreate {
+    a = unknown
+    if a > 2 {
+        a = "hello" * 2
+    }
+    a = 5
+}
+
+button1.onClick {
+    toast(a.idk)
+}
+"#;
+
+    let errors = compile_logic(parse_logic(code).unwrap(), &layout)
+        .expect_err("code should fail to compile")
+        .0;
+
+    let spans = errors
+        .iter()
+        .map(|err| &code[err.span().clone()])
+        .collect::<Vec<_>>();
+
+    assert_eq!(spans, vec!["unknown", "\"hello\"", "a.idk"]);
+}
+
+#[test]
+fn compile_raw_block_type_errors() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let code = r##"onCreate {
+    toast(#block("x", "x", "component<foo>", "#ff000000"))
+    toast(#block("y", "y", "view<Foo>", "#ff000000"))
+}
+"##;
+
+    let errors = compile_logic(parse_logic(code).unwrap(), &layout)
+        .expect_err("code should fail to compile")
+        .0;
+
+    assert!(errors
+        .iter()
+        .all(|err| matches!(err, LogicCompileError::InvalidRawBlockType { .. })));
+
+    let spans = errors
+        .iter()
+        .map(|err| &code[err.span().clone()])
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        spans,
+        vec![
+            r##"#block("x", "x", "component<foo>", "#ff000000")"##,
+            r##"#block("y", "y", "view<Foo>", "#ff000000")"##
+        ]
+    );
+}
+
+#[test]
+fn compile_more_blocks() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let code = r#"list<string> names
+
+moreblock greet(name: string, times: number, target: view, items: list<string>) {
+    toast(name)
+}
+
+onCreate {
+    greet("world", 3, button1, names)
+}
+"#;
+
+    let compiled = compile_logic(parse_logic(code).unwrap(), &layout).expect("failed to compile");
+
+    let more_block = compiled
+        .more_blocks
+        .get("greet")
+        .expect("moreblock is missing");
+    assert_eq!(
+        more_block.spec.to_string(),
+        "greet %s.name %d.times %m.view.target %m.listStr.items"
+    );
+
+    // parameters are accessed through getArg blocks
+    let Argument::String {
+        value: ArgValue::Block(get_arg),
+        ..
+    } = more_block.code.0[0].content.get_args()[0]
+    else {
+        panic!("toast isn't given a block");
+    };
+    assert_eq!(get_arg.op_code, "getArg");
+    assert_eq!(get_arg.content.to_string(), "name");
+
+    let call = &compiled.events[0].code.0[0];
+    assert_eq!(call.op_code, "definedFunc");
+    assert_eq!(call.content.to_string(), more_block.spec.to_string());
+    assert_eq!(
+        call.content.get_args()[2],
+        &Argument::Menu {
+            name: "view.target".to_string(),
+            value: ArgValue::Value("button1".to_string()),
+        }
+    );
+}
+
+#[test]
+fn compile_more_block_errors() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let code = r#"moreblock greet(name: string) {
+    name = "hello"
+}
+
+moreblock greet() {
+}
+
+moreblock toast(text: string) {
+}
+
+onCreate {
+    greet(5)
+}
+"#;
+
+    let errors = compile_logic(parse_logic(code).unwrap(), &layout)
+        .expect_err("code should fail to compile")
+        .0;
+
+    assert!(matches!(
+        errors[0],
+        LogicCompileError::MoreBlockAlreadyExists { .. }
+    ));
+    assert_eq!(&code[errors[0].span().clone()], "moreblock greet() {\n}");
+    assert!(matches!(
+        errors[1],
+        LogicCompileError::MoreBlockAlreadyExists { .. }
+    ));
+
+    // parameters can't be assigned
+    assert!(matches!(
+        errors[2],
+        LogicCompileError::UnAssignableVariable { .. }
+    ));
+    assert_eq!(&code[errors[2].span().clone()], "name = \"hello\"");
+
+    assert!(matches!(
+        errors[3],
+        LogicCompileError::GenerateError {
+            error: GenerateError::InvalidArgumentType { index: 0, .. },
+            ..
+        }
+    ));
+    assert_eq!(errors.len(), 4);
+}
+
+#[test]
+fn compile_components() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let code = r#"sharedpreferences sp(file: "data")
+vibrator v
+string name
+
+onCreate {
+    name = sp.get("name")
+    v.vibrate(200)
+}
+"#;
+
+    let compiled = compile_logic(parse_logic(code).unwrap(), &layout).expect("failed to compile");
+
+    assert_eq!(
+        compiled.components.get("sp"),
+        Some(&ComponentKind::SharedPreferences {
+            file_name: "data".to_string()
+        })
+    );
+    assert_eq!(compiled.components.get("v"), Some(&ComponentKind::Vibrator));
+
+    // components aren't variables
+    assert_eq!(compiled.variables.len(), 1);
+
+    let Argument::String {
+        value: ArgValue::Block(get_data),
+        ..
+    } = compiled.events[0].code.0[0].content.get_args()[1]
+    else {
+        panic!("name isn't assigned to a block");
+    };
+    assert_eq!(get_data.op_code, "fileGetData");
+    assert_eq!(
+        get_data.content.get_args()[0],
+        &Argument::Menu {
+            name: "file".to_string(),
+            value: ArgValue::Value("sp".to_string()),
+        }
+    );
+
+    let vibrate = &compiled.events[0].code.0[1];
+    assert_eq!(vibrate.op_code, "vibratorAction");
+    assert_eq!(vibrate.content.to_string(), "%m.vibrator vibrate for %d ms");
+}
+
+#[test]
+fn compile_component_errors() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let code = r#"gyroscope g
+sharedpreferences sp
+sharedpreferences prefs(file: "data", mode: "private")
+timer button1
+
+onCreate {
+}
+"#;
+
+    let errors = compile_logic(parse_logic(code).unwrap(), &layout)
+        .expect_err("code should fail to compile")
+        .0;
+
+    let spans = errors
+        .iter()
+        .map(|err| &code[err.span().clone()])
+        .collect::<Vec<_>>();
+
+    assert!(matches!(
+        errors[0],
+        LogicCompileError::UnknownComponentType { .. }
+    ));
+    assert!(matches!(
+        errors[1],
+        LogicCompileError::MissingComponentArgument { .. }
+    ));
+    assert!(matches!(
+        errors[2],
+        LogicCompileError::UnknownComponentArgument { .. }
+    ));
+    assert!(matches!(
+        errors[3],
+        LogicCompileError::VariableAlreadyExists { .. }
+    ));
+    assert_eq!(
+        spans,
+        vec![
+            "gyroscope g",
+            "sharedpreferences sp",
+            "mode: \"private\"",
+            "timer button1"
+        ]
+    );
+}
+
+#[test]
+fn compile_component_events() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let code = r#"button1.onClick {
+    net.start("GET", "https://example.com", "page")
+}
+
+net.onResponse {
+    toast(response)
+}
+
+requestnetwork net
+"#;
+
+    let compiled = compile_logic(parse_logic(code).unwrap(), &layout).expect("failed to compile");
+
+    assert_eq!(
+        compiled.events[0].event_type,
+        EventType::ViewEvent {
+            id: "button1".to_string()
+        }
+    );
+    assert_eq!(
+        compiled.events[1].event_type,
+        EventType::ComponentEvent {
+            id: "net".to_string(),
+            component_type: 17
+        }
+    );
+
+    // event parameters are accessed through getArg blocks
+    let Argument::String {
+        value: ArgValue::Block(get_arg),
+        ..
+    } = compiled.events[1].code.0[0].content.get_args()[0]
+    else {
+        panic!("toast isn't given a block");
+    };
+    assert_eq!(get_arg.op_code, "getArg");
+    assert_eq!(get_arg.content.to_string(), "response");
+}
+
+#[test]
+fn compile_component_event_errors() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let code = r#"timer t
+firebasedb fdb(path: "users")
+
+t.onClick {
+}
+
+fdb.onChildAdded {
+    childKey = "key"
+}
+
+onCreate {
+    toast(childKey)
+}
+"#;
+
+    let errors = compile_logic(parse_logic(code).unwrap(), &layout)
+        .expect_err("code should fail to compile")
+        .0;
+
+    assert!(matches!(
+        errors[0],
+        LogicCompileError::ComponentEventDoesntExist { .. }
+    ));
+    assert_eq!(&code[errors[0].span().clone()], "t.onClick {\n}");
+
+    // event parameters are read-only, and can only be accessed inside the event
+    assert!(matches!(
+        errors[1],
+        LogicCompileError::UnAssignableVariable { .. }
+    ));
+    assert!(matches!(
+        errors[2],
+        LogicCompileError::VariableDoesntExist { .. }
+    ));
+    assert_eq!(errors.len(), 3);
+}
+
+#[test]
+fn compile_maps() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let code = r#"map<string> names
+map<number> scores
+number total
+boolean found
+
+onCreate {
+    names.put("a", "b")
+    scores.put("a", 10)
+    total = scores["a"]
+    found = names.containsKey("a")
+}
+"#;
+
+    let compiled = compile_logic(parse_logic(code).unwrap(), &layout).expect("failed to compile");
+
+    assert_eq!(
+        compiled.variables.get("names").map(|var| &var.r#type),
+        Some(&VariableType::HashMap)
+    );
+    assert_eq!(
+        compiled.variables.get("scores").map(|var| &var.r#type),
+        Some(&VariableType::HashMap)
+    );
+
+    let code = &compiled.events[0].code.0;
+
+    assert_eq!(code[0].op_code, "mapPut");
+    assert_eq!(
+        code[0].content.get_args()[0],
+        &Argument::Menu {
+            name: "varMap".to_string(),
+            value: ArgValue::Value("names".to_string()),
+        }
+    );
+
+    // numbers are stored as strings on sketchware's maps
+    let Argument::String {
+        value: ArgValue::Block(to_string),
+        ..
+    } = code[1].content.get_args()[2]
+    else {
+        panic!("the number isn't converted into a string");
+    };
+    assert_eq!(to_string.op_code, "toStringWithDecimal");
+
+    let Argument::Number {
+        value: ArgValue::Block(to_number),
+        ..
+    } = code[2].content.get_args()[1]
+    else {
+        panic!("total isn't assigned to a block");
+    };
+    assert_eq!(to_number.op_code, "toNumber");
+
+    let Argument::String {
+        value: ArgValue::Block(get),
+        ..
+    } = to_number.content.get_args()[0]
+    else {
+        panic!("the map value isn't converted into a number");
+    };
+    assert_eq!(get.op_code, "mapGet");
+
+    let Argument::Boolean {
+        value: ArgValue::Block(contains_key),
+        ..
+    } = code[3].content.get_args()[1]
+    else {
+        panic!("found isn't assigned to a block");
+    };
+    assert_eq!(contains_key.op_code, "mapContainKey");
+}
+
+#[test]
+fn compile_map_errors() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let code = r#"map<boolean> flags
+map<number> scores
+string name
+
+onCreate {
+    scores.put("a", "b")
+    name = scores
+}
+"#;
+
+    let errors = compile_logic(parse_logic(code).unwrap(), &layout)
+        .expect_err("code should fail to compile")
+        .0;
+
+    let spans = errors
+        .iter()
+        .map(|err| &code[err.span().clone()])
+        .collect::<Vec<_>>();
+
+    assert!(matches!(
+        errors[0],
+        LogicCompileError::InvalidInnerType { .. }
+    ));
+    assert!(matches!(
+        errors[1],
+        LogicCompileError::GenerateError {
+            error: GenerateError::InvalidArgumentType { index: 1, .. },
+            ..
+        }
+    ));
+    assert!(matches!(errors[2], LogicCompileError::TypeError { .. }));
+    assert_eq!(
+        spans,
+        vec!["map<boolean> flags", "scores.put(\"a\", \"b\")", "scores"]
+    );
+}
+
+#[test]
+fn compile_lists() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let code = r#"list<number> numbers
+list<string> names
+number total
+boolean found
+
+onCreate {
+    numbers.add(1)
+    names.insert(0, "a")
+    total = numbers[0] + numbers.length()
+    found = names.contains("a")
+    numbers.remove(0)
+}
+"#;
+
+    let compiled = compile_logic(parse_logic(code).unwrap(), &layout).expect("failed to compile");
+    let code = &compiled.events[0].code.0;
+
+    assert_eq!(code[0].op_code, "addListInt");
+    assert_eq!(code[0].content.to_string(), "add %d to %m.listInt");
+    assert_eq!(
+        code[0].content.get_args()[1],
+        &Argument::Menu {
+            name: "listInt".to_string(),
+            value: ArgValue::Value("numbers".to_string()),
+        }
+    );
+
+    assert_eq!(code[1].op_code, "insertListStr");
+    assert_eq!(code[1].content.to_string(), "insert %s at %d to %m.listStr");
+
+    let Argument::Number {
+        value: ArgValue::Block(plus),
+        ..
+    } = code[2].content.get_args()[1]
+    else {
+        panic!("total isn't assigned to a block");
+    };
+
+    let args = plus.content.get_args();
+    let (
+        Argument::Number {
+            value: ArgValue::Block(get),
+            ..
+        },
+        Argument::Number {
+            value: ArgValue::Block(length),
+            ..
+        },
+    ) = (args[0], args[1])
+    else {
+        panic!("the list items aren't blocks");
+    };
+    assert_eq!(get.op_code, "getAtListInt");
+    assert_eq!(length.op_code, "lengthList");
+
+    let Argument::Boolean {
+        value: ArgValue::Block(contains),
+        ..
+    } = code[3].content.get_args()[1]
+    else {
+        panic!("found isn't assigned to a block");
+    };
+    assert_eq!(contains.op_code, "containListStr");
+
+    assert_eq!(code[4].op_code, "deleteList");
+    assert_eq!(code[4].content.to_string(), "delete at %d of %m.list");
+}
+
+#[test]
+fn compile_list_errors() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let code = r#"list<boolean> flags
+list<number> numbers
+number total
+
+onCreate {
+    numbers.add("a")
+    numbers.insert(0, "a")
+    total = numbers["a"]
+}
+"#;
+
+    let errors = compile_logic(parse_logic(code).unwrap(), &layout)
+        .expect_err("code should fail to compile")
+        .0;
+
+    let spans = errors
+        .iter()
+        .map(|err| &code[err.span().clone()])
+        .collect::<Vec<_>>();
+
+    assert!(matches!(
+        errors[0],
+        LogicCompileError::InvalidInnerType { .. }
+    ));
+    assert!(matches!(
+        errors[1],
+        LogicCompileError::GenerateError {
+            error: GenerateError::InvalidArgumentType { index: 0, .. },
+            ..
+        }
+    ));
+    assert!(matches!(
+        errors[2],
+        LogicCompileError::GenerateError {
+            error: GenerateError::InvalidArgumentType { index: 1, .. },
+            ..
+        }
+    ));
+    assert!(matches!(
+        errors[3],
+        LogicCompileError::CannotBeIndexed { .. }
+    ));
+    assert_eq!(
+        spans,
+        vec![
+            "list<boolean> flags",
+            "numbers.add(\"a\")",
+            "numbers.insert(0, \"a\")",
+            "numbers[\"a\"]"
+        ]
+    );
+}
+
+#[test]
+fn compile_index_assignments() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let declarations = r#"map<string> names
+map<number> scores
+list<number> numbers
+"#;
+
+    let indexed = format!(
+        r#"{declarations}
+onCreate {{
+    names["a"] = "b"
+    scores["a"] = 10
+    numbers[0] = 5
+}}
+"#
+    );
+
+    let methods = format!(
+        r#"{declarations}
+onCreate {{
+    names.put("a", "b")
+    scores.put("a", 10)
+    numbers.set(0, 5)
+}}
+"#
+    );
+
+    let indexed =
+        compile_logic(parse_logic(&indexed).unwrap(), &layout).expect("failed to compile");
+    let methods =
+        compile_logic(parse_logic(&methods).unwrap(), &layout).expect("failed to compile");
+
+    assert_eq!(indexed.events[0].code, methods.events[0].code);
+}
+
+#[test]
+fn compile_index_assignment_errors() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let code = r#"map<string> names
+number total
+
+onCreate {
+    names["a"] = 1
+    total[0] = 1
+    names = names
+}
+"#;
+
+    let errors = compile_logic(parse_logic(code).unwrap(), &layout)
+        .expect_err("code should fail to compile")
+        .0;
+
+    let spans = errors
+        .iter()
+        .map(|err| &code[err.span().clone()])
+        .collect::<Vec<_>>();
+
+    assert!(matches!(
+        errors[0],
+        LogicCompileError::GenerateError {
+            error: GenerateError::InvalidArgumentType { index: 1, .. },
+            ..
+        }
+    ));
+    assert!(matches!(
+        errors[1],
+        LogicCompileError::IndexCannotBeAssigned {
+            typ: Type::Primitive(PrimitiveType::Number),
+            ..
+        }
+    ));
+    assert!(matches!(
+        errors[2],
+        LogicCompileError::UnAssignableVariable {
+            variable_type: Type::Complex(ComplexType::Map {
+                inner_type: PrimitiveType::String
+            }),
+            ..
+        }
+    ));
+    assert_eq!(
+        spans,
+        vec!["names[\"a\"] = 1", "total[0] = 1", "names = names"]
+    );
+}
+
+#[test]
+fn compile_negative_numbers() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let code = r#"number a
+number b
+
+onCreate {
+    a = -1
+    a = -b
+}
+"#;
+
+    let compiled = compile_logic(parse_logic(code).unwrap(), &layout).expect("failed to compile");
+    let code = &compiled.events[0].code.0;
+
+    // a negative number is a value
+    assert_eq!(
+        code[0].content.get_args()[1],
+        &Argument::Number {
+            name: None,
+            value: ArgValue::Value(-1.),
+        }
+    );
+
+    // while the negation of anything else is multiplied by -1
+    let Argument::Number {
+        value: ArgValue::Block(multiply),
+        ..
+    } = code[1].content.get_args()[1]
+    else {
+        panic!("-b isn't a block");
+    };
+
+    let args = multiply.content.get_args();
+
+    assert_eq!(multiply.op_code, "*");
+    assert!(matches!(
+        args[0],
+        Argument::Number {
+            value: ArgValue::Block(get_var),
+            ..
+        } if get_var.op_code == "getVar"
+    ));
+    assert_eq!(
+        args[1],
+        &Argument::Number {
+            name: None,
+            value: ArgValue::Value(-1.),
+        }
+    );
+}
+
+#[test]
+fn compile_not() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let code = r#"
+boolean a
+boolean b
+
+onCreate {
+    a = !b
+}
+"#
+    .trim();
+
+    let compiled = compile_logic(parse_logic(code).unwrap(), &layout).expect("failed to compile");
+
+    // `!` returns a boolean, it can be used as an argument
+    let Argument::Boolean {
+        value: ArgValue::Block(not),
+        ..
+    } = compiled.events[0].code.0[0].content.get_args()[1]
+    else {
+        panic!("a isn't assigned to a block");
+    };
+
+    assert_eq!(not.op_code, "not");
+    assert_eq!(
+        not.block_type,
+        BlockType::Argument(ArgumentBlockReturnType::Boolean)
+    );
+}
@@ -1,2 +1,65 @@
 pub mod layout;
 pub mod logic;
+
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// A byte range of a node or an error on the source code
+pub type Span = std::ops::Range<usize>;
+
+/// Every errors found while compiling a source code, there is always at least one error.
+#[derive(Debug, Error)]
+#[error("{} error(s) found, the first one being: {}", .0.len(), .0[0])]
+pub struct CompileErrors<E: std::error::Error>(pub Vec<E>);
+
+/// The kinds of resources a project can have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceKind {
+    Image,
+    Sound,
+    Font,
+}
+
+impl ResourceKind {
+    pub const ALL: [ResourceKind; 3] =
+        [ResourceKind::Image, ResourceKind::Sound, ResourceKind::Font];
+
+    /// The name of the folder inside `res/` where the resources of this kind are placed
+    pub fn folder(&self) -> &'static str {
+        match self {
+            ResourceKind::Image => "images",
+            ResourceKind::Sound => "sounds",
+            ResourceKind::Font => "fonts",
+        }
+    }
+}
+
+impl std::fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ResourceKind::Image => "image",
+            ResourceKind::Sound => "sound",
+            ResourceKind::Font => "font",
+        })
+    }
+}
+
+/// The names of the resources of a project, used to check the resources referenced on the codes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResourceNames {
+    pub images: HashSet<String>,
+    pub sounds: HashSet<String>,
+    pub fonts: HashSet<String>,
+    /// The custom views declared on the manifest
+    pub custom_views: HashSet<String>,
+}
+
+impl ResourceNames {
+    pub fn contains(&self, kind: ResourceKind, name: &str) -> bool {
+        match kind {
+            ResourceKind::Image => self.images.contains(name),
+            ResourceKind::Sound => self.sounds.contains(name),
+            ResourceKind::Font => self.fonts.contains(name),
+        }
+    }
+}
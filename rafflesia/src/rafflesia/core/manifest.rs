@@ -1,5 +1,7 @@
+use crate::compiler::Span;
+use crate::diagnostic::{Diagnostic, Diagnostics, SourceDiagnostics};
 use anyhow::{Context, Result};
-use chrono::DateTime;
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 use std::num::ParseIntError;
 use std::path::PathBuf;
@@ -7,16 +9,22 @@ use std::{collections::HashMap, fs};
 use swrs::api::library::{AdMob, Firebase, GoogleMap};
 use swrs::api::{Colors, Libraries, Metadata, Resources, SketchwareProject};
 use swrs::color::Color;
+use swrs::parser::file::{KeyboardSetting, Orientation, Theme};
 use swrs::parser::library::AdUnit;
+use swrs::parser::resource::ResourceItem;
 use thiserror::Error;
 use toml::value::Datetime;
+use toml::Spanned;
 
 // The structure of a swproj.toml file
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Manifest {
     pub project: ProjectTable,
     pub activity: HashMap<String, ActivityTable>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub view: HashMap<String, ViewTable>,
     pub library: Option<LibraryTable>,
+    pub resources: Option<ResourcesTable>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,10 +51,150 @@ pub struct ColorsTable {
     pub control_highlight: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
 pub struct ActivityTable {
     pub logic: String,
     pub layout: String,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub orientation: ActivityOrientation,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub theme: ActivityTheme,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub keyboard: ActivityKeyboard,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub fullscreen: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub toolbar: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub drawer: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub fab: bool,
+}
+
+impl ActivityTable {
+    /// Creates an activity with the default screen settings
+    pub fn new(logic: impl ToString, layout: impl ToString) -> Self {
+        ActivityTable {
+            logic: logic.to_string(),
+            layout: layout.to_string(),
+            orientation: Default::default(),
+            theme: Default::default(),
+            keyboard: Default::default(),
+            fullscreen: false,
+            toolbar: false,
+            drawer: false,
+            fab: false,
+        }
+    }
+}
+
+/// A custom view, a layout that's not an activity; like the layout of the items of a ListView, or
+/// the layout of a dialog. It is referred by its name on the `[view.<name>]` table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ViewTable {
+    pub layout: String,
+}
+
+// the settings that are left as default aren't written when generating a manifest
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// The `orientation` of an activity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ActivityOrientation {
+    #[default]
+    Portrait,
+    Landscape,
+    Both,
+}
+
+/// The `theme` of an activity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ActivityTheme {
+    #[default]
+    None,
+    Default,
+    NoActionBar,
+    FullScreen,
+}
+
+/// The `keyboard` setting of an activity, whether the keyboard is shown when it's opened
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ActivityKeyboard {
+    #[default]
+    Unspecified,
+    Visible,
+    Hidden,
+}
+
+impl From<ActivityOrientation> for Orientation {
+    fn from(orientation: ActivityOrientation) -> Self {
+        match orientation {
+            ActivityOrientation::Portrait => Orientation::Portrait,
+            ActivityOrientation::Landscape => Orientation::Landscape,
+            ActivityOrientation::Both => Orientation::Both,
+        }
+    }
+}
+
+impl From<&Orientation> for ActivityOrientation {
+    fn from(orientation: &Orientation) -> Self {
+        match orientation {
+            Orientation::Portrait => ActivityOrientation::Portrait,
+            Orientation::Landscape => ActivityOrientation::Landscape,
+            Orientation::Both => ActivityOrientation::Both,
+        }
+    }
+}
+
+impl From<ActivityTheme> for Theme {
+    fn from(theme: ActivityTheme) -> Self {
+        match theme {
+            ActivityTheme::None => Theme::None,
+            ActivityTheme::Default => Theme::Default,
+            ActivityTheme::NoActionBar => Theme::NoActionBar,
+            ActivityTheme::FullScreen => Theme::FullScreen,
+        }
+    }
+}
+
+impl From<&Theme> for ActivityTheme {
+    fn from(theme: &Theme) -> Self {
+        match theme {
+            Theme::None => ActivityTheme::None,
+            Theme::Default => ActivityTheme::Default,
+            Theme::NoActionBar => ActivityTheme::NoActionBar,
+            Theme::FullScreen => ActivityTheme::FullScreen,
+        }
+    }
+}
+
+impl From<ActivityKeyboard> for KeyboardSetting {
+    fn from(keyboard: ActivityKeyboard) -> Self {
+        match keyboard {
+            ActivityKeyboard::Unspecified => KeyboardSetting::Unspecified,
+            ActivityKeyboard::Visible => KeyboardSetting::Visible,
+            ActivityKeyboard::Hidden => KeyboardSetting::Hidden,
+        }
+    }
+}
+
+impl From<&KeyboardSetting> for ActivityKeyboard {
+    fn from(keyboard: &KeyboardSetting) -> Self {
+        match keyboard {
+            KeyboardSetting::Unspecified => ActivityKeyboard::Unspecified,
+            KeyboardSetting::Visible => ActivityKeyboard::Visible,
+            KeyboardSetting::Hidden => ActivityKeyboard::Hidden,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -89,15 +237,64 @@ pub struct GoogleMapLibraryTable {
     pub api_key: String,
 }
 
+/// Lists the resource files that the project uses, the files of a kind that isn't listed are
+/// taken from its folder on `res/`, see [`crate::core::resource`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourcesTable {
+    /// File names inside `res/images`
+    pub images: Option<Vec<String>>,
+    /// File names inside `res/sounds`
+    pub sounds: Option<Vec<String>>,
+    /// File names inside `res/fonts`
+    pub fonts: Option<Vec<String>>,
+}
+
+/// Parses the content of a manifest, a syntax error is returned as [`Diagnostics`] on the
+/// manifest file.
 pub fn parse_manifest_str(content: &str) -> Result<Manifest> {
-    Ok(toml::from_str::<Manifest>(content)?)
+    toml::from_str::<Manifest>(content).map_err(|err| {
+        let diagnostic = Diagnostic::from_manifest_error(&err, content);
+        Diagnostics::from(SourceDiagnostics::new(
+            MANIFEST_FILE_NAME,
+            content,
+            vec![diagnostic],
+        ))
+        .into()
+    })
 }
 
+/// Parses the manifest file at the given path, see [`parse_manifest_str`].
 pub fn parse_manifest(path: PathBuf) -> Result<Manifest> {
-    parse_manifest_str(&fs::read_to_string(path.clone()).context(format!(
+    let content = fs::read_to_string(path.clone()).context(format!(
         "Failed to parse manifest at path {}",
         path.display()
-    ))?)
+    ))?;
+
+    parse_manifest_str(&content)
+}
+
+/// The file name of a manifest
+pub const MANIFEST_FILE_NAME: &str = "swproj.toml";
+
+// only used to retrieve the spans of the colors, since it's not possible to create a `Spanned`
+// when generating a manifest out of a project
+#[derive(Deserialize)]
+struct ColorSpans {
+    project: ProjectColorSpans,
+}
+
+#[derive(Deserialize)]
+struct ProjectColorSpans {
+    colors: Option<HashMap<String, Spanned<String>>>,
+}
+
+/// Retrieves the span of a color value on the `[project.colors]` table of a manifest
+pub fn color_span(content: &str, key: &str) -> Option<Span> {
+    let spans = toml::from_str::<ColorSpans>(content).ok()?;
+    let color = spans.project.colors?.remove(key)?;
+
+    Some(color.start()..color.end())
 }
 
 impl TryInto<SketchwareProject> for Manifest {
@@ -124,30 +321,35 @@ impl TryInto<SketchwareProject> for Manifest {
                         color_primary: Color::parse_hex(&colors.primary).map_err(|err| {
                             ProjectGenerationError::ColorParseError {
                                 name: "colorPrimary".to_string(),
+                                key: "primary".to_string(),
                                 source: err,
                             }
                         })?,
                         color_primary_dark: Color::parse_hex(&colors.primary_dark).map_err(
                             |err| ProjectGenerationError::ColorParseError {
                                 name: "colorPrimaryDark".to_string(),
+                                key: "primary-dark".to_string(),
                                 source: err,
                             },
                         )?,
                         color_accent: Color::parse_hex(&colors.accent).map_err(|err| {
                             ProjectGenerationError::ColorParseError {
                                 name: "colorAccent".to_string(),
+                                key: "accent".to_string(),
                                 source: err,
                             }
                         })?,
                         color_control_normal: Color::parse_hex(&colors.control_normal).map_err(
                             |err| ProjectGenerationError::ColorParseError {
                                 name: "colorControlNormal".to_string(),
+                                key: "control-normal".to_string(),
                                 source: err,
                             },
                         )?,
                         color_control_highlight: Color::parse_hex(&colors.control_highlight)
                             .map_err(|err| ProjectGenerationError::ColorParseError {
                                 name: "colorControlHighlight".to_string(),
+                                key: "control-highlight".to_string(),
                                 source: err,
                             })?,
                     }
@@ -228,10 +430,138 @@ impl TryInto<SketchwareProject> for Manifest {
     }
 }
 
+impl From<&SketchwareProject> for Manifest {
+    fn from(project: &SketchwareProject) -> Self {
+        Manifest {
+            project: ProjectTable {
+                // the local id is specific to the device the project came from
+                id: None,
+                name: project.metadata.name.clone(),
+                workspace_name: Some(project.metadata.workspace_name.clone()),
+                package: project.metadata.package_name.clone(),
+                version_code: project.metadata.version_code,
+                version_name: project.metadata.version_name.clone(),
+                time_created: timestamp_to_toml_datetime(project.metadata.time_created),
+                sw_ver: project.metadata.sketchware_version,
+                colors: Some(ColorsTable {
+                    primary: color_to_hex(project.colors.color_primary),
+                    primary_dark: color_to_hex(project.colors.color_primary_dark),
+                    accent: color_to_hex(project.colors.color_accent),
+                    control_normal: color_to_hex(project.colors.color_control_normal),
+                    control_highlight: color_to_hex(project.colors.color_control_highlight),
+                }),
+            },
+            activity: project
+                .screens
+                .iter()
+                .map(|screen| {
+                    (
+                        screen.layout_name.clone(),
+                        ActivityTable {
+                            logic: format!("{}.logic", screen.layout_name),
+                            layout: format!("{}.layout", screen.layout_name),
+                            orientation: (&screen.orientation).into(),
+                            theme: (&screen.theme).into(),
+                            keyboard: (&screen.keyboard_setting).into(),
+                            fullscreen: screen.fullscreen_enabled,
+                            toolbar: screen.toolbar_enabled,
+                            drawer: screen.drawer_enabled,
+                            fab: screen.fab_enabled,
+                        },
+                    )
+                })
+                .collect(),
+            view: project
+                .custom_views
+                .iter()
+                .map(|view| {
+                    (
+                        view.name.clone(),
+                        ViewTable {
+                            layout: format!("{}.layout", view.name),
+                        },
+                    )
+                })
+                .collect(),
+            library: Some(LibraryTable {
+                compat: Some(CompatLibraryTable {
+                    enabled: project.libraries.app_compat_enabled,
+                }),
+                firebase: project.libraries.firebase.as_ref().map(|firebase| {
+                    FirebaseLibraryTable {
+                        enabled: true,
+                        api_key: firebase.api_key.clone(),
+                        project_id: firebase.project_id.clone(),
+                        app_id: firebase.app_id.clone(),
+                        storage_bucket: firebase.storage_bucket.clone(),
+                    }
+                }),
+                admob: project
+                    .libraries
+                    .ad_mob
+                    .as_ref()
+                    .map(|admob| AdMobLibraryTable {
+                        enabled: true,
+                        ad_units: admob
+                            .ad_units
+                            .iter()
+                            .map(|unit| (unit.name.clone(), unit.id.clone()))
+                            .collect(),
+                        test_devices: admob.test_devices.clone(),
+                    }),
+                google_map: project.libraries.google_map.as_ref().map(|google_map| {
+                    GoogleMapLibraryTable {
+                        enabled: true,
+                        api_key: google_map.api_key.clone(),
+                    }
+                }),
+            }),
+            // the resource files themselves aren't a part of the project files, see
+            // `ops::generate`
+            resources: resources_table(&project.resources),
+        }
+    }
+}
+
+// lists the file names of every resources, a kind without any resources is left out
+fn resources_table(resources: &Resources) -> Option<ResourcesTable> {
+    let files = |items: &[ResourceItem]| {
+        (!items.is_empty()).then(|| items.iter().map(|item| item.full_name.clone()).collect())
+    };
+
+    let table = ResourcesTable {
+        images: files(&resources.images),
+        sounds: files(&resources.sounds),
+        fonts: files(&resources.fonts),
+    };
+
+    (table != ResourcesTable::default()).then_some(table)
+}
+
 #[derive(Debug, Error)]
 pub enum ProjectGenerationError {
     #[error("failed to parse color {name}")]
-    ColorParseError { name: String, source: ParseIntError },
+    ColorParseError {
+        name: String,
+        // the key of the color on the `[project.colors]` table
+        key: String,
+        source: ParseIntError,
+    },
+}
+
+// the inverse of `toml_datetime_to_timestamp`
+fn timestamp_to_toml_datetime(timestamp: u64) -> Datetime {
+    Utc.timestamp_opt(timestamp as i64, 0)
+        .single()
+        .unwrap_or_else(Utc::now)
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+        .parse()
+        .unwrap()
+}
+
+// turns a color into the hex format used in swproj.toml, e.g. ff008dcd
+fn color_to_hex(color: Color) -> String {
+    format!("{:08x}", u32::from(color))
 }
 
 // reference: https://docs.rs/toml/latest/toml/value/struct.Datetime.html
@@ -1,2 +1,4 @@
 pub mod manifest;
 pub mod project;
+pub mod resource;
+pub mod source;
@@ -19,12 +19,16 @@ impl Project {
 
     pub fn find_project() -> Result<Self> {
         // todo: search for parent directories?
-        let current_dir = std::env::current_dir()?;
-        let manifest_file = current_dir.join(Path::new("swproj.toml"));
+        Self::open(&std::env::current_dir()?)
+    }
+
+    /// Opens the project at the given folder, which contains the manifest
+    pub fn open(path: &Path) -> Result<Self> {
+        let manifest_file = path.join(Path::new(manifest::MANIFEST_FILE_NAME));
         let manifest = manifest::parse_manifest(manifest_file)?;
 
         Ok(Self {
-            working_directory: current_dir,
+            working_directory: path.to_path_buf(),
             manifest,
         })
     }
@@ -0,0 +1,180 @@
+//! The resources of a project; images, sounds and fonts that are placed in `res/images`,
+//! `res/sounds` and `res/fonts`.
+//!
+//! Every files in those folders are used unless the manifest lists the files of that kind on its
+//! `[resources]` table. A resource is referred by its file name without the extension, an image
+//! `res/images/app_icon.png` is referred as `app_icon`.
+
+use crate::compiler::{ResourceKind, ResourceNames};
+use crate::core::manifest::ResourcesTable;
+use crate::core::source::{SourceError, SourceProvider};
+use swrs::api::Resources;
+use swrs::parser::resource::ResourceItem;
+use thiserror::Error;
+
+// the resource type sketchware gives to the resources added by the user
+const USER_RESOURCE_TYPE: u8 = 1;
+
+/// A resource file of a project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resource {
+    pub kind: ResourceKind,
+    /// The name that the codes use to refer to this resource
+    pub name: String,
+    pub file_name: String,
+}
+
+impl Resource {
+    /// The path of this resource relative to the project
+    pub fn path(&self) -> String {
+        format!("res/{}/{}", self.kind.folder(), self.file_name)
+    }
+}
+
+/// Every resources of a project, in the order of images, sounds then fonts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectResources(pub Vec<Resource>);
+
+impl ProjectResources {
+    /// Collects the resources of a project, `table` is the `[resources]` table of its manifest.
+    pub fn collect(
+        source: &dyn SourceProvider,
+        table: Option<&ResourcesTable>,
+    ) -> Result<Self, ResourceError> {
+        let mut resources: Vec<Resource> = Vec::new();
+
+        for kind in ResourceKind::ALL {
+            let folder = format!("res/{}", kind.folder());
+            let files = source.list(&folder)?;
+
+            let listed = table.and_then(|table| match kind {
+                ResourceKind::Image => table.images.as_ref(),
+                ResourceKind::Sound => table.sounds.as_ref(),
+                ResourceKind::Font => table.fonts.as_ref(),
+            });
+
+            let files = match listed {
+                Some(listed) => {
+                    if let Some(missing) = listed.iter().find(|file| !files.contains(file)) {
+                        return Err(ResourceError::Missing {
+                            kind,
+                            path: format!("{}/{}", folder, missing),
+                        });
+                    }
+
+                    listed.clone()
+                }
+                None => files,
+            };
+
+            for file_name in files {
+                let resource = Resource {
+                    kind,
+                    name: resource_name(&file_name).to_string(),
+                    file_name,
+                };
+
+                if !is_valid_name(&resource.name) {
+                    return Err(ResourceError::InvalidName {
+                        path: resource.path(),
+                        name: resource.name,
+                        kind,
+                    });
+                }
+
+                if let Some(other) = resources
+                    .iter()
+                    .find(|other| other.kind == kind && other.name == resource.name)
+                {
+                    return Err(ResourceError::DuplicateName {
+                        kind,
+                        first: other.path(),
+                        second: resource.path(),
+                        name: resource.name,
+                    });
+                }
+
+                resources.push(resource);
+            }
+        }
+
+        Ok(ProjectResources(resources))
+    }
+
+    pub fn of_kind(&self, kind: ResourceKind) -> impl Iterator<Item = &Resource> {
+        self.0.iter().filter(move |resource| resource.kind == kind)
+    }
+
+    /// The names of the resources, to check the resources referred by the codes
+    pub fn names(&self) -> ResourceNames {
+        let names = |kind| self.of_kind(kind).map(|res| res.name.clone()).collect();
+
+        ResourceNames {
+            images: names(ResourceKind::Image),
+            sounds: names(ResourceKind::Sound),
+            fonts: names(ResourceKind::Font),
+            ..Default::default()
+        }
+    }
+
+    /// Turns the resources into swrs' [`Resources`], to be put into a sketchware project
+    pub fn to_swrs(&self) -> Resources {
+        let items = |kind| {
+            self.of_kind(kind)
+                .map(|res| ResourceItem {
+                    full_name: res.file_name.clone(),
+                    name: res.name.clone(),
+                    r#type: USER_RESOURCE_TYPE,
+                })
+                .collect()
+        };
+
+        Resources {
+            images: items(ResourceKind::Image),
+            sounds: items(ResourceKind::Sound),
+            fonts: items(ResourceKind::Font),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ResourceError {
+    #[error("{kind} {path} is listed on the manifest, but it doesn't exist")]
+    Missing { kind: ResourceKind, path: String },
+
+    #[error(
+        "invalid {kind} name `{name}` of {path}, resource names must start with a lowercase \
+         letter and can only contain lowercase letters, digits and underscores"
+    )]
+    InvalidName {
+        kind: ResourceKind,
+        name: String,
+        path: String,
+    },
+
+    #[error("{first} and {second} are both {kind}s named `{name}`")]
+    DuplicateName {
+        kind: ResourceKind,
+        name: String,
+        first: String,
+        second: String,
+    },
+
+    #[error(transparent)]
+    SourceError(#[from] SourceError),
+}
+
+// the name of a resource is its file name without the extension
+fn resource_name(file_name: &str) -> &str {
+    file_name
+        .rsplit_once('.')
+        .map_or(file_name, |(name, _)| name)
+}
+
+// android only accepts resource names that are valid java identifiers in lowercase
+fn is_valid_name(name: &str) -> bool {
+    name.starts_with(|ch: char| ch.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_')
+}
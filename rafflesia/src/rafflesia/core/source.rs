@@ -0,0 +1,144 @@
+//! Where the files of a project are read from, so that a project can be built without being on
+//! the disk, like on rafflesia-web.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Provides the files of a project.
+///
+/// Paths are relative to the root of the project (where the manifest is) and are separated with
+/// `/`, for instance: `src/main.logic`.
+pub trait SourceProvider {
+    /// Reads the content of a file
+    fn read_bytes(&self, path: &str) -> Result<Vec<u8>, SourceError>;
+
+    /// Lists the names of the files (not folders) directly inside a folder, sorted by their
+    /// names. A folder that doesn't exist has no files.
+    fn list(&self, folder: &str) -> Result<Vec<String>, SourceError>;
+
+    /// Reads a text file
+    fn read(&self, path: &str) -> Result<String, SourceError> {
+        String::from_utf8(self.read_bytes(path)?).map_err(|_| SourceError::InvalidUtf8 {
+            path: path.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SourceError {
+    #[error("file {path} doesn't exist")]
+    NotFound { path: String },
+
+    #[error("file {path} is not a valid UTF-8 text")]
+    InvalidUtf8 { path: String },
+
+    #[error("failed to read {path}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Reads the files of a project from a folder on the disk.
+#[derive(Debug, Clone)]
+pub struct DirSource {
+    pub root: PathBuf,
+}
+
+impl DirSource {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        DirSource { root: root.into() }
+    }
+}
+
+impl SourceProvider for DirSource {
+    fn read_bytes(&self, path: &str) -> Result<Vec<u8>, SourceError> {
+        fs::read(self.root.join(path)).map_err(|err| match err.kind() {
+            ErrorKind::NotFound => SourceError::NotFound {
+                path: path.to_string(),
+            },
+            _ => SourceError::Io {
+                path: path.to_string(),
+                source: err,
+            },
+        })
+    }
+
+    fn list(&self, folder: &str) -> Result<Vec<String>, SourceError> {
+        let io_error = |err| SourceError::Io {
+            path: folder.to_string(),
+            source: err,
+        };
+
+        let entries = match fs::read_dir(self.root.join(folder)) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(io_error(err)),
+        };
+
+        let mut files = Vec::new();
+
+        for entry in entries {
+            let entry = entry.map_err(io_error)?;
+
+            if entry.file_type().map_err(io_error)?.is_file() {
+                files.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+
+        files.sort();
+        Ok(files)
+    }
+}
+
+/// Holds the files of a project in memory, keyed by their paths.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemorySource {
+    pub files: HashMap<String, Vec<u8>>,
+}
+
+impl MemorySource {
+    pub fn new() -> Self {
+        MemorySource::default()
+    }
+
+    /// Adds a file, replacing the file that has the same path
+    pub fn insert(&mut self, path: impl ToString, content: impl Into<Vec<u8>>) {
+        self.files.insert(path.to_string(), content.into());
+    }
+
+    pub fn with_file(mut self, path: impl ToString, content: impl Into<Vec<u8>>) -> Self {
+        self.insert(path, content);
+        self
+    }
+}
+
+impl SourceProvider for MemorySource {
+    fn read_bytes(&self, path: &str) -> Result<Vec<u8>, SourceError> {
+        self.files
+            .get(path)
+            .cloned()
+            .ok_or_else(|| SourceError::NotFound {
+                path: path.to_string(),
+            })
+    }
+
+    fn list(&self, folder: &str) -> Result<Vec<String>, SourceError> {
+        let prefix = format!("{}/", folder.trim_end_matches('/'));
+
+        let mut files = self
+            .files
+            .keys()
+            .filter_map(|path| path.strip_prefix(&prefix))
+            .filter(|name| !name.contains('/'))
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        files.sort();
+        Ok(files)
+    }
+}
@@ -0,0 +1,124 @@
+//! Messages printed by `rafflesia build --message-format json` for tools to read. Each message is
+//! printed as a JSON object on its own line, the diagnostics come first, then a summary of the
+//! build as the last one:
+//!
+//! ```json
+//! {"type":"diagnostic","severity":"error","code":"unexpected-token","message":"Syntax error",...}
+//! {"type":"summary","success":false,"errors":1,"warnings":0}
+//! ```
+
+use super::{Diagnostic, Diagnostics, Severity, SourceDiagnostics};
+use crate::compiler::Span;
+use buffered_lexer::source_map::{ColumnMode, SourceFile};
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Message {
+    Diagnostic(JsonDiagnostic),
+    Summary(BuildSummary),
+}
+
+impl Message {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("failed to serialize a message")
+    }
+}
+
+/// A [`Diagnostic`] along with the file it's on and where it is.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JsonDiagnostic {
+    pub severity: Severity,
+    pub code: Option<&'static str>,
+    pub message: String,
+    pub label: String,
+    pub file: String,
+    pub span: JsonSpan,
+    pub notes: Vec<String>,
+}
+
+impl JsonDiagnostic {
+    pub fn new(diagnostic: &Diagnostic, source: &SourceFile) -> Self {
+        JsonDiagnostic {
+            severity: diagnostic.severity,
+            code: diagnostic.code,
+            message: diagnostic.message.clone(),
+            label: diagnostic.label.clone(),
+            file: source.name().to_string(),
+            span: JsonSpan::new(diagnostic.span.clone(), source),
+            notes: diagnostic.note.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Where a diagnostic is, in bytes and in lines and columns. Lines and columns start from 1, and
+/// the columns are counted in bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonSpan {
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub line_start: usize,
+    pub column_start: usize,
+    pub line_end: usize,
+    pub column_end: usize,
+}
+
+impl JsonSpan {
+    pub fn new(span: Span, source: &SourceFile) -> Self {
+        let start = source.line_col(span.start, ColumnMode::Utf8);
+        let end = source.line_col(span.end, ColumnMode::Utf8);
+
+        JsonSpan {
+            byte_start: span.start,
+            byte_end: span.end,
+            line_start: start.line,
+            column_start: start.column,
+            line_end: end.line,
+            column_end: end.column,
+        }
+    }
+}
+
+/// The last message of a build.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BuildSummary {
+    pub success: bool,
+    pub errors: usize,
+    pub warnings: usize,
+    /// An error that isn't located on a source file, like a missing file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SourceDiagnostics {
+    /// Turns every diagnostics into messages, in the order they're reported
+    pub fn json_messages(&self) -> Vec<Message> {
+        self.diagnostics
+            .iter()
+            .map(|diagnostic| Message::Diagnostic(JsonDiagnostic::new(diagnostic, &self.source)))
+            .collect()
+    }
+
+    /// Counts the diagnostics that have the given severity
+    pub fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    }
+}
+
+impl Diagnostics {
+    /// Turns every diagnostics of every source files into messages
+    pub fn json_messages(&self) -> Vec<Message> {
+        self.0
+            .iter()
+            .flat_map(SourceDiagnostics::json_messages)
+            .collect()
+    }
+
+    /// Counts the diagnostics that have the given severity
+    pub fn count(&self, severity: Severity) -> usize {
+        self.0.iter().map(|source| source.count(severity)).sum()
+    }
+}
@@ -0,0 +1,297 @@
+//! Errors that are located on a source file, rendered into reports using [`ariadne`].
+
+#[cfg(test)]
+mod tests;
+
+pub mod json;
+
+use crate::compiler::layout::ViewCompileError;
+use crate::compiler::logic::LogicCompileError;
+use crate::compiler::Span;
+use crate::core::manifest::{color_span, ProjectGenerationError};
+use ariadne::{sources, Config, Label, Report, ReportKind};
+use buffered_lexer::error::ParseError;
+use buffered_lexer::source_map::{ColumnMode, LineCol, SourceFile};
+use buffered_lexer::SpannedTokenOwned;
+use std::fmt::{Debug, Display, Formatter};
+use thiserror::Error;
+
+/// How severe a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// An error located on a span of a source file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// A short identifier of what went wrong, like `unexpected-token`, for tools to match on
+    pub code: Option<&'static str>,
+    /// What kind of error this is, shown as the title of the report
+    pub message: String,
+    pub span: Span,
+    /// The error itself, shown on the label pointing at the span
+    pub label: String,
+    pub note: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(message: impl ToString, span: Span, label: impl ToString) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            code: None,
+            message: message.to_string(),
+            span,
+            label: label.to_string(),
+            note: None,
+        }
+    }
+
+    pub fn with_note(mut self, note: impl ToString) -> Self {
+        self.note = Some(note.to_string());
+        self
+    }
+
+    pub fn with_code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Creates a diagnostic out of a syntax error of a logic or a layout code
+    pub fn from_parse_error<T: Debug + Clone + PartialEq>(
+        err: &ParseError<T, SpannedTokenOwned<T>>,
+        source: &str,
+    ) -> Self {
+        match err {
+            ParseError::UnexpectedTokenError {
+                expected,
+                unexpected_token,
+                pos,
+            } => Diagnostic::new(
+                "Syntax error",
+                pos.clone(),
+                match expected {
+                    Some(expected) => format!(
+                        "unexpected {:?}, expected {}",
+                        unexpected_token.token,
+                        expected_tokens(expected)
+                    ),
+                    None => format!("unexpected {:?}", unexpected_token.token),
+                },
+            )
+            .with_code("unexpected-token"),
+
+            ParseError::EOF { expected } => Diagnostic::new(
+                "Syntax error",
+                source.len()..source.len(),
+                match expected {
+                    Some(expected) => format!(
+                        "unexpected end of file, expected {}",
+                        expected_tokens(expected)
+                    ),
+                    None => "unexpected end of file".to_string(),
+                },
+            )
+            .with_code("unexpected-eof"),
+
+            ParseError::LexerError { pos, slice, .. } => Diagnostic::new(
+                "Syntax error",
+                pos.clone(),
+                format!("invalid token `{slice}`"),
+            )
+            .with_code("invalid-token"),
+        }
+    }
+
+    /// Creates a diagnostic out of an error while compiling a logic code
+    pub fn from_logic_compile_error(err: &LogicCompileError) -> Self {
+        Diagnostic::new("Failed to compile logic", err.span().clone(), err)
+            .with_code("logic-compile-error")
+    }
+
+    /// Creates a diagnostic out of an error while compiling a layout code, errors on children are
+    /// pointed at the child that caused the error
+    pub fn from_view_compile_error(err: &ViewCompileError) -> Self {
+        let mut err = err;
+
+        while let ViewCompileError::ChildCompileError { source, .. } = err {
+            err = source.as_ref();
+        }
+
+        let code = match err {
+            ViewCompileError::UnknownResource { .. } => "unknown-resource",
+            ViewCompileError::UnknownCustomView { .. } => "unknown-custom-view",
+            _ => "layout-compile-error",
+        };
+
+        Diagnostic::new("Failed to compile layout", err.span().clone(), err).with_code(code)
+    }
+
+    /// Creates a diagnostic out of a syntax or a type error of a manifest
+    pub fn from_manifest_error(err: &toml::de::Error, source: &str) -> Self {
+        // toml only gives us a zero-based line and column of where the error is
+        let offset = err
+            .line_col()
+            .map(|(line, column)| {
+                source
+                    .split_inclusive('\n')
+                    .take(line)
+                    .map(|line| line.len())
+                    .sum::<usize>()
+                    + column
+            })
+            .unwrap_or(0)
+            .min(source.len());
+
+        Diagnostic::new("Invalid manifest", offset..offset, err).with_code("invalid-manifest")
+    }
+
+    /// Creates a diagnostic out of an error while turning a manifest into a sketchware project
+    pub fn from_project_generation_error(err: &ProjectGenerationError, source: &str) -> Self {
+        match err {
+            ProjectGenerationError::ColorParseError {
+                key, source: error, ..
+            } => Diagnostic::new(
+                "Invalid manifest",
+                color_span(source, key).unwrap_or_default(),
+                err,
+            )
+            .with_code("invalid-color")
+            .with_note(format!(
+                "{error}, colors are written in hex, for instance: \"ff008dcd\""
+            )),
+        }
+    }
+
+    /// Returns the line and column of where this diagnostic starts on the source file, columns
+    /// are counted in bytes
+    pub fn location(&self, source: &SourceFile) -> LineCol {
+        source.line_col(self.span.start, ColumnMode::Utf8)
+    }
+
+    /// Renders this diagnostic into a report on the given source file
+    pub fn render(&self, source: &SourceFile, color: bool) -> String {
+        let file = source.name().to_string();
+
+        // ariadne counts characters rather than bytes
+        let span = source.char_span(self.span.clone());
+
+        let kind = match self.severity {
+            Severity::Error => ReportKind::Error,
+            Severity::Warning => ReportKind::Warning,
+        };
+
+        let mut report = Report::build(kind, file.clone(), span.start)
+            .with_config(Config::default().with_color(color))
+            .with_message(&self.message)
+            .with_label(Label::new((file.clone(), span)).with_message(&self.label));
+
+        if let Some(note) = &self.note {
+            report = report.with_note(note);
+        }
+
+        let mut result = Vec::new();
+
+        report
+            .finish()
+            .write(sources(vec![(file, source.source())]), &mut result)
+            .expect("failed to write a report into a buffer");
+
+        String::from_utf8_lossy(&result).into_owned()
+    }
+}
+
+// lists the expected tokens, e.g. `Comma or RBrace`
+fn expected_tokens<T: Debug>(expected: &[T]) -> String {
+    let tokens = expected
+        .iter()
+        .map(|token| format!("{:?}", token))
+        .collect::<Vec<_>>();
+
+    match tokens.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+        Some((last, _)) => last.to_string(),
+        None => "nothing".to_string(),
+    }
+}
+
+/// The diagnostics of a source file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceDiagnostics {
+    pub source: SourceFile,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl SourceDiagnostics {
+    pub fn new(file: impl ToString, code: impl ToString, diagnostics: Vec<Diagnostic>) -> Self {
+        SourceDiagnostics {
+            source: SourceFile::new(file, code),
+            diagnostics,
+        }
+    }
+
+    /// Renders every diagnostics into reports on the source file
+    pub fn render(&self, color: bool) -> String {
+        self.diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.render(&self.source, color))
+            .collect()
+    }
+}
+
+/// Diagnostics of one or more source files.
+///
+/// This is used as an error to be carried around in an [`anyhow::Error`], so that it can be
+/// rendered as reports once it reaches the user.
+#[derive(Debug, Error)]
+pub struct Diagnostics(pub Vec<SourceDiagnostics>);
+
+impl Diagnostics {
+    /// Renders every diagnostics into reports
+    pub fn render(&self, color: bool) -> String {
+        self.0.iter().map(|source| source.render(color)).collect()
+    }
+
+    /// Prints the rendered reports to stderr, colored when stderr supports it
+    pub fn eprint(&self) {
+        eprint!("{}", self.render(console::colors_enabled_stderr()));
+    }
+}
+
+impl From<SourceDiagnostics> for Diagnostics {
+    fn from(source: SourceDiagnostics) -> Self {
+        Diagnostics(vec![source])
+    }
+}
+
+impl Display for Diagnostics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut diagnostics = self.0.iter().flat_map(|source| {
+            source
+                .diagnostics
+                .iter()
+                .map(move |diagnostic| (&source.source, diagnostic))
+        });
+
+        match (diagnostics.next(), diagnostics.count()) {
+            (Some((source, diagnostic)), 0) => write!(
+                f,
+                "{} on {}:{}: {}",
+                diagnostic.message.to_lowercase(),
+                source.name(),
+                diagnostic.location(source),
+                diagnostic.label
+            ),
+            (Some(_), others) => write!(f, "{} errors found", others + 1),
+            (None, _) => write!(f, "no errors found"),
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use super::json::{BuildSummary, JsonSpan, Message};
+use super::{Diagnostic, Diagnostics, Severity, SourceDiagnostics};
+use crate::compiler::layout::compile_view_tree;
+use crate::compiler::layout::parser::parse_layout;
+use crate::compiler::logic::compile_logic;
+use crate::compiler::logic::parser::parse_logic;
+use crate::core::manifest::Manifest;
+use buffered_lexer::source_map::SourceFile;
+use swrs::api::SketchwareProject;
+
+#[test]
+fn parse_error() {
+    let code = "onCreate {\n    a = )\n}";
+    let err = parse_logic(code).unwrap_err();
+    let diagnostic = Diagnostic::from_parse_error(&err, code);
+
+    assert_eq!(diagnostic.message, "Syntax error");
+    assert_eq!(&code[diagnostic.span.clone()], ")");
+    assert!(diagnostic.label.starts_with("unexpected RParen, expected "));
+
+    let code = "LinearLayout (text: hello,";
+    let err = parse_layout(code).unwrap_err();
+    let diagnostic = Diagnostic::from_parse_error(&err, code);
+
+    assert_eq!(diagnostic.span, code.len()..code.len());
+    assert!(diagnostic.label.starts_with("unexpected end of file"));
+}
+
+#[test]
+fn compile_errors() {
+    let layout = "LinearLayout {\n    Button (text_size: big): button1\n}";
+    let err = compile_view_tree(parse_layout(layout).unwrap()).unwrap_err();
+    let diagnostic = Diagnostic::from_view_compile_error(&err.0[0]);
+
+    // points to the attribute of the child, not the child itself
+    assert_eq!(&layout[diagnostic.span.clone()], "big");
+
+    let view = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let logic = "number a\nonCreate {\n    a = 5 + unknown\n}";
+    let err = compile_logic(parse_logic(logic).unwrap(), &view).unwrap_err();
+    let err = &err.0[0];
+    let diagnostic = Diagnostic::from_logic_compile_error(err);
+
+    assert_eq!(diagnostic.message, "Failed to compile logic");
+    assert_eq!(&logic[diagnostic.span.clone()], "unknown");
+    assert_eq!(diagnostic.label, err.to_string());
+}
+
+const MANIFEST: &str = r#"[project]
+name = "Test"
+package = "com.test"
+version-code = 1
+version-name = "1.0"
+time-created = 2022-06-25T08:51:55Z
+sw-ver = 150
+
+[project.colors]
+primary = "ff008dcd"
+primary-dark = "nope"
+accent = "ff008dcd"
+control-normal = "ff57beee"
+control-highlight = "20000000"
+
+[activity.main]
+logic = "main.logic"
+layout = "main.layout"
+"#;
+
+#[test]
+fn manifest_errors() {
+    let manifest = toml::from_str::<Manifest>(MANIFEST).unwrap();
+    let err = TryInto::<SketchwareProject>::try_into(manifest).unwrap_err();
+    let diagnostic = Diagnostic::from_project_generation_error(&err, MANIFEST);
+
+    assert_eq!(&MANIFEST[diagnostic.span.clone()], "\"nope\"");
+
+    let source = MANIFEST.replace("sw-ver = 150", "sw-ver = ");
+    let err = toml::from_str::<Manifest>(&source).unwrap_err();
+    let diagnostic = Diagnostic::from_manifest_error(&err, &source);
+
+    let line = source[..diagnostic.span.start].lines().count();
+    assert_eq!(source.lines().nth(line - 1), Some("sw-ver = "));
+}
+
+#[test]
+fn render() {
+    let code = "number a\nonCreate {\n    a = \"hello\" * 2\n}";
+    let diagnostic = Diagnostic::new("Failed to compile logic", 29..36, "wrong type given")
+        .with_note("strings can't be multiplied");
+
+    let rendered = diagnostic.render(&SourceFile::new("src/main.logic", code), false);
+
+    assert!(rendered.contains("Failed to compile logic"));
+    assert!(rendered.contains("src/main.logic"));
+    assert!(rendered.contains("    a = \"hello\" * 2"));
+    assert!(rendered.contains("wrong type given"));
+    assert!(rendered.contains("strings can't be multiplied"));
+}
+
+#[test]
+fn display_location() {
+    let code = "number a\nonCreate {\n    toast(\"é\" * a)\n}";
+    let span = code.find('*').unwrap();
+
+    let diagnostics = Diagnostics::from(SourceDiagnostics::new(
+        "src/main.logic",
+        code,
+        vec![Diagnostic::new(
+            "Failed to compile logic",
+            span..span + 1,
+            "wrong type given",
+        )],
+    ));
+
+    assert_eq!(
+        diagnostics.to_string(),
+        "failed to compile logic on src/main.logic:3:16: wrong type given"
+    );
+
+    // the label still points at the operator even with a multi-byte character before it
+    let rendered = diagnostics.render(false);
+    let column = |c: char| {
+        rendered
+            .lines()
+            .find_map(|line| line.chars().position(|ch| ch == c))
+            .unwrap_or_else(|| panic!("{:?} is not rendered", c))
+    };
+
+    assert_eq!(column('┬'), column('*'));
+}
+
+#[test]
+fn json_messages() {
+    let code = "onCreate {\n    a = )\n}";
+    let err = parse_logic(code).unwrap_err();
+
+    let diagnostics = Diagnostics::from(SourceDiagnostics::new(
+        "src/main.logic",
+        code,
+        vec![Diagnostic::from_parse_error(&err, code).with_note("a note")],
+    ));
+
+    let messages = diagnostics.json_messages();
+    assert_eq!(messages.len(), 1);
+
+    let Message::Diagnostic(diagnostic) = &messages[0] else {
+        panic!("not a diagnostic");
+    };
+
+    assert_eq!(diagnostic.code, Some("unexpected-token"));
+    assert_eq!(diagnostic.file, "src/main.logic");
+    assert_eq!(
+        diagnostic.span,
+        JsonSpan {
+            byte_start: 19,
+            byte_end: 20,
+            line_start: 2,
+            column_start: 9,
+            line_end: 2,
+            column_end: 10,
+        }
+    );
+
+    let json = messages[0].to_json();
+    assert!(
+        json.starts_with(r#"{"type":"diagnostic","severity":"error","code":"unexpected-token""#)
+    );
+    assert!(json.ends_with(r#""notes":["a note"]}"#));
+
+    assert_eq!(diagnostics.count(Severity::Error), 1);
+    assert_eq!(diagnostics.count(Severity::Warning), 0);
+
+    let summary = Message::Summary(BuildSummary {
+        success: false,
+        errors: 1,
+        warnings: 0,
+        error: None,
+    });
+
+    assert_eq!(
+        summary.to_json(),
+        r#"{"type":"summary","success":false,"errors":1,"warnings":0}"#
+    );
+}
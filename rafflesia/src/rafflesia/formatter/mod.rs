@@ -0,0 +1,240 @@
+#[cfg(test)]
+mod tests;
+
+use crate::compiler::layout::parser::{
+    self as layout_parser, parse_layout_with, LayoutParseError, Token as LayoutToken,
+};
+use crate::compiler::logic::parser::{
+    self as logic_parser, parse_logic_with, LogicParseError, Token as LogicToken,
+};
+use crate::generator::layout::print_layout_with_comments;
+use crate::generator::logic::print_logic_with_comments;
+use buffered_lexer::BufferedLexer;
+use logos::Logos;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+
+/// Formats a logic source code into the canonical style.
+///
+/// The code is parsed and printed back using [`crate::generator::logic::print_logic`], the
+/// comments and empty lines are placed back around the statements they're attached to.
+pub fn format_logic(source: &str) -> Result<String, LogicParseError> {
+    let mut lex = logic_parser::lexer(source);
+    let statements = parse_logic_with(&mut lex)?;
+
+    // newlines are tokens, a comment that comes after one is on its own line
+    let mut comments = Comments::new(
+        source,
+        &lex,
+        |token| *token == LogicToken::Comment,
+        |token| *token == LogicToken::Newline,
+    );
+
+    Ok(print_logic_with_comments(&statements, &mut comments))
+}
+
+/// Formats a layout source code into the canonical style.
+///
+/// The code is parsed and printed back using [`crate::generator::layout::print_layout`], the
+/// comments and empty lines are placed back around the views and attributes they're attached to.
+pub fn format_layout(source: &str) -> Result<String, LayoutParseError> {
+    let mut lex = layout_parser::lexer(source);
+    let view = parse_layout_with(&mut lex)?;
+
+    // the parser stops right after the root view, the comments at the end of the code are only
+    // collected once the lexer reaches the end
+    while lex.next().is_ok() {}
+
+    let mut comments = Comments::new(
+        source,
+        &lex,
+        |token| *token == LayoutToken::Comment,
+        |_| false,
+    );
+
+    Ok(print_layout_with_comments(&view, &mut comments))
+}
+
+/// The comments and empty lines of a source code, taken out of the trivia of a lossless lexer
+/// (see [`BufferedLexer::new_lossless`]).
+///
+/// Printers place them back while going through the AST: before printing a line, they call
+/// [`Comments::line`] with the position of the line's first token on the source code, every
+/// trivia that comes before it gets placed. A comment that is on the same line as the token
+/// it's attached to stays at the end of the line where that token got printed, the others are
+/// placed on their own lines.
+#[derive(Debug, Default)]
+pub(crate) struct Comments<'s> {
+    // the slice and position of every significant tokens
+    tokens: Vec<(&'s str, usize)>,
+    // the trivia that haven't been placed yet along with their position, in order
+    trivia: VecDeque<(usize, Trivia<'s>)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Trivia<'s> {
+    // a comment placed at the end of the line of the token before it
+    Trailing(&'s str),
+    // a comment placed on its own line
+    Comment(&'s str),
+    EmptyLine,
+}
+
+const INDENT: &str = "    ";
+
+impl<'s> Comments<'s> {
+    fn new<T>(
+        source: &'s str,
+        lexer: &BufferedLexer<'s, T>,
+        is_comment: fn(&T) -> bool,
+        is_line_break: fn(&T) -> bool,
+    ) -> Self
+    where
+        T: Logos<'s, Source = str> + Debug + Clone + PartialEq,
+    {
+        let mut comments = Comments::default();
+
+        // every tokens and trivia in order, trivia come with whether they're on the same line
+        // as the token before them
+        let mut items = Vec::new();
+
+        for token in lexer.lossless_tokens() {
+            // a trivia after a line break is on a line of its own
+            let same_line = !is_line_break(&token.token.token);
+
+            items.extend(token.leading.iter().map(|trivia| (trivia, Some(false))));
+            items.push((&token.token, None));
+            items.extend(
+                token
+                    .trailing
+                    .iter()
+                    .map(|trivia| (trivia, Some(same_line))),
+            );
+        }
+
+        items.extend(
+            lexer
+                .eof_trivia()
+                .iter()
+                .map(|trivia| (trivia, Some(false))),
+        );
+
+        // where the last token or comment that isn't a line break ends
+        let mut last_end = None;
+
+        for (token, same_line) in items {
+            match same_line {
+                // whitespaces are thrown away, the printer does its own
+                Some(_) if !is_comment(&token.token) => continue,
+                Some(_) => (),
+                None => {
+                    comments.tokens.push((token.slice, token.pos.start));
+
+                    if is_line_break(&token.token) {
+                        continue;
+                    }
+                }
+            }
+
+            if let Some(last_end) = last_end.replace(token.pos.end) {
+                if source[last_end..token.pos.start].matches('\n').count() > 1 {
+                    comments.trivia.push_back((last_end, Trivia::EmptyLine));
+                }
+            }
+
+            match same_line {
+                Some(true) => comments
+                    .trivia
+                    .push_back((token.pos.start, Trivia::Trailing(token.slice.trim_end()))),
+                Some(false) => comments
+                    .trivia
+                    .push_back((token.pos.start, Trivia::Comment(token.slice.trim_end()))),
+                None => (),
+            }
+        }
+
+        comments
+    }
+
+    /// Returns the position of the first token that is `slice`, starting from `from`. Returns
+    /// `from` if there isn't any.
+    pub(crate) fn find(&self, slice: &str, from: usize) -> usize {
+        let start = self
+            .tokens
+            .partition_point(|(_, position)| *position < from);
+
+        self.tokens[start..]
+            .iter()
+            .find(|(token, _)| *token == slice)
+            .map(|(_, position)| *position)
+            .unwrap_or(from)
+    }
+
+    /// Checks if there are comments that haven't been placed within a range of the source
+    pub(crate) fn contains(&self, range: std::ops::Range<usize>) -> bool {
+        self.trivia
+            .iter()
+            .any(|(position, trivia)| range.contains(position) && *trivia != Trivia::EmptyLine)
+    }
+
+    /// Places the trivia before a line whose first token is at `position`. Comments on their
+    /// own line are indented with `indent`.
+    pub(crate) fn line(&mut self, result: &mut String, position: usize, indent: &str) {
+        self.place(result, position, indent, false);
+    }
+
+    /// Places the trivia before a line that closes a block with the token at `position`, these
+    /// are at the end of the block so their indentation is one level deeper than `indent`.
+    pub(crate) fn closing(&mut self, result: &mut String, position: usize, indent: &str) {
+        self.place(result, position, &format!("{}{}", indent, INDENT), true);
+    }
+
+    /// Places the rest of the trivia at the end of the code
+    pub(crate) fn finish(&mut self, result: &mut String) {
+        self.place(result, usize::MAX, "", true);
+    }
+
+    fn place(&mut self, result: &mut String, position: usize, indent: &str, closing: bool) {
+        let end = self
+            .trivia
+            .iter()
+            .position(|(trivia_position, _)| *trivia_position >= position)
+            .unwrap_or(self.trivia.len());
+
+        let trivia = self
+            .trivia
+            .drain(..end)
+            .map(|(_, trivia)| trivia)
+            .collect::<Vec<_>>();
+
+        for (index, item) in trivia.iter().enumerate() {
+            match item {
+                // stays on the line where the previous token went
+                Trivia::Trailing(comment) if !result.trim_end().is_empty() => {
+                    let line_end = result.trim_end_matches('\n').len();
+                    result.insert_str(line_end, &format!(" {}", comment));
+                }
+                Trivia::Trailing(comment) | Trivia::Comment(comment) => {
+                    result.push_str(indent);
+                    result.push_str(comment);
+                    result.push('\n');
+                }
+                Trivia::EmptyLine => {
+                    // empty lines are only kept between things
+                    let is_followed = !closing
+                        || trivia[index + 1..]
+                            .iter()
+                            .any(|item| *item != Trivia::EmptyLine);
+
+                    let is_preceded = !result.is_empty()
+                        && !result.ends_with("\n\n")
+                        && !result.trim_end().ends_with(['{', '(']);
+
+                    if is_followed && is_preceded {
+                        result.push('\n');
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use super::{format_layout, format_logic};
+
+#[test]
+fn format_logic_simple() {
+    let code = r#"// variables
+number   counter
+string name
+
+// runs when the activity starts
+onCreate {
+    counter=((counter+1)) // increment
+
+
+    if counter>=2 { toast("hi") } else {
+      // nothing
+    }
+}
+button1.onClick{
+  name = counter.toString()
+
+}
+"#;
+
+    let formatted = format_logic(code).expect("failed to format code");
+
+    assert_eq!(
+        formatted,
+        r#"// variables
+number counter
+string name
+
+// runs when the activity starts
+onCreate {
+    counter = counter + 1 // increment
+
+    if counter >= 2 {
+        toast("hi")
+    } else {
+        // nothing
+    }
+}
+
+button1.onClick {
+    name = counter.toString()
+}
+"#
+    );
+
+    // formatting it again shouldn't change anything
+    assert_eq!(format_logic(&formatted).unwrap(), formatted);
+}
+
+#[test]
+fn format_layout_simple() {
+    let code = r##"
+// the root
+LinearLayout(orientation:vertical,padding:"8") {
+    // a greeting
+    TextView(text: "Hello world", text_size: "14", text_style: "bold|italic", text_color: "#ff0000"): text1,
+    Button (
+        text: "Click me"   // the button
+    ): button1
+}: root
+"##;
+
+    let formatted = format_layout(code).expect("failed to format code");
+
+    assert_eq!(
+        formatted,
+        r##"// the root
+LinearLayout (orientation: vertical, padding: "8") {
+    // a greeting
+    TextView (
+        text: "Hello world",
+        text_size: "14",
+        text_style: "bold|italic",
+        text_color: "#ff0000",
+    ): text1,
+    Button (
+        text: "Click me", // the button
+    ): button1,
+}: root
+"##
+    );
+
+    assert_eq!(format_layout(&formatted).unwrap(), formatted);
+}
+
+#[test]
+fn format_logic_nested_expression_comments() {
+    let code = r#"onCreate {
+    a = ((((((1)))))) // c1
+    b = 2 // c2
+}
+"#;
+
+    let formatted = format_logic(code).expect("failed to format code");
+
+    assert_eq!(
+        formatted,
+        r#"onCreate {
+    a = 1 // c1
+    b = 2 // c2
+}
+"#
+    );
+
+    assert_eq!(format_logic(&formatted).unwrap(), formatted);
+}
+
+#[test]
+fn format_layout_attribute_comments() {
+    let code = r##"LinearLayout(
+    // vertical
+    orientation: vertical,
+    padding: "8" // some padding
+) {
+    TextView(text: "Hello world", // first
+        text_size: "14" // second
+    ): text1
+}: root
+"##;
+
+    let formatted = format_layout(code).expect("failed to format code");
+
+    assert_eq!(
+        formatted,
+        r##"LinearLayout (
+    // vertical
+    orientation: vertical,
+    padding: "8", // some padding
+) {
+    TextView (
+        text: "Hello world", // first
+        text_size: "14", // second
+    ): text1,
+}: root
+"##
+    );
+
+    assert_eq!(format_layout(&formatted).unwrap(), formatted);
+}
@@ -1 +1,658 @@
+#[cfg(test)]
+mod tests;
 
+use crate::compiler::layout::parser::{Attribute, View};
+use crate::compiler::Span;
+use crate::formatter::Comments;
+use swrs::api::view::{SidesValue, View as SWRSView, ViewType};
+use swrs::color::Color;
+use swrs::parser::view::models::image::ImageScaleType;
+use swrs::parser::view::models::layout::gravity::Gravity;
+use swrs::parser::view::models::layout::{gravity, Orientation, Size};
+use swrs::parser::view::models::text::{ImeOption, InputType, TextType};
+use swrs::parser::view::models::SpinnerMode;
+use swrs::LinkedHashMap;
+use thiserror::Error;
+
+/// Generates a layout AST out of an swrs [`swrs::api::view::View`], this is the inverse of
+/// [`crate::compiler::layout::compile_view_tree`].
+///
+/// Attributes that are equal to the defaults used by the compiler are omitted, so compiling the
+/// generated AST back results in the same view. Attributes are placed in a fixed order
+/// (view-independent attributes first).
+pub fn generate_layout(view: &SWRSView) -> Result<View, LayoutGenerateError> {
+    let view_type = view
+        .view
+        .as_ref()
+        .map_err(|_| LayoutGenerateError::UnknownView {
+            view_id: view.id.clone(),
+        })?;
+
+    let mut attributes = Vec::new();
+
+    // view-independent attributes
+    if let Some(height) = size_to_string(&view.height) {
+        attributes.push(("height", height));
+    }
+
+    if let Some(width) = size_to_string(&view.width) {
+        attributes.push(("width", width));
+    }
+
+    if view.weight != 0 {
+        attributes.push(("weight", view.weight.to_string()));
+    }
+
+    if view.weight_sum != 0 {
+        attributes.push(("weight_sum", view.weight_sum.to_string()));
+    }
+
+    if u32::from(view.background_color) != 0xFFFFFF {
+        attributes.push(("background_color", color_to_string(view.background_color)));
+    }
+
+    if view.layout_gravity.0 != gravity::NONE {
+        attributes.push(("layout_gravity", gravity_to_string(&view.layout_gravity)));
+    }
+
+    sides_value_attributes(
+        &mut attributes,
+        &view.padding,
+        [
+            "padding",
+            "padding_top",
+            "padding_right",
+            "padding_bottom",
+            "padding_left",
+        ],
+        8,
+    );
+
+    sides_value_attributes(
+        &mut attributes,
+        &view.margin,
+        [
+            "margin",
+            "margin_top",
+            "margin_right",
+            "margin_bottom",
+            "margin_left",
+        ],
+        0,
+    );
+
+    // then view-specific attributes
+    let name = map_view_type_attrs(view_type, &mut attributes);
+
+    attributes.sort_by_key(|(name, _)| {
+        (
+            ATTRIBUTES_ORDER
+                .iter()
+                .position(|attr| attr == name)
+                .unwrap_or(ATTRIBUTES_ORDER.len()),
+            *name,
+        )
+    });
+
+    let children = view
+        .children
+        .iter()
+        .enumerate()
+        .map(|(index, child)| {
+            generate_layout(child).map_err(|e| LayoutGenerateError::ChildGenerateError {
+                index,
+                source: Box::new(e),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(View {
+        name: name.to_string(),
+        name_span: Span::default(),
+        attributes: if attributes.is_empty() {
+            None
+        } else {
+            Some(
+                attributes
+                    .into_iter()
+                    .map(|(name, value)| (name.to_string(), Attribute::new(value)))
+                    .collect::<LinkedHashMap<_, _>>(),
+            )
+        },
+        children: if children.is_empty() {
+            None
+        } else {
+            Some(Box::new(children))
+        },
+        view_id: Some(view.id.clone()),
+        view_id_span: Some(Span::default()),
+    })
+}
+
+/// Prints a layout AST into a prettified layout source code.
+///
+/// Attributes are printed in the order they're in, a view's attributes are placed on a single
+/// line when they fit, otherwise each of them is placed on their own line.
+pub fn print_layout(view: &View) -> String {
+    print_layout_with_comments(view, &mut Comments::default())
+}
+
+/// Prints a layout AST like [`print_layout`], placing back the comments of the code it was
+/// parsed from. Attributes are placed on their own lines when there are comments among them.
+pub(crate) fn print_layout_with_comments(view: &View, comments: &mut Comments) -> String {
+    let mut result = String::new();
+    comments.line(&mut result, view.name_span.start, "");
+    print_view(&mut result, view, 0, comments);
+    result.push('\n');
+    comments.finish(&mut result);
+    result
+}
+
+const INDENT: &str = "    ";
+const MAX_LINE_WIDTH: usize = 100;
+
+// the order of generated attributes, attributes that aren't in this list are placed afterwards in
+// alphabetical order
+const ATTRIBUTES_ORDER: &[&str] = &[
+    "height",
+    "width",
+    "weight",
+    "weight_sum",
+    "background_color",
+    "layout_gravity",
+    "padding",
+    "padding_top",
+    "padding_right",
+    "padding_bottom",
+    "padding_left",
+    "margin",
+    "margin_top",
+    "margin_right",
+    "margin_bottom",
+    "margin_left",
+    "orientation",
+    "gravity",
+    "checked",
+    "text",
+    "text_color",
+    "text_size",
+    "single_line",
+    "text_font",
+    "text_style",
+    "lines",
+    "hint",
+    "hint_color",
+    "ime_option",
+    "input_type",
+    "image",
+    "scale_type",
+    "max_progress",
+    "progress",
+    "indeterminate",
+    "progress_style",
+    "divider_height",
+    "custom_view",
+    "spinner_mode",
+    "first_day_of_the_week",
+    "adview_size",
+];
+
+// returns where the view ends on the source
+fn print_view(result: &mut String, view: &View, depth: usize, comments: &mut Comments) -> usize {
+    let indent = INDENT.repeat(depth);
+    let mut end = view.name_span.end;
+
+    result.push_str(&view.name);
+
+    if let Some(attributes) = &view.attributes {
+        let closing = comments.find(
+            ")",
+            attributes
+                .values()
+                .next_back()
+                .map(|attribute| attribute.value_span.end)
+                .unwrap_or(end),
+        );
+
+        let printed = attributes
+            .iter()
+            .map(|(name, attribute)| {
+                format!("{}: {}", print_value(name), print_value(&attribute.value))
+            })
+            .collect::<Vec<_>>();
+
+        let one_lined = format!(" ({})", printed.join(", "));
+
+        if indent.len() + view.name.len() + one_lined.len() <= MAX_LINE_WIDTH
+            && !comments.contains(end..closing)
+        {
+            result.push_str(&one_lined);
+        } else {
+            result.push_str(" (\n");
+
+            for (attribute, printed) in attributes.values().zip(printed) {
+                comments.line(
+                    result,
+                    attribute.key_span.start,
+                    &format!("{}{}", indent, INDENT),
+                );
+                result.push_str(&format!("{}{}{},\n", indent, INDENT, printed));
+            }
+
+            comments.closing(result, closing, &indent);
+            result.push_str(&indent);
+            result.push(')');
+        }
+
+        end = closing + 1;
+    }
+
+    if let Some(children) = &view.children {
+        result.push_str(" {\n");
+        end = comments.find("{", end) + 1;
+
+        for child in children.iter() {
+            comments.line(
+                result,
+                child.name_span.start,
+                &format!("{}{}", indent, INDENT),
+            );
+            result.push_str(&indent);
+            result.push_str(INDENT);
+            end = print_view(result, child, depth + 1, comments);
+            result.push_str(",\n");
+        }
+
+        end = comments.find("}", end);
+        comments.closing(result, end, &indent);
+        result.push_str(&indent);
+        result.push('}');
+        end += 1;
+    }
+
+    if let Some(view_id) = &view.view_id {
+        result.push_str(": ");
+        result.push_str(view_id);
+
+        if let Some(view_id_span) = &view.view_id_span {
+            end = view_id_span.end;
+        }
+    }
+
+    end
+}
+
+// values that are identifier-like can be written as-is, otherwise it needs to be wrapped in a
+// string
+fn print_value(value: &str) -> String {
+    let mut chars = value.chars();
+
+    if chars
+        .next()
+        .map(|ch| ch.is_ascii_alphabetic() || ch == '_')
+        .unwrap_or(false)
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+    {
+        value.to_string()
+    } else {
+        // strings are kept as-is, including their escapes
+        format!("\"{}\"", value)
+    }
+}
+
+/// This function is the inverse of `compiler::layout::map_view_name_attrs`, it returns the name
+/// of the view and pushes the view-specific attributes that aren't the default values.
+fn map_view_type_attrs(
+    view_type: &ViewType,
+    attributes: &mut Vec<(&'static str, String)>,
+) -> &'static str {
+    // pushes an attribute if it's not the same as the default value
+    macro_rules! attr {
+        ($name:expr, $value:expr, $default:expr) => {
+            if $value != $default {
+                attributes.push(($name, $value.to_string()));
+            }
+        };
+    }
+
+    macro_rules! attr_color {
+        ($name:expr, $value:expr, $default:expr) => {
+            if u32::from(*$value) != $default {
+                attributes.push(($name, color_to_string(*$value)));
+            }
+        };
+    }
+
+    macro_rules! attr_text_style {
+        ($value:expr) => {
+            if let Some(text_style) = text_style_to_string($value) {
+                attributes.push(("text_style", text_style));
+            }
+        };
+    }
+
+    match view_type {
+        ViewType::LinearLayout {
+            orientation,
+            gravity,
+        } => {
+            if let Orientation::Horizontal = orientation {
+                attributes.push(("orientation", "horizontal".to_string()));
+            }
+
+            if gravity.0 != gravity::NONE {
+                attributes.push(("gravity", gravity_to_string(gravity)));
+            }
+
+            "LinearLayout"
+        }
+        ViewType::ScrollView {
+            orientation,
+            gravity,
+        } => {
+            if let Orientation::Horizontal = orientation {
+                attributes.push(("orientation", "horizontal".to_string()));
+            }
+
+            if gravity.0 != gravity::NONE {
+                attributes.push(("gravity", gravity_to_string(gravity)));
+            }
+
+            "ScrollView"
+        }
+        ViewType::Button {
+            text,
+            text_color,
+            text_size,
+            text_style,
+        } => {
+            attr!("text", text.as_str(), "Button");
+            attr_color!("text_color", text_color, 0x000000);
+            attr!("text_size", *text_size, 12);
+            attr_text_style!(text_style);
+
+            "Button"
+        }
+        ViewType::TextView {
+            text,
+            text_color,
+            text_size,
+            single_line,
+            text_font,
+            text_style,
+            lines,
+        } => {
+            attr!("text", text.as_str(), "TextView");
+            attr_color!("text_color", text_color, 0x000000);
+            attr!("text_size", *text_size, 12);
+            attr!("single_line", *single_line, false);
+            attr!("text_font", text_font.as_str(), "default_font");
+            attr_text_style!(text_style);
+            attr!("lines", *lines, 0);
+
+            "TextView"
+        }
+        ViewType::EditText {
+            text,
+            text_color,
+            text_size,
+            single_line,
+            text_font,
+            text_style,
+            lines,
+            hint,
+            hint_color,
+            ime_option,
+            input_type,
+        } => {
+            attr!("text", text.as_str(), "EditText");
+            attr_color!("text_color", text_color, 0x000000);
+            attr!("text_size", *text_size, 12);
+            attr!("single_line", *single_line, false);
+            attr!("text_font", text_font.as_str(), "default_font");
+            attr_text_style!(text_style);
+            attr!("lines", *lines, 0);
+            attr!("hint", hint.as_str(), "");
+            attr_color!("hint_color", hint_color, 0x607d8b);
+
+            let ime_option = match ime_option {
+                ImeOption::Normal => None,
+                ImeOption::None => Some("none"),
+                ImeOption::Go => Some("go"),
+                ImeOption::Search => Some("search"),
+                ImeOption::Send => Some("send"),
+                ImeOption::Next => Some("next"),
+                ImeOption::Done => Some("done"),
+            };
+
+            if let Some(ime_option) = ime_option {
+                attributes.push(("ime_option", ime_option.to_string()));
+            }
+
+            let input_type = match input_type {
+                InputType::NumberDecimal => Some("decimal"),
+                InputType::NumberSigned => Some("signed"),
+                InputType::NumberSignedDecimal => Some("decimal_signed"),
+                InputType::Text => None,
+                InputType::Password => Some("password"),
+                InputType::Phone => Some("phone"),
+            };
+
+            if let Some(input_type) = input_type {
+                attributes.push(("input_type", input_type.to_string()));
+            }
+
+            "EditText"
+        }
+        ViewType::ImageView {
+            image_res_name,
+            image_scale_type,
+        } => {
+            attr!("image", image_res_name.as_str(), "");
+
+            let scale_type = match image_scale_type {
+                ImageScaleType::Center => None,
+                ImageScaleType::FitXy => Some("fit_xy"),
+                ImageScaleType::FitStart => Some("fit_start"),
+                ImageScaleType::FitEnd => Some("fit_end"),
+                ImageScaleType::CenterCrop => Some("center_crop"),
+                ImageScaleType::CenterInside => Some("center_inside"),
+            };
+
+            if let Some(scale_type) = scale_type {
+                attributes.push(("scale_type", scale_type.to_string()));
+            }
+
+            "ImageView"
+        }
+        ViewType::WebView => "WebView",
+        ViewType::ProgressBar {
+            max_progress,
+            progress,
+            indeterminate,
+            progress_style,
+        } => {
+            attr!("max_progress", *max_progress, 100);
+            attr!("progress", *progress, 0);
+            attr!("indeterminate", *indeterminate, false);
+
+            // other progress styles aren't supported by the compiler, they fall back to the
+            // default circular style
+            if progress_style == "?android:progressBarStyleHorizontal" {
+                attributes.push(("progress_style", "horizontal".to_string()));
+            }
+
+            "ProgressBar"
+        }
+        ViewType::ListView {
+            divider_height,
+            custom_view,
+        } => {
+            attr!("divider_height", *divider_height, 0);
+            attr!("custom_view", custom_view.as_str(), "");
+
+            "ListView"
+        }
+        ViewType::Spinner { spinner_mode } => {
+            if let SpinnerMode::Dialog = spinner_mode {
+                attributes.push(("spinner_mode", "dialog".to_string()));
+            }
+
+            "Spinner"
+        }
+        ViewType::CheckBox {
+            checked,
+            text,
+            text_color,
+            text_size,
+            text_font,
+            text_style,
+        } => {
+            attr!("checked", *checked, false);
+            attr!("text", text.as_str(), "CheckBox");
+            attr_color!("text_color", text_color, 0x000000);
+            attr!("text_size", *text_size, 12);
+            attr!("text_font", text_font.as_str(), "default_font");
+            attr_text_style!(text_style);
+
+            "CheckBox"
+        }
+        ViewType::Switch {
+            checked,
+            text,
+            text_color,
+            text_size,
+            text_font,
+            text_style,
+        } => {
+            attr!("checked", *checked, false);
+            attr!("text", text.as_str(), "Switch");
+            attr_color!("text_color", text_color, 0x000000);
+            attr!("text_size", *text_size, 12);
+            attr!("text_font", text_font.as_str(), "default_font");
+            attr_text_style!(text_style);
+
+            "Switch"
+        }
+        ViewType::SeekBar {
+            max_progress,
+            progress,
+        } => {
+            attr!("max_progress", *max_progress, 100);
+            attr!("progress", *progress, 0);
+
+            "SeekBar"
+        }
+        ViewType::CalendarView { first_day_of_week } => {
+            attr!("first_day_of_the_week", *first_day_of_week, 1);
+
+            "CalendarView"
+        }
+        ViewType::Fab { image_res_name } => {
+            attr!("image", image_res_name.as_str(), "");
+
+            "FloatingActionButton"
+        }
+        ViewType::AdView { adview_size } => {
+            attr!("adview_size", adview_size.as_str(), "");
+
+            "AdView"
+        }
+        ViewType::MapView => "MapView",
+    }
+}
+
+// pushes `names[0]` if all sides are the same, otherwise pushes each side that isn't the default
+// value with `names[1..]` in the order of top, right, bottom, left
+fn sides_value_attributes(
+    attributes: &mut Vec<(&'static str, String)>,
+    value: &SidesValue,
+    names: [&'static str; 5],
+    default: u32,
+) {
+    if value.top == value.right && value.top == value.bottom && value.top == value.left {
+        if value.top != default {
+            attributes.push((names[0], value.top.to_string()));
+        }
+
+        return;
+    }
+
+    let sides = [value.top, value.right, value.bottom, value.left];
+
+    for (name, side) in names[1..].iter().zip(sides) {
+        if side != default {
+            attributes.push((name, side.to_string()));
+        }
+    }
+}
+
+fn size_to_string(size: &Size) -> Option<String> {
+    match size {
+        Size::MatchParent => Some("match_parent".to_string()),
+        Size::WrapContent => None,
+        Size::Fixed(size) => Some(size.to_string()),
+    }
+}
+
+// colors with no alpha are printed as `#rrggbb`, otherwise `#aarrggbb`
+fn color_to_string(color: Color) -> String {
+    let color = u32::from(color);
+
+    if color <= 0xFFFFFF {
+        format!("#{:06x}", color)
+    } else {
+        format!("#{:08x}", color)
+    }
+}
+
+// the inverse of `compiler::layout::attr_parser::parse_gravity`
+fn gravity_to_string(value: &Gravity) -> String {
+    let horizontal = value.0 & (gravity::LEFT | gravity::RIGHT);
+    let vertical = value.0 & (gravity::TOP | gravity::BOTTOM);
+
+    if horizontal == gravity::CENTER_HORIZONTAL && vertical == gravity::CENTER_VERTICAL {
+        return "center".to_string();
+    }
+
+    let mut values = Vec::new();
+
+    if horizontal == gravity::CENTER_HORIZONTAL {
+        values.push("center_horizontal");
+    } else if horizontal == gravity::LEFT {
+        values.push("left");
+    } else if horizontal == gravity::RIGHT {
+        values.push("right");
+    }
+
+    if vertical == gravity::CENTER_VERTICAL {
+        values.push("center_vertical");
+    } else if vertical == gravity::TOP {
+        values.push("top");
+    } else if vertical == gravity::BOTTOM {
+        values.push("bottom");
+    }
+
+    values.join("|")
+}
+
+fn text_style_to_string(text_style: &TextType) -> Option<String> {
+    match text_style {
+        TextType::Normal => None,
+        TextType::Bold => Some("bold".to_string()),
+        TextType::Italic => Some("italic".to_string()),
+        TextType::BoldItalic => Some("bold|italic".to_string()),
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LayoutGenerateError {
+    #[error("the view `{view_id}` has an unknown view type")]
+    UnknownView { view_id: String },
+
+    #[error("error on generating a child at index {index}")]
+    ChildGenerateError {
+        index: usize,
+        source: Box<LayoutGenerateError>,
+    },
+}
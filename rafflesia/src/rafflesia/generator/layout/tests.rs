@@ -0,0 +1,57 @@
+use super::{generate_layout, print_layout};
+use crate::compiler::layout::compile_view_tree;
+use crate::compiler::layout::parser::parse_layout;
+
+const LAYOUT: &str = r##"
+LinearLayout (
+    height: match_parent,
+    width: match_parent,
+    padding: "16",
+    orientation: horizontal,
+    gravity: center,
+) {
+    TextView (text: "Hello world", text_size: "14", text_style: "bold|italic"): text1,
+    Button (layout_gravity: "right|bottom", margin_top: "4", text_color: "#ff0000"): button1,
+    EditText (hint: "Type here", input_type: password): edittext1,
+    ListView: listview1,
+}: linear1
+"##;
+
+#[test]
+fn generate_simple() {
+    let parsed = parse_layout(LAYOUT).unwrap();
+    let compiled = compile_view_tree(parse_layout(LAYOUT).unwrap()).unwrap();
+
+    // generated views don't have spans, compare them by their printed form
+    assert_eq!(
+        print_layout(&generate_layout(&compiled).unwrap()),
+        print_layout(&parsed)
+    );
+}
+
+#[test]
+fn print_simple() {
+    let compiled = compile_view_tree(parse_layout(LAYOUT).unwrap()).unwrap();
+    let generated = generate_layout(&compiled).unwrap();
+    let printed = print_layout(&generated);
+
+    assert_eq!(
+        printed,
+        r##"LinearLayout (
+    height: match_parent,
+    width: match_parent,
+    padding: "16",
+    orientation: horizontal,
+    gravity: center,
+) {
+    TextView (text: "Hello world", text_size: "14", text_style: "bold|italic"): text1,
+    Button (layout_gravity: "right|bottom", margin_top: "4", text_color: "#ff0000"): button1,
+    EditText (hint: "Type here", input_type: password): edittext1,
+    ListView: listview1,
+}: linear1
+"##
+    );
+
+    // it should be parsed back into the same thing
+    assert_eq!(print_layout(&parse_layout(&printed).unwrap()), printed);
+}
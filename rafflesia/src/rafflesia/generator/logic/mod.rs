@@ -1 +1,1363 @@
+#[cfg(test)]
+mod tests;
 
+use crate::compiler::logic::ast::{
+    Arguments, BinaryOperator, ComplexVariableType, ComponentArgument, Expression,
+    ForeverStatement, IfStatement, IndexAssignment, InnerStatement, InnerStatements, Literal,
+    OuterStatement, OuterStatements, Parameter, ParameterType, PrimaryExpression, RawBlock,
+    RepeatStatement, UnaryOperator, VariableAssignment, VariableType,
+};
+use crate::compiler::logic::LogicCompileResult;
+use crate::compiler::Span;
+use crate::formatter::Comments;
+use std::collections::{HashMap, HashSet};
+use swrs::api::block::{
+    ArgValue, Argument, ArgumentBlockReturnType, Block, BlockControl, BlockType, Blocks, ListItem,
+};
+use swrs::api::component::ComponentKind;
+use swrs::api::screen::{EventType, MoreBlock};
+use swrs::parser::logic::variable::VariableType as SWRSVariableType;
+use thiserror::Error;
+
+// the names of maps and map parameters that store numbers
+type NumberMaps = HashSet<String>;
+
+// what the code of a screen is generated with
+struct Context {
+    number_maps: NumberMaps,
+    // the moreblocks that are skipped, calls to them are kept as raw blocks
+    skipped_more_blocks: HashSet<String>,
+}
+
+/// The logic AST generated by [`generate_logic`].
+#[derive(Debug)]
+pub struct GeneratedLogic {
+    pub statements: OuterStatements,
+    /// Variables, components, moreblocks and events that can't be written in rafflesia, they're
+    /// left out of the statements
+    pub skipped: Vec<LogicGenerateError>,
+}
+
+/// Generates a logic AST out of the variables and events of a screen, this is the inverse of
+/// [`crate::compiler::logic::compile_logic`].
+///
+/// Anything that can't be generated is skipped rather than failing the whole screen, the reasons
+/// are returned in [`GeneratedLogic::skipped`] so they can be reported.
+pub fn generate_logic(logic: &LogicCompileResult) -> GeneratedLogic {
+    let mut statements = Vec::new();
+    let mut skipped = Vec::new();
+
+    let number_maps = infer_number_maps(logic);
+
+    for (name, variable) in logic.variables.iter() {
+        statements.push(match variable.r#type {
+            SWRSVariableType::Boolean => OuterStatement::SimpleVariableDeclaration {
+                variable_type: VariableType::Boolean,
+                identifier: name.clone(),
+                span: Span::default(),
+            },
+            SWRSVariableType::Integer => OuterStatement::SimpleVariableDeclaration {
+                variable_type: VariableType::Number,
+                identifier: name.clone(),
+                span: Span::default(),
+            },
+            SWRSVariableType::String => OuterStatement::SimpleVariableDeclaration {
+                variable_type: VariableType::String,
+                identifier: name.clone(),
+                span: Span::default(),
+            },
+            SWRSVariableType::HashMap => OuterStatement::ComplexVariableDeclaration {
+                variable_type: ComplexVariableType::Map {
+                    inner_type: map_inner_type(name, &number_maps),
+                },
+                identifier: name.clone(),
+                span: Span::default(),
+            },
+        });
+    }
+
+    for (name, list_variable) in logic.list_variables.iter() {
+        statements.push(OuterStatement::ComplexVariableDeclaration {
+            variable_type: ComplexVariableType::List {
+                inner_type: match list_variable.r#type {
+                    SWRSVariableType::Integer => VariableType::Number,
+                    SWRSVariableType::String => VariableType::String,
+                    _ => {
+                        skipped.push(LogicGenerateError::UnsupportedListVariable {
+                            name: name.clone(),
+                        });
+                        continue;
+                    }
+                },
+            },
+            identifier: name.clone(),
+            span: Span::default(),
+        });
+    }
+
+    for (name, component) in logic.components.iter() {
+        let (component_type, arguments) = match component {
+            ComponentKind::Intent => ("intent", vec![]),
+            ComponentKind::SharedPreferences { file_name } => {
+                ("sharedpreferences", vec![("file", file_name)])
+            }
+            ComponentKind::Timer => ("timer", vec![]),
+            ComponentKind::Dialog => ("dialog", vec![]),
+            ComponentKind::Calendar => ("calendar", vec![]),
+            ComponentKind::Vibrator => ("vibrator", vec![]),
+            ComponentKind::RequestNetwork => ("requestnetwork", vec![]),
+            ComponentKind::FirebaseDB { path } => ("firebasedb", vec![("path", path)]),
+            _ => {
+                skipped.push(LogicGenerateError::UnsupportedComponent { name: name.clone() });
+                continue;
+            }
+        };
+
+        statements.push(OuterStatement::ComponentDeclaration {
+            component_type: component_type.to_string(),
+            identifier: name.clone(),
+            arguments: arguments
+                .into_iter()
+                .map(|(name, value)| ComponentArgument {
+                    name: name.to_string(),
+                    value: value.clone(),
+                    span: Span::default(),
+                })
+                .collect(),
+            span: Span::default(),
+        });
+    }
+
+    let context = Context {
+        skipped_more_blocks: logic
+            .more_blocks
+            .values()
+            .filter(|more_block| generate_parameters(more_block, &number_maps).is_err())
+            .map(|more_block| more_block.id.clone())
+            .collect(),
+        number_maps,
+    };
+
+    for more_block in logic.more_blocks.values() {
+        let generated =
+            generate_parameters(more_block, &context.number_maps).and_then(|parameters| {
+                Ok(OuterStatement::MoreBlockDeclaration {
+                    name: more_block.id.clone(),
+                    parameters,
+                    body: generate_inner_statements(&more_block.code, &context)?,
+                    span: Span::default(),
+                })
+            });
+
+        match generated {
+            Ok(statement) => statements.push(statement),
+            Err(err) => skipped.push(err),
+        }
+    }
+
+    for event in logic.events.iter() {
+        let body = match generate_inner_statements(&event.code, &context) {
+            Ok(body) => body,
+            Err(err) => {
+                skipped.push(err);
+                continue;
+            }
+        };
+
+        statements.push(match &event.event_type {
+            EventType::ActivityEvent => OuterStatement::ActivityEventListener {
+                event_name: event.name.clone(),
+                body,
+                span: Span::default(),
+            },
+            // component listeners are written the same way as view listeners
+            EventType::ViewEvent { id } | EventType::ComponentEvent { id, .. } => {
+                OuterStatement::ViewEventListener {
+                    view_id: id.clone(),
+                    event_name: event.name.clone(),
+                    body,
+                    span: Span::default(),
+                }
+            }
+            _ => {
+                skipped.push(LogicGenerateError::UnsupportedEvent {
+                    event_name: event.name.clone(),
+                });
+                continue;
+            }
+        });
+    }
+
+    GeneratedLogic {
+        statements: OuterStatements(statements),
+        skipped,
+    }
+}
+
+// retrieves the parameters of a moreblock from the arguments of its spec, e.g. `%s.name` or
+// `%m.listInt.items`
+fn generate_parameters(
+    more_block: &MoreBlock,
+    number_maps: &NumberMaps,
+) -> Result<Vec<Parameter>, LogicGenerateError> {
+    more_block
+        .spec
+        .get_args()
+        .into_iter()
+        .map(|arg| {
+            let unsupported = || LogicGenerateError::UnsupportedMoreBlockParameter {
+                more_block: more_block.id.clone(),
+            };
+
+            let (name, parameter_type) = match arg {
+                Argument::Number { name, .. } => {
+                    (name, ParameterType::Simple(VariableType::Number))
+                }
+                Argument::String { name, .. } => {
+                    (name, ParameterType::Simple(VariableType::String))
+                }
+                Argument::Boolean { name, .. } => {
+                    (name, ParameterType::Simple(VariableType::Boolean))
+                }
+                Argument::Menu { name, .. } => {
+                    let (kind, name) = name.split_once('.').ok_or_else(unsupported)?;
+
+                    let parameter_type = match kind {
+                        "listInt" => ParameterType::Complex(ComplexVariableType::List {
+                            inner_type: VariableType::Number,
+                        }),
+                        "listStr" => ParameterType::Complex(ComplexVariableType::List {
+                            inner_type: VariableType::String,
+                        }),
+                        "varMap" => ParameterType::Complex(ComplexVariableType::Map {
+                            inner_type: map_inner_type(name, number_maps),
+                        }),
+                        "view" => ParameterType::View,
+                        _ => return Err(unsupported()),
+                    };
+
+                    return Ok(Parameter {
+                        name: name.to_string(),
+                        parameter_type,
+                        span: Span::default(),
+                    });
+                }
+            };
+
+            Ok(Parameter {
+                name: name.clone().ok_or_else(unsupported)?,
+                parameter_type,
+                span: Span::default(),
+            })
+        })
+        .collect()
+}
+
+// sketchware's maps are untyped, a map is a `map<number>` when every value put into it is converted
+// from a number and every value taken out of it is converted into a number, the way
+// `map<number>`s are compiled. any other map is a `map<string>`
+fn infer_number_maps(logic: &LogicCompileResult) -> NumberMaps {
+    // whether each map is used as a number map, or used as a string map at least once
+    let mut uses = HashMap::new();
+
+    let code = logic.events.iter().map(|event| &event.code).chain(
+        logic
+            .more_blocks
+            .values()
+            .map(|more_block| &more_block.code),
+    );
+
+    for blocks in code {
+        for block in &blocks.0 {
+            find_map_uses(block, None, &mut uses);
+        }
+    }
+
+    uses.into_iter()
+        .filter(|(_, as_number)| *as_number)
+        .map(|(name, _)| name)
+        .collect()
+}
+
+fn find_map_uses(block: &Block, parent: Option<&Block>, uses: &mut HashMap<String, bool>) {
+    let args = block.content.get_args();
+
+    let as_number = match block.op_code.as_str() {
+        "mapGet" => Some(matches!(parent, Some(parent) if parent.op_code == "toNumber")),
+        "mapPut" => Some(matches!(
+            args.get(2),
+            Some(Argument::String {
+                value: ArgValue::Block(value),
+                ..
+            }) if value.op_code == "toStringWithDecimal"
+        )),
+        _ => None,
+    };
+
+    if let (Some(as_number), Some(name)) = (as_number, args.first().and_then(|arg| map_name(arg))) {
+        *uses.entry(name).or_insert(true) &= as_number;
+    }
+
+    for arg in args {
+        if let Argument::String {
+            value: ArgValue::Block(inner),
+            ..
+        }
+        | Argument::Number {
+            value: ArgValue::Block(inner),
+            ..
+        }
+        | Argument::Boolean {
+            value: ArgValue::Block(inner),
+            ..
+        }
+        | Argument::Menu {
+            value: ArgValue::Block(inner),
+            ..
+        } = arg
+        {
+            find_map_uses(inner, Some(block), uses);
+        }
+    }
+
+    for sub_stack in [&block.sub_stack1, &block.sub_stack2].into_iter().flatten() {
+        for inner in &sub_stack.0 {
+            find_map_uses(inner, None, uses);
+        }
+    }
+}
+
+// the name of the map a map block works on, it's either a map variable or a map parameter
+fn map_name(arg: &Argument) -> Option<String> {
+    match arg {
+        Argument::Menu {
+            value: ArgValue::Value(name),
+            ..
+        } => Some(name.clone()),
+        Argument::Menu {
+            value: ArgValue::Block(block),
+            ..
+        } if block.op_code == "getArg" => Some(block.content.to_string()),
+        _ => None,
+    }
+}
+
+fn map_inner_type(name: &str, number_maps: &NumberMaps) -> VariableType {
+    if number_maps.contains(name) {
+        VariableType::Number
+    } else {
+        VariableType::String
+    }
+}
+
+// whether the first argument of a map block is a number map
+fn is_number_map(block: &Block, number_maps: &NumberMaps) -> bool {
+    block
+        .content
+        .get_args()
+        .first()
+        .and_then(|arg| map_name(arg))
+        .is_some_and(|name| number_maps.contains(&name))
+}
+
+// the mapGet block inside a toNumber block, when it gets a value of a number map
+fn number_map_get<'a>(block: &'a Block, number_maps: &NumberMaps) -> Option<&'a Block> {
+    match block.content.get_args().first() {
+        Some(Argument::String {
+            value: ArgValue::Block(get),
+            ..
+        }) if block.op_code == "toNumber"
+            && get.op_code == "mapGet"
+            && is_number_map(get, number_maps) =>
+        {
+            Some(get)
+        }
+        _ => None,
+    }
+}
+
+fn generate_inner_statements(
+    blocks: &Blocks,
+    context: &Context,
+) -> Result<InnerStatements, LogicGenerateError> {
+    Ok(InnerStatements(
+        blocks
+            .0
+            .iter()
+            .map(|block| generate_inner_statement(block, context))
+            .collect::<Result<_, _>>()?,
+    ))
+}
+
+// a block with a known opcode whose arguments don't fit its syntax (like a toast without its text,
+// or a setVar with a block as its variable) is kept as a raw block, so it isn't lost
+fn generate_inner_statement(
+    block: &Block,
+    context: &Context,
+) -> Result<InnerStatement, LogicGenerateError> {
+    match try_generate_inner_statement(block, context) {
+        Err(LogicGenerateError::InvalidBlockArguments { .. }) => Ok(InnerStatement::RawBlock(
+            generate_raw_block(block, context)?,
+        )),
+        result => result,
+    }
+}
+
+fn try_generate_inner_statement(
+    block: &Block,
+    context: &Context,
+) -> Result<InnerStatement, LogicGenerateError> {
+    let args = block.content.get_args();
+
+    // retrieves the argument at the given index, or returns an error if it doesn't exist
+    macro_rules! arg {
+        ($index:expr) => {
+            args.get($index)
+                .ok_or_else(|| LogicGenerateError::InvalidBlockArguments {
+                    op_code: block.op_code.clone(),
+                })?
+        };
+    }
+
+    Ok(match block.op_code.as_str() {
+        "if" => InnerStatement::IfStatement(IfStatement {
+            condition: generate_expression(arg!(0), context)?,
+            body: generate_sub_stack(&block.sub_stack1, context)?,
+            else_body: None,
+            span: Span::default(),
+        }),
+        "ifElse" => InnerStatement::IfStatement(IfStatement {
+            condition: generate_expression(arg!(0), context)?,
+            body: generate_sub_stack(&block.sub_stack1, context)?,
+            else_body: Some(generate_sub_stack(&block.sub_stack2, context)?),
+            span: Span::default(),
+        }),
+        "repeat" => InnerStatement::RepeatStatement(RepeatStatement {
+            condition: generate_expression(arg!(0), context)?,
+            body: generate_sub_stack(&block.sub_stack1, context)?,
+            span: Span::default(),
+        }),
+        "forever" => InnerStatement::ForeverStatement(ForeverStatement {
+            body: generate_sub_stack(&block.sub_stack1, context)?,
+            span: Span::default(),
+        }),
+        "break" => InnerStatement::Break {
+            span: Span::default(),
+        },
+        "continue" => InnerStatement::Continue {
+            span: Span::default(),
+        },
+        "setVarInt" | "setVarBoolean" | "setVarString" => {
+            InnerStatement::VariableAssignment(VariableAssignment {
+                identifier: menu_value(arg!(0), &block.op_code)?,
+                value: generate_expression(arg!(1), context)?,
+                span: Span::default(),
+            })
+        }
+        "doToast" => {
+            InnerStatement::Expression(Expression::PrimaryExpression(PrimaryExpression::Call {
+                from: None,
+                name: "toast".to_string(),
+                arguments: Arguments(vec![generate_expression(arg!(0), context)?]),
+                span: Span::default(),
+            }))
+        }
+        // the first word of the spec of a moreblock call is the moreblock's name
+        "definedFunc" => {
+            let name = block
+                .content
+                .to_string()
+                .split_whitespace()
+                .next()
+                .ok_or_else(|| LogicGenerateError::InvalidBlockArguments {
+                    op_code: block.op_code.clone(),
+                })?
+                .to_string();
+
+            // a skipped moreblock doesn't exist on the generated code, it can't be called by name
+            if context.skipped_more_blocks.contains(&name) {
+                return Ok(InnerStatement::RawBlock(generate_raw_block(
+                    block, context,
+                )?));
+            }
+
+            InnerStatement::Expression(Expression::PrimaryExpression(PrimaryExpression::Call {
+                from: None,
+                name,
+                arguments: Arguments(
+                    args.iter()
+                        .map(|arg| generate_expression(arg, context))
+                        .collect::<Result<_, _>>()?,
+                ),
+                span: Span::default(),
+            }))
+        }
+        op_code => match method_name(op_code) {
+            Some(method) => {
+                InnerStatement::Expression(generate_method_call(block, method, context)?)
+            }
+            None => InnerStatement::RawBlock(generate_raw_block(block, context)?),
+        },
+    })
+}
+
+// the method names of component, map and list blocks, the inverse of their type data
+fn method_name(op_code: &str) -> Option<&'static str> {
+    Some(match op_code {
+        "intentSetAction" => "setAction",
+        "intentSetData" => "setData",
+        "intentSetScreen" => "setScreen",
+        "intentPutExtra" => "putExtra",
+        "startActivity" => "start",
+        "fileGetData" => "get",
+        "fileSetData" => "set",
+        "fileRemoveData" => "remove",
+        "firebaseDelete" => "remove",
+        "timerCancel" => "cancel",
+        "dialogSetTitle" => "setTitle",
+        "dialogSetMessage" => "setMessage",
+        "dialogShow" => "show",
+        "dialogDismiss" => "dismiss",
+        "calendarGetNow" => "now",
+        "calendarAdd" => "add",
+        "calendarSet" => "set",
+        "calendarFormat" => "format",
+        "calendarGetTime" => "getTime",
+        "calendarSetTime" => "setTime",
+        "vibratorAction" => "vibrate",
+        "requestnetworkStartRequestNetwork" => "start",
+        "mapGet" => "get",
+        "mapPut" => "put",
+        "mapContainKey" => "containsKey",
+        "mapRemoveKey" => "remove",
+        "mapSize" => "size",
+        "mapClear" => "clear",
+        "mapGetAllKeys" => "keys",
+        "addListInt" | "addListStr" => "add",
+        "insertListInt" | "insertListStr" => "insert",
+        "getAtListInt" | "getAtListStr" => "get",
+        "setListInt" | "setListStr" => "set",
+        "deleteList" => "remove",
+        "lengthList" => "length",
+        "containListInt" | "containListStr" => "contains",
+        "indexListInt" | "indexListStr" => "indexOf",
+        "clearList" => "clear",
+        _ => return None,
+    })
+}
+
+// most list blocks don't start with the list, like `add %d to %m.listInt`. these are the positions
+// of the list and then the method arguments on the block
+fn argument_order(op_code: &str) -> Option<&'static [usize]> {
+    Some(match op_code {
+        "addListInt" | "addListStr" | "getAtListInt" | "getAtListStr" | "indexListInt"
+        | "indexListStr" | "deleteList" => &[1, 0],
+        "insertListInt" | "insertListStr" | "setListInt" | "setListStr" => &[2, 1, 0],
+        _ => return None,
+    })
+}
+
+// the menus that are values rather than references to a variable, written as strings
+const VALUE_MENUS: &[&str] = &["intentAction", "activity", "calendarField", "method"];
+
+// the component, the map or the list is the first argument of the block unless its argument order
+// says otherwise, value menus on the rest of the arguments are written as strings, like the action
+// of `intent.setAction("android.intent.action.VIEW")`
+fn generate_method_call(
+    block: &Block,
+    method: &str,
+    context: &Context,
+) -> Result<Expression, LogicGenerateError> {
+    let args = block.content.get_args();
+    let invalid_arguments = || LogicGenerateError::InvalidBlockArguments {
+        op_code: block.op_code.clone(),
+    };
+
+    let args = match argument_order(&block.op_code) {
+        Some(order) => order
+            .iter()
+            .map(|index| args.get(*index).copied().ok_or_else(invalid_arguments))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => args,
+    };
+
+    let (from, arguments) = args.split_first().ok_or_else(invalid_arguments)?;
+
+    // the values put into number maps are converted from numbers
+    let unwrap_value = block.op_code == "mapPut" && is_number_map(block, &context.number_maps);
+
+    let arguments = arguments
+        .iter()
+        .enumerate()
+        .map(|(index, arg)| match arg {
+            Argument::String {
+                value: ArgValue::Block(value),
+                ..
+            } if unwrap_value && index == 1 && value.op_code == "toStringWithDecimal" => {
+                generate_expression(
+                    value
+                        .content
+                        .get_args()
+                        .first()
+                        .ok_or_else(invalid_arguments)?,
+                    context,
+                )
+            }
+            Argument::Menu {
+                name,
+                value: ArgValue::Value(value),
+            } if VALUE_MENUS.contains(&name.as_str()) => Ok(Expression::Literal {
+                value: Literal::String(value.clone()),
+                span: Span::default(),
+            }),
+            arg => generate_expression(arg, context),
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Expression::PrimaryExpression(PrimaryExpression::Call {
+        from: Some(Box::new(generate_expression(from, context)?)),
+        name: method.to_string(),
+        arguments: Arguments(arguments),
+        span: Span::default(),
+    }))
+}
+
+// an empty substack is stored as None
+fn generate_sub_stack(
+    blocks: &Option<Blocks>,
+    context: &Context,
+) -> Result<InnerStatements, LogicGenerateError> {
+    blocks
+        .as_ref()
+        .map(|blocks| generate_inner_statements(blocks, context))
+        .unwrap_or_else(|| Ok(InnerStatements(vec![])))
+}
+
+// generates an expression out of a block argument
+fn generate_expression(
+    arg: &Argument,
+    context: &Context,
+) -> Result<Expression, LogicGenerateError> {
+    Ok(match arg {
+        Argument::String { value, .. } => match value {
+            ArgValue::Value(value) => Expression::Literal {
+                value: Literal::String(value.clone()),
+                span: Span::default(),
+            },
+            ArgValue::Block(block) => generate_block_expression(block, context)?,
+        },
+        Argument::Number { value, .. } => match value {
+            ArgValue::Value(value) => Expression::Literal {
+                value: Literal::Number(*value),
+                span: Span::default(),
+            },
+            ArgValue::Block(block) => generate_block_expression(block, context)?,
+        },
+        Argument::Boolean { value, .. } => match value {
+            ArgValue::Value(value) => Expression::Literal {
+                value: Literal::Boolean(*value),
+                span: Span::default(),
+            },
+            ArgValue::Block(block) => generate_block_expression(block, context)?,
+        },
+        // menus are references to something, like variables
+        Argument::Menu { value, .. } => match value {
+            ArgValue::Value(value) => {
+                Expression::PrimaryExpression(PrimaryExpression::VariableAccess {
+                    from: None,
+                    name: value.clone(),
+                    span: Span::default(),
+                })
+            }
+            ArgValue::Block(block) => generate_block_expression(block, context)?,
+        },
+    })
+}
+
+// generates an expression out of an argument block, falls back to a raw block the same way as
+// `generate_inner_statement`
+fn generate_block_expression(
+    block: &Block,
+    context: &Context,
+) -> Result<Expression, LogicGenerateError> {
+    match try_generate_block_expression(block, context) {
+        Err(LogicGenerateError::InvalidBlockArguments { .. }) => Ok(Expression::PrimaryExpression(
+            PrimaryExpression::RawBlock(generate_raw_block(block, context)?),
+        )),
+        result => result,
+    }
+}
+
+fn try_generate_block_expression(
+    block: &Block,
+    context: &Context,
+) -> Result<Expression, LogicGenerateError> {
+    let args = block.content.get_args();
+
+    macro_rules! arg {
+        ($index:expr) => {
+            generate_expression(
+                args.get($index)
+                    .ok_or_else(|| LogicGenerateError::InvalidBlockArguments {
+                        op_code: block.op_code.clone(),
+                    })?,
+                context,
+            )?
+        };
+    }
+
+    macro_rules! binop {
+        ($operator:ident) => {
+            Expression::BinOp {
+                first: Box::new(arg!(0)),
+                operator: BinaryOperator::$operator,
+                second: Box::new(arg!(1)),
+                span: Span::default(),
+            }
+        };
+    }
+
+    // the values of number maps are converted into numbers when they're taken out
+    if let Some(get) = number_map_get(block, &context.number_maps) {
+        return generate_method_call(get, "get", context);
+    }
+
+    Ok(match block.op_code.as_str() {
+        "||" => {
+            let first = arg!(0);
+            let second = arg!(1);
+
+            // `a < b || a == b` is how `a <= b` gets compiled, same goes to `a >= b`
+            match (first, second) {
+                (
+                    Expression::BinOp {
+                        first: lt_first,
+                        operator: operator @ (BinaryOperator::LT | BinaryOperator::GT),
+                        second: lt_second,
+                        ..
+                    },
+                    Expression::BinOp {
+                        first: eq_first,
+                        operator: BinaryOperator::EQ,
+                        second: eq_second,
+                        ..
+                    },
+                ) if lt_first == eq_first && lt_second == eq_second => Expression::BinOp {
+                    first: lt_first,
+                    operator: if operator == BinaryOperator::LT {
+                        BinaryOperator::LTE
+                    } else {
+                        BinaryOperator::GTE
+                    },
+                    second: lt_second,
+                    span: Span::default(),
+                },
+
+                (first, second) => Expression::BinOp {
+                    first: Box::new(first),
+                    operator: BinaryOperator::Or,
+                    second: Box::new(second),
+                    span: Span::default(),
+                },
+            }
+        }
+        "&&" => binop!(And),
+        "<" => binop!(LT),
+        ">" => binop!(GT),
+        "=" => binop!(EQ),
+        "+" => binop!(Plus),
+        "-" => binop!(Minus),
+        "/" => binop!(Divide),
+        "*" => match (args.first(), args.get(1)) {
+            // `-a` gets compiled into `a * -1`, except when `a` is a number since `-1` is
+            // compiled into a negative number
+            (
+                Some(Argument::Number {
+                    value: ArgValue::Block(_),
+                    ..
+                }),
+                Some(Argument::Number {
+                    value: ArgValue::Value(value),
+                    ..
+                }),
+            ) if *value == -1f64 => Expression::UnaryOp {
+                value: Box::new(arg!(0)),
+                operator: UnaryOperator::Minus,
+                span: Span::default(),
+            },
+            _ => binop!(Multiply),
+        },
+        "not" => Expression::UnaryOp {
+            value: Box::new(arg!(0)),
+            operator: UnaryOperator::Not,
+            span: Span::default(),
+        },
+        // the spec of getVar and getArg is the variable or parameter name itself
+        "getVar" | "getArg" => Expression::PrimaryExpression(PrimaryExpression::VariableAccess {
+            from: None,
+            name: block.content.to_string(),
+            span: Span::default(),
+        }),
+        "toString" => Expression::PrimaryExpression(PrimaryExpression::Call {
+            from: Some(Box::new(arg!(0))),
+            name: "toString".to_string(),
+            arguments: Arguments(vec![]),
+            span: Span::default(),
+        }),
+        "toStringWithDecimal" => Expression::PrimaryExpression(PrimaryExpression::Call {
+            from: Some(Box::new(arg!(0))),
+            name: "toStringDec".to_string(),
+            arguments: Arguments(vec![]),
+            span: Span::default(),
+        }),
+        op_code => match method_name(op_code) {
+            Some(method) => generate_method_call(block, method, context)?,
+            None => Expression::PrimaryExpression(PrimaryExpression::RawBlock(generate_raw_block(
+                block, context,
+            )?)),
+        },
+    })
+}
+
+// generates a raw block out of a block that doesn't have a rafflesia syntax, it compiles back into
+// the exact same block
+fn generate_raw_block(block: &Block, context: &Context) -> Result<RawBlock, LogicGenerateError> {
+    let arguments = block
+        .content
+        .get_args()
+        .into_iter()
+        .map(|arg| {
+            Ok(match arg {
+                Argument::String {
+                    value: ArgValue::Value(value),
+                    ..
+                }
+                | Argument::Menu {
+                    value: ArgValue::Value(value),
+                    ..
+                } => Expression::Literal {
+                    value: Literal::String(value.clone()),
+                    span: Span::default(),
+                },
+                Argument::Number {
+                    value: ArgValue::Value(value),
+                    ..
+                } => Expression::Literal {
+                    value: Literal::Number(*value),
+                    span: Span::default(),
+                },
+                Argument::Boolean {
+                    value: ArgValue::Value(value),
+                    ..
+                } => Expression::Literal {
+                    value: Literal::Boolean(*value),
+                    span: Span::default(),
+                },
+                Argument::String {
+                    value: ArgValue::Block(block),
+                    ..
+                }
+                | Argument::Number {
+                    value: ArgValue::Block(block),
+                    ..
+                }
+                | Argument::Boolean {
+                    value: ArgValue::Block(block),
+                    ..
+                }
+                | Argument::Menu {
+                    value: ArgValue::Block(block),
+                    ..
+                } => generate_block_expression(block, context)?,
+            })
+        })
+        .collect::<Result<_, LogicGenerateError>>()?;
+
+    // nesting blocks always have their bodies written, even when they're empty
+    let sub_stacks_count = match block.block_type {
+        BlockType::Control(BlockControl::OneNest) => 1,
+        BlockType::Control(BlockControl::TwoNest) => 2,
+        _ => 0,
+    }
+    .max(if block.sub_stack2.is_some() {
+        2
+    } else if block.sub_stack1.is_some() {
+        1
+    } else {
+        0
+    });
+
+    Ok(RawBlock {
+        op_code: block.op_code.clone(),
+        spec: block.content.to_string(),
+        block_type: raw_block_type(&block.block_type),
+        color: format!("#{:08x}", u32::from(block.color)),
+        arguments: Arguments(arguments),
+        sub_stacks: [&block.sub_stack1, &block.sub_stack2]
+            .into_iter()
+            .take(sub_stacks_count)
+            .map(|blocks| generate_sub_stack(blocks, context))
+            .collect::<Result<_, _>>()?,
+        span: Span::default(),
+    })
+}
+
+// the inverse of the raw block type parsing in the compiler
+fn raw_block_type(block_type: &BlockType) -> String {
+    match block_type {
+        BlockType::Regular => "regular".to_string(),
+        BlockType::Control(BlockControl::OneNest) => "one_nest".to_string(),
+        BlockType::Control(BlockControl::TwoNest) => "two_nest".to_string(),
+        BlockType::Control(BlockControl::EndingBlock) => "ending".to_string(),
+        BlockType::Argument(ArgumentBlockReturnType::Boolean) => "boolean".to_string(),
+        BlockType::Argument(ArgumentBlockReturnType::Number) => "number".to_string(),
+        BlockType::Argument(ArgumentBlockReturnType::String) => "string".to_string(),
+        BlockType::Argument(ArgumentBlockReturnType::List { inner_type }) => match inner_type {
+            ListItem::Number => "list<number>".to_string(),
+            ListItem::String => "list<string>".to_string(),
+        },
+        BlockType::Argument(ArgumentBlockReturnType::View { type_name }) => {
+            format!("view<{}>", type_name)
+        }
+        BlockType::Argument(ArgumentBlockReturnType::Component { type_name }) => {
+            format!("component<{}>", type_name)
+        }
+    }
+}
+
+// retrieves the value of a menu argument, e.g. the variable name of a setVar block
+fn menu_value(arg: &Argument, op_code: &str) -> Result<String, LogicGenerateError> {
+    match arg {
+        Argument::Menu {
+            value: ArgValue::Value(value),
+            ..
+        } => Ok(value.clone()),
+        _ => Err(LogicGenerateError::InvalidBlockArguments {
+            op_code: op_code.to_string(),
+        }),
+    }
+}
+
+/// Prints a logic AST into a prettified logic source code.
+///
+/// Variable declarations are grouped together, while event listeners are separated by an empty
+/// line.
+pub fn print_logic(statements: &OuterStatements) -> String {
+    print_logic_with_comments(statements, &mut Comments::default())
+}
+
+/// Prints a logic AST like [`print_logic`], placing back the comments of the code it was parsed
+/// from.
+pub(crate) fn print_logic_with_comments(
+    statements: &OuterStatements,
+    comments: &mut Comments,
+) -> String {
+    let mut result = String::new();
+    let mut previous_is_declaration = None;
+
+    for statement in statements.0.iter() {
+        let is_declaration = matches!(
+            statement,
+            OuterStatement::SimpleVariableDeclaration { .. }
+                | OuterStatement::ComplexVariableDeclaration { .. }
+                | OuterStatement::ComponentDeclaration { .. }
+        );
+
+        // put an empty line between event listeners, and between declarations and listeners
+        if let Some(previous_is_declaration) = previous_is_declaration {
+            if !(previous_is_declaration && is_declaration) {
+                result.push('\n');
+            }
+        }
+
+        previous_is_declaration = Some(is_declaration);
+        comments.line(&mut result, statement.span().start, "");
+
+        match statement {
+            OuterStatement::SimpleVariableDeclaration {
+                variable_type,
+                identifier,
+                ..
+            } => {
+                result.push_str(&format!(
+                    "{} {}\n",
+                    print_variable_type(variable_type),
+                    identifier
+                ));
+            }
+            OuterStatement::ComplexVariableDeclaration {
+                variable_type,
+                identifier,
+                ..
+            } => {
+                let (name, inner_type) = match variable_type {
+                    ComplexVariableType::Map { inner_type } => ("map", inner_type),
+                    ComplexVariableType::List { inner_type } => ("list", inner_type),
+                };
+
+                result.push_str(&format!(
+                    "{}<{}> {}\n",
+                    name,
+                    print_variable_type(inner_type),
+                    identifier
+                ));
+            }
+            OuterStatement::ComponentDeclaration {
+                component_type,
+                identifier,
+                arguments,
+                ..
+            } => {
+                result.push_str(&format!("{} {}", component_type, identifier));
+
+                if !arguments.is_empty() {
+                    let arguments = arguments
+                        .iter()
+                        .map(|argument| {
+                            format!("{}: {}", argument.name, print_string(&argument.value))
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    result.push_str(&format!("({})", arguments));
+                }
+
+                result.push('\n');
+            }
+            OuterStatement::ActivityEventListener {
+                event_name,
+                body,
+                span,
+            } => {
+                result.push_str(event_name);
+                print_body(&mut result, body, 0, span.start, comments);
+                result.push('\n');
+            }
+            OuterStatement::ViewEventListener {
+                view_id,
+                event_name,
+                body,
+                span,
+            } => {
+                result.push_str(&format!("{}.{}", view_id, event_name));
+                print_body(&mut result, body, 0, span.start, comments);
+                result.push('\n');
+            }
+            OuterStatement::MoreBlockDeclaration {
+                name,
+                parameters,
+                body,
+                span,
+            } => {
+                let parameters = parameters
+                    .iter()
+                    .map(|parameter| {
+                        format!(
+                            "{}: {}",
+                            parameter.name,
+                            print_parameter_type(&parameter.parameter_type)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                result.push_str(&format!("moreblock {}({})", name, parameters));
+                print_body(&mut result, body, 0, span.start, comments);
+                result.push('\n');
+            }
+        }
+    }
+
+    comments.finish(&mut result);
+    result
+}
+
+const INDENT: &str = "    ";
+
+fn print_variable_type(variable_type: &VariableType) -> &'static str {
+    match variable_type {
+        VariableType::Number => "number",
+        VariableType::String => "string",
+        VariableType::Boolean => "boolean",
+    }
+}
+
+fn print_parameter_type(parameter_type: &ParameterType) -> String {
+    match parameter_type {
+        ParameterType::Simple(variable_type) => print_variable_type(variable_type).to_string(),
+        ParameterType::Complex(ComplexVariableType::Map { inner_type }) => {
+            format!("map<{}>", print_variable_type(inner_type))
+        }
+        ParameterType::Complex(ComplexVariableType::List { inner_type }) => {
+            format!("list<{}>", print_variable_type(inner_type))
+        }
+        ParameterType::View => "view".to_string(),
+    }
+}
+
+// prints ` { statements }` with the statements indented one level deeper than `depth`. the body
+// is the first one after `from` on the source, returns where it ends
+fn print_body(
+    result: &mut String,
+    body: &InnerStatements,
+    depth: usize,
+    from: usize,
+    comments: &mut Comments,
+) -> usize {
+    result.push_str(" {\n");
+
+    let mut end = comments.find("{", from) + 1;
+
+    for statement in body.0.iter() {
+        comments.line(result, statement.span().start, &INDENT.repeat(depth + 1));
+        result.push_str(&INDENT.repeat(depth + 1));
+        print_inner_statement(result, statement, depth + 1, comments);
+        end = statement.span().end;
+        result.push('\n');
+    }
+
+    let closing = comments.find("}", end);
+    comments.closing(result, closing, &INDENT.repeat(depth));
+
+    result.push_str(&INDENT.repeat(depth));
+    result.push('}');
+
+    closing + 1
+}
+
+fn print_inner_statement(
+    result: &mut String,
+    statement: &InnerStatement,
+    depth: usize,
+    comments: &mut Comments,
+) {
+    match statement {
+        InnerStatement::VariableAssignment(VariableAssignment {
+            identifier, value, ..
+        }) => {
+            result.push_str(&format!("{} = {}", identifier, print_expression(value)));
+        }
+        InnerStatement::IndexAssignment(IndexAssignment {
+            from, index, value, ..
+        }) => {
+            result.push_str(&format!(
+                "{}[{}] = {}",
+                print_expression_prec(from, PREC_PRIMARY),
+                print_expression(index),
+                print_expression(value)
+            ));
+        }
+        InnerStatement::IfStatement(IfStatement {
+            condition,
+            body,
+            else_body,
+            ..
+        }) => {
+            result.push_str(&format!("if {}", print_expression(condition)));
+            let end = print_body(result, body, depth, condition.span().end, comments);
+
+            if let Some(else_body) = else_body {
+                result.push_str(" else");
+                print_body(result, else_body, depth, end, comments);
+            }
+        }
+        InnerStatement::RepeatStatement(RepeatStatement {
+            condition, body, ..
+        }) => {
+            result.push_str(&format!("repeat {}", print_expression(condition)));
+            print_body(result, body, depth, condition.span().end, comments);
+        }
+        InnerStatement::ForeverStatement(ForeverStatement { body, span }) => {
+            result.push_str("forever");
+            print_body(result, body, depth, span.start, comments);
+        }
+        InnerStatement::Break { .. } => result.push_str("break"),
+        InnerStatement::Continue { .. } => result.push_str("continue"),
+        InnerStatement::Expression(expr) => result.push_str(&print_expression(expr)),
+        InnerStatement::RawBlock(raw_block) => {
+            result.push_str(&print_raw_block(raw_block));
+
+            // the span of a raw block statement goes until its last sub stack, the first one is
+            // right after the raw block
+            let mut from = raw_block.span.start;
+
+            for sub_stack in raw_block.sub_stacks.iter() {
+                from = print_body(result, sub_stack, depth, from, comments);
+            }
+        }
+    }
+}
+
+// precedences of expressions, following the rules of the parser. the higher it is, the tighter
+// it binds
+const PREC_BOOLEAN: u8 = 1;
+const PREC_COMPARISON: u8 = 2;
+const PREC_ARITHMETIC: u8 = 3;
+const PREC_TERM: u8 = 4;
+const PREC_FACTOR: u8 = 5;
+const PREC_POWER: u8 = 6;
+const PREC_PRIMARY: u8 = 7;
+
+/// Prints an expression with the least amount of parentheses needed
+pub fn print_expression(expr: &Expression) -> String {
+    print_expression_prec(expr, PREC_BOOLEAN)
+}
+
+// prints an expression, wraps it in parentheses if its precedence is lower than `min_prec`
+fn print_expression_prec(expr: &Expression, min_prec: u8) -> String {
+    let (result, prec) = match expr {
+        Expression::BinOp {
+            first,
+            operator,
+            second,
+            ..
+        } => {
+            // (precedence, operator, min precedence of the first, min precedence of the second)
+            let (prec, op, first_prec, second_prec) = match operator {
+                BinaryOperator::Or => (PREC_BOOLEAN, "||", PREC_BOOLEAN, PREC_COMPARISON),
+                BinaryOperator::And => (PREC_BOOLEAN, "&&", PREC_BOOLEAN, PREC_COMPARISON),
+                BinaryOperator::LT => (PREC_COMPARISON, "<", PREC_ARITHMETIC, PREC_COMPARISON),
+                BinaryOperator::LTE => (PREC_COMPARISON, "<=", PREC_ARITHMETIC, PREC_COMPARISON),
+                BinaryOperator::GT => (PREC_COMPARISON, ">", PREC_ARITHMETIC, PREC_COMPARISON),
+                BinaryOperator::GTE => (PREC_COMPARISON, ">=", PREC_ARITHMETIC, PREC_COMPARISON),
+                BinaryOperator::EQ => (PREC_COMPARISON, "==", PREC_ARITHMETIC, PREC_COMPARISON),
+                BinaryOperator::Plus => (PREC_ARITHMETIC, "+", PREC_ARITHMETIC, PREC_TERM),
+                BinaryOperator::Minus => (PREC_ARITHMETIC, "-", PREC_ARITHMETIC, PREC_TERM),
+                BinaryOperator::Multiply => (PREC_TERM, "*", PREC_TERM, PREC_FACTOR),
+                BinaryOperator::Divide => (PREC_TERM, "/", PREC_TERM, PREC_FACTOR),
+                BinaryOperator::Power => (PREC_POWER, "**", PREC_PRIMARY, PREC_POWER),
+            };
+
+            (
+                format!(
+                    "{} {} {}",
+                    print_expression_prec(first, first_prec),
+                    op,
+                    print_expression_prec(second, second_prec)
+                ),
+                prec,
+            )
+        }
+        Expression::UnaryOp {
+            value, operator, ..
+        } => match operator {
+            UnaryOperator::Not => (
+                format!("!{}", print_expression_prec(value, PREC_ARITHMETIC)),
+                PREC_COMPARISON,
+            ),
+            UnaryOperator::Minus => (
+                format!("-{}", print_expression_prec(value, PREC_FACTOR)),
+                PREC_FACTOR,
+            ),
+            UnaryOperator::Plus => (
+                format!("+{}", print_expression_prec(value, PREC_FACTOR)),
+                PREC_FACTOR,
+            ),
+        },
+        Expression::PrimaryExpression(primary) => (
+            match primary {
+                PrimaryExpression::Index { from, index, .. } => format!(
+                    "{}[{}]",
+                    print_expression_prec(from, PREC_PRIMARY),
+                    print_expression(index)
+                ),
+                PrimaryExpression::VariableAccess { from, name, .. } => match from {
+                    Some(from) => format!("{}.{}", print_expression_prec(from, PREC_PRIMARY), name),
+                    None => name.clone(),
+                },
+                PrimaryExpression::Call {
+                    from,
+                    name,
+                    arguments,
+                    ..
+                } => {
+                    let arguments = arguments
+                        .0
+                        .iter()
+                        .map(print_expression)
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    match from {
+                        Some(from) => format!(
+                            "{}.{}({})",
+                            print_expression_prec(from, PREC_PRIMARY),
+                            name,
+                            arguments
+                        ),
+                        None => format!("{}({})", name, arguments),
+                    }
+                }
+                PrimaryExpression::RawBlock(raw_block) => print_raw_block(raw_block),
+            },
+            PREC_PRIMARY,
+        ),
+        Expression::Literal { value, .. } => match value {
+            // negative numbers are parsed as an unary minus
+            Literal::Number(num) if *num < 0f64 => (num.to_string(), PREC_FACTOR),
+            Literal::Number(num) => (num.to_string(), PREC_PRIMARY),
+            Literal::Boolean(bool) => (bool.to_string(), PREC_PRIMARY),
+            Literal::String(str) => (print_string(str), PREC_PRIMARY),
+        },
+    };
+
+    if prec < min_prec {
+        format!("({})", result)
+    } else {
+        result
+    }
+}
+
+// prints a string literal, quotes are escaped as `\"`. backslashes are kept as-is since they're
+// java's escapes, unless they'd be read as an escape of the parser: those before a quote, before
+// another backslash and at the end of the string are escaped as `\\`
+fn print_string(str: &str) -> String {
+    let mut result = String::from('"');
+    let mut chars = str.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '"' => result.push_str("\\\""),
+            '\\' if matches!(chars.peek(), None | Some('"' | '\\')) => result.push_str("\\\\"),
+            char => result.push(char),
+        }
+    }
+
+    result.push('"');
+    result
+}
+
+// prints the `#block(..)` of a raw block, without its substacks
+fn print_raw_block(raw_block: &RawBlock) -> String {
+    let mut arguments = [
+        &raw_block.op_code,
+        &raw_block.spec,
+        &raw_block.block_type,
+        &raw_block.color,
+    ]
+    .into_iter()
+    .map(|str| print_string(str))
+    .collect::<Vec<_>>();
+
+    arguments.extend(raw_block.arguments.0.iter().map(print_expression));
+
+    format!("#block({})", arguments.join(", "))
+}
+
+#[derive(Debug, Error)]
+pub enum LogicGenerateError {
+    #[error("block with opcode `{op_code}` has invalid arguments")]
+    InvalidBlockArguments { op_code: String },
+
+    #[error("the event type of event `{event_name}` is not supported")]
+    UnsupportedEvent { event_name: String },
+
+    #[error("the type of list variable `{name}` is not supported")]
+    UnsupportedListVariable { name: String },
+
+    #[error("the type of component `{name}` is not supported")]
+    UnsupportedComponent { name: String },
+
+    #[error("moreblock `{more_block}` has a parameter that is not supported")]
+    UnsupportedMoreBlockParameter { more_block: String },
+}
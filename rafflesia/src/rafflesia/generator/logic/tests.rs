@@ -0,0 +1,489 @@
+use super::{generate_logic, print_logic, LogicGenerateError};
+use crate::compiler::layout::compile_view_tree;
+use crate::compiler::layout::parser::parse_layout;
+use crate::compiler::logic::parser::parse_logic;
+use crate::compiler::logic::{compile_logic, LogicCompileResult};
+use swrs::api::block::{
+    ArgValue, Argument, ArgumentBlockReturnType, Block, BlockCategory, BlockContent, BlockControl,
+    BlockType, Blocks,
+};
+use swrs::api::component::ComponentKind;
+use swrs::api::screen::{Event, EventType, MoreBlock};
+use swrs::color::Color;
+use swrs::LinkedHashMap;
+
+const LOGIC: &str = r#"
+number counter
+string name
+boolean enabled
+map<string> labels
+map<number> scores
+list<number> numbers
+sharedpreferences prefs(file: "data")
+intent page
+requestnetwork net
+
+moreblock greet(who: string, times: number, target: view, items: list<number>) {
+    repeat times {
+        toast(who)
+    }
+}
+
+onCreate {
+    counter = 0
+    if enabled {
+        counter = counter + 1 * 2
+    } else {
+        repeat 10 {
+            counter = (counter - 1) / 2
+        }
+    }
+    forever {
+        break
+    }
+    name = counter.toString()
+    toast("hello world")
+    greet(name, 2, button1, numbers)
+    prefs.set("name", prefs.get("last"))
+    page.setAction("android.intent.action.VIEW")
+    page.start()
+    labels.put("greeting", name)
+    scores.put(name, counter + 1)
+    counter = scores.get(name) * 2
+    if labels.containsKey("greeting") {
+        name = labels.get("greeting")
+    }
+    numbers.add(counter)
+    numbers.insert(0, 5)
+    counter = numbers.get(0) + numbers.indexOf(5)
+    if numbers.contains(counter) {
+        numbers.remove(numbers.length() - 1)
+    }
+}
+
+button1.onClick {
+    enabled = !(counter < 5) || counter >= -counter
+}
+
+net.onResponse {
+    name = response
+}
+"#;
+
+#[test]
+fn generate_simple() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let parsed = parse_logic(LOGIC).expect("failed to parse code");
+    let compiled = compile_logic(parsed.clone(), &layout).expect("failed to compile code");
+
+    let generated = generate_logic(&compiled);
+    assert!(generated.skipped.is_empty());
+
+    // generated nodes don't have spans, compare them by their printed form
+    assert_eq!(print_logic(&generated.statements), print_logic(&parsed));
+}
+
+#[test]
+fn print_simple() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let source = LOGIC.trim_start();
+    let parsed = parse_logic(source).expect("failed to parse code");
+    let printed = print_logic(&parsed);
+
+    assert_eq!(printed, source);
+
+    // it should be parsed back into the same thing
+    assert_eq!(parse_logic(&printed).unwrap(), parsed);
+}
+
+#[test]
+fn raw_block_round_trip() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let is_ready = Block {
+        sub_stack1: None,
+        sub_stack2: None,
+        color: Color::from(0xff5cb722),
+        op_code: "isReady".to_string(),
+        content: BlockContent::builder().text("is").text("ready").build(),
+        block_type: BlockType::Argument(ArgumentBlockReturnType::Boolean),
+    };
+
+    let do_something = Block {
+        sub_stack1: None,
+        sub_stack2: None,
+        color: Color::from(0xffa1887f),
+        op_code: "doSomething".to_string(),
+        content: BlockContent::builder()
+            .text("do")
+            .arg(Argument::String {
+                name: None,
+                value: ArgValue::Value("hello".to_string()),
+            })
+            .text("times")
+            .arg(Argument::Number {
+                name: Some("count".to_string()),
+                value: ArgValue::Value(-3.5),
+            })
+            .text("if")
+            .arg(Argument::Boolean {
+                name: None,
+                value: ArgValue::Block(is_ready),
+            })
+            .text("on")
+            .arg(Argument::Menu {
+                name: "view".to_string(),
+                value: ArgValue::Value("button1".to_string()),
+            })
+            .build(),
+        block_type: BlockType::Regular,
+    };
+
+    let when = Block {
+        sub_stack1: Some(Blocks(vec![do_something])),
+        sub_stack2: None,
+        color: Color::from(0xffe1a92a),
+        op_code: "when".to_string(),
+        content: BlockContent::builder()
+            .text("when")
+            .arg(Argument::Boolean {
+                name: None,
+                value: ArgValue::Value(true),
+            })
+            .build(),
+        block_type: BlockType::Control(BlockControl::TwoNest),
+    };
+
+    let code = Blocks(vec![when]);
+
+    let generated = generate_logic(&LogicCompileResult {
+        variables: LinkedHashMap::new(),
+        list_variables: LinkedHashMap::new(),
+        more_blocks: LinkedHashMap::new(),
+        components: LinkedHashMap::new(),
+        events: vec![Event {
+            name: "onCreate".to_string(),
+            event_type: EventType::ActivityEvent,
+            code: code.clone(),
+        }],
+    });
+
+    let printed = print_logic(&generated.statements);
+
+    assert_eq!(
+        printed,
+        r##"onCreate {
+    #block("when", "when %b", "two_nest", "#ffe1a92a", true) {
+        #block("doSomething", "do %s times %d.count if %b on %m.view", "regular", "#ffa1887f", "hello", -3.5, #block("isReady", "is ready", "boolean", "#ff5cb722"), "button1")
+    } {
+    }
+}
+"##
+    );
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let compiled =
+        compile_logic(parse_logic(&printed).unwrap(), &layout).expect("failed to compile code");
+
+    assert_eq!(compiled.events[0].code, code);
+}
+
+#[test]
+fn generate_skips_unsupported() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let toast = |text: &str| Block {
+        sub_stack1: None,
+        sub_stack2: None,
+        color: Color::from(0xffa1887f),
+        op_code: "doToast".to_string(),
+        content: BlockContent::builder()
+            .text("Toast")
+            .arg(Argument::String {
+                name: None,
+                value: ArgValue::Value(text.to_string()),
+            })
+            .build(),
+        block_type: BlockType::Regular,
+    };
+
+    let mut components = LinkedHashMap::new();
+    components.insert("gyro".to_string(), ComponentKind::Gyroscope);
+    components.insert("page".to_string(), ComponentKind::Intent);
+
+    let generated = generate_logic(&LogicCompileResult {
+        variables: LinkedHashMap::new(),
+        list_variables: LinkedHashMap::new(),
+        more_blocks: LinkedHashMap::new(),
+        components,
+        events: vec![
+            Event {
+                name: "onDrawerOpened".to_string(),
+                event_type: EventType::DrawerViewEvent {
+                    id: "drawer".to_string(),
+                },
+                code: Blocks(vec![toast("opened")]),
+            },
+            Event {
+                name: "onCreate".to_string(),
+                event_type: EventType::ActivityEvent,
+                code: Blocks(vec![toast("hello")]),
+            },
+        ],
+    });
+
+    // the rest of the screen is still generated
+    assert_eq!(
+        print_logic(&generated.statements),
+        "intent page\n\nonCreate {\n    toast(\"hello\")\n}\n"
+    );
+
+    assert!(matches!(
+        generated.skipped[..],
+        [
+            LogicGenerateError::UnsupportedComponent { .. },
+            LogicGenerateError::UnsupportedEvent { .. }
+        ]
+    ));
+}
+
+#[test]
+fn malformed_blocks_round_trip() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    // a toast without its text
+    let toast = Block {
+        sub_stack1: None,
+        sub_stack2: None,
+        color: Color::from(0xffa1887f),
+        op_code: "doToast".to_string(),
+        content: BlockContent::builder().text("Toast").build(),
+        block_type: BlockType::Regular,
+    };
+
+    let name = Block {
+        sub_stack1: None,
+        sub_stack2: None,
+        color: Color::from(0xff5cb722),
+        op_code: "varName".to_string(),
+        content: BlockContent::builder().text("name").build(),
+        block_type: BlockType::Argument(ArgumentBlockReturnType::String),
+    };
+
+    // a setVar whose variable is a block, inside an if whose condition is fine
+    let set_var = Block {
+        sub_stack1: None,
+        sub_stack2: None,
+        color: Color::from(0xffee7d16),
+        op_code: "setVarInt".to_string(),
+        content: BlockContent::builder()
+            .text("set")
+            .arg(Argument::Menu {
+                name: "varInt".to_string(),
+                value: ArgValue::Block(name),
+            })
+            .text("to")
+            .arg(Argument::Number {
+                name: None,
+                value: ArgValue::Value(1.),
+            })
+            .build(),
+        block_type: BlockType::Regular,
+    };
+
+    let r#if = Block {
+        sub_stack1: Some(Blocks(vec![set_var])),
+        sub_stack2: None,
+        color: Default::default(),
+        op_code: "if".to_string(),
+        content: BlockContent::builder()
+            .text("if")
+            .arg(Argument::Boolean {
+                name: None,
+                value: ArgValue::Value(true),
+            })
+            .text("then")
+            .build(),
+        block_type: BlockType::Control(BlockControl::OneNest),
+    };
+
+    let code = Blocks(vec![toast, r#if]);
+
+    let generated = generate_logic(&LogicCompileResult {
+        variables: LinkedHashMap::new(),
+        list_variables: LinkedHashMap::new(),
+        more_blocks: LinkedHashMap::new(),
+        components: LinkedHashMap::new(),
+        events: vec![Event {
+            name: "onCreate".to_string(),
+            event_type: EventType::ActivityEvent,
+            code: code.clone(),
+        }],
+    });
+
+    assert!(generated.skipped.is_empty());
+
+    let printed = print_logic(&generated.statements);
+
+    assert_eq!(
+        printed,
+        r##"onCreate {
+    #block("doToast", "Toast", "regular", "#ffa1887f")
+    if true {
+        #block("setVarInt", "set %m.varInt to %d", "regular", "#ffee7d16", #block("varName", "name", "string", "#ff5cb722"), 1)
+    }
+}
+"##
+    );
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let compiled =
+        compile_logic(parse_logic(&printed).unwrap(), &layout).expect("failed to compile code");
+
+    assert_eq!(compiled.events[0].code, code);
+}
+
+#[test]
+fn string_escapes_round_trip() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let add_source = Block {
+        sub_stack1: None,
+        sub_stack2: None,
+        color: Color::from(0xff5cb722),
+        op_code: "addSourceDirectly".to_string(),
+        content: BlockContent::builder()
+            .text("add")
+            .text("source")
+            .text("directly")
+            .arg(Argument::String {
+                name: Some("inputOnly".to_string()),
+                value: ArgValue::Value(r#"Log.d("tag", "a\\b\n");"#.to_string()),
+            })
+            .build(),
+        block_type: BlockType::Regular,
+    };
+
+    let toast = Block::new(
+        BlockCategory::ComponentFunc,
+        "doToast".to_string(),
+        BlockContent::builder()
+            .text("Toast")
+            .arg(Argument::String {
+                name: None,
+                value: ArgValue::Value(r#"say "hi" \"#.to_string()),
+            })
+            .build(),
+        BlockType::Regular,
+    );
+
+    let code = Blocks(vec![add_source, toast]);
+
+    let generated = generate_logic(&LogicCompileResult {
+        variables: LinkedHashMap::new(),
+        list_variables: LinkedHashMap::new(),
+        more_blocks: LinkedHashMap::new(),
+        components: LinkedHashMap::new(),
+        events: vec![Event {
+            name: "onCreate".to_string(),
+            event_type: EventType::ActivityEvent,
+            code: code.clone(),
+        }],
+    });
+
+    let printed = print_logic(&generated.statements);
+
+    assert_eq!(
+        printed,
+        r##"onCreate {
+    #block("addSourceDirectly", "add source directly %s.inputOnly", "regular", "#ff5cb722", "Log.d(\"tag\", \"a\\\b\n\");")
+    toast("say \"hi\" \\")
+}
+"##
+    );
+
+    let layout = compile_view_tree(parse_layout("LinearLayout { Button: button1 }").unwrap())
+        .expect("failed to compile layout");
+
+    let compiled =
+        compile_logic(parse_logic(&printed).unwrap(), &layout).expect("failed to compile code");
+
+    assert_eq!(compiled.events[0].code, code);
+}
+
+#[test]
+fn generate_skipped_more_block_calls() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    // moreblock parameters must have a name
+    let spec = || {
+        BlockContent::builder()
+            .text("track")
+            .arg(Argument::String {
+                name: None,
+                value: ArgValue::Value("".to_string()),
+            })
+            .build()
+    };
+
+    let mut more_blocks = LinkedHashMap::new();
+    more_blocks.insert(
+        "track".to_string(),
+        MoreBlock {
+            id: "track".to_string(),
+            spec: spec(),
+            code: Blocks(vec![]),
+        },
+    );
+
+    let call = Block {
+        sub_stack1: None,
+        sub_stack2: None,
+        color: Color::from(0xff8a55d7),
+        op_code: "definedFunc".to_string(),
+        content: BlockContent::builder()
+            .text("track")
+            .arg(Argument::String {
+                name: None,
+                value: ArgValue::Value("hello".to_string()),
+            })
+            .build(),
+        block_type: BlockType::Regular,
+    };
+
+    let generated = generate_logic(&LogicCompileResult {
+        variables: LinkedHashMap::new(),
+        list_variables: LinkedHashMap::new(),
+        more_blocks,
+        components: LinkedHashMap::new(),
+        events: vec![Event {
+            name: "onCreate".to_string(),
+            event_type: EventType::ActivityEvent,
+            code: Blocks(vec![call]),
+        }],
+    });
+
+    assert!(matches!(
+        generated.skipped[..],
+        [LogicGenerateError::UnsupportedMoreBlockParameter { .. }]
+    ));
+
+    // the moreblock doesn't exist on the generated code, the call is kept as a raw block to still
+    // be compiled
+    assert_eq!(
+        print_logic(&generated.statements),
+        r##"onCreate {
+    #block("definedFunc", "track %s", "regular", "#ff8a55d7", "hello")
+}
+"##
+    );
+}
@@ -1,4 +1,6 @@
 pub mod compiler;
 pub mod core;
+pub mod diagnostic;
+pub mod formatter;
 pub mod generator;
 pub mod ops;
@@ -0,0 +1,450 @@
+#[cfg(test)]
+mod tests;
+
+use crate::compiler;
+use crate::compiler::logic::LogicCompileResult;
+use crate::compiler::ResourceNames;
+use crate::core::manifest::{self, ActivityTable, ViewTable, MANIFEST_FILE_NAME};
+use crate::core::resource::ProjectResources;
+use crate::core::source::SourceProvider;
+use crate::diagnostic::{Diagnostic, Diagnostics, SourceDiagnostics};
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use swrs::api::screen::Screen;
+use swrs::api::view::View;
+use swrs::api::{CustomView, SketchwareProject};
+use swrs::parser::RawSketchwareProject;
+
+/// Receives what's going on while a project is being built, so that it can be shown to the user.
+///
+/// Every methods do nothing by default, `()` can be used to not report anything.
+pub trait BuildReporter {
+    /// Called once the project is found, before anything is compiled
+    fn started(&mut self, _project_name: &str) {}
+
+    /// Called whenever the build moves on to the next step
+    fn progress(&mut self, _step: BuildStep) {}
+
+    /// Called with the diagnostics of a source file as soon as they're found. The build will
+    /// still go on to find more of them, then fails with every diagnostics as [`Diagnostics`].
+    fn diagnostics(&mut self, _diagnostics: &SourceDiagnostics) {}
+
+    /// Called when the build is over, whether it succeeded or not
+    fn finished(&mut self, _success: bool) {}
+}
+
+impl BuildReporter for () {}
+
+/// A step of a build, see [`BuildReporter::progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildStep<'a> {
+    /// The activity of the given name is being compiled
+    CompilingActivity(&'a str),
+    /// The custom view of the given name is being compiled
+    CompilingCustomView(&'a str),
+    /// The compiled activities are being put together into a sketchware project
+    Packing,
+    /// The project files are being written to the given folder
+    Writing(&'a Path),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BuildOptions {
+    /// The folder where the encrypted project files will be written to, along with the resource
+    /// files inside `resources/images`, `resources/sounds` and `resources/fonts`. It will be
+    /// replaced if it already exists. Nothing is written if this is `None`.
+    pub output: Option<PathBuf>,
+}
+
+/// Builds a project into a sketchware project, its files are read from the given source, see
+/// [`crate::core::source::DirSource`] to build a project on the disk.
+pub fn build_project(
+    source: &dyn SourceProvider,
+    options: &BuildOptions,
+    reporter: &mut dyn BuildReporter,
+) -> Result<RawSketchwareProject> {
+    let result = build_inner(source, options, reporter);
+    reporter.finished(result.is_ok());
+
+    result
+}
+
+fn build_inner(
+    source: &dyn SourceProvider,
+    options: &BuildOptions,
+    reporter: &mut dyn BuildReporter,
+) -> Result<RawSketchwareProject> {
+    let manifest_content = source
+        .read(MANIFEST_FILE_NAME)
+        .context("Failed to read the manifest")?;
+
+    let manifest = manifest::parse_manifest_str(&manifest_content)
+        .map_err(|err| report_error(reporter, err))?;
+
+    // verify that the project has at least one activity and at least one activity that's named "main"
+    if manifest.activity.len() == 0 {
+        bail!("There must be at least one activity");
+    }
+
+    if !manifest.activity.contains_key("main") {
+        bail!("There must be at least one activity named `main`");
+    }
+
+    // custom views and activities share the same namespace of layouts on sketchware
+    if let Some(name) = manifest
+        .view
+        .keys()
+        .find(|name| manifest.activity.contains_key(*name))
+    {
+        bail!(
+            "The custom view `{}` has the same name as an activity",
+            name
+        );
+    }
+
+    let project_name = manifest.project.name.to_owned();
+    reporter.started(&project_name);
+
+    let resources = ProjectResources::collect(source, manifest.resources.as_ref())
+        .context("Error while collecting the resources")?;
+
+    // start building.. i guess?
+    // this is very anti-climactic
+
+    let mut names = resources.names();
+    names.custom_views = manifest.view.keys().cloned().collect();
+
+    // both are compiled before bailing so the diagnostics of both can be reported at once
+    let custom_views = compile_custom_views(source, manifest.view.clone(), &names, reporter);
+    let screens = compile_screens(source, manifest.activity.clone(), &names, reporter);
+
+    let (custom_views, screens) = match (custom_views, screens) {
+        (Ok(custom_views), Ok(screens)) => Ok((custom_views, screens)),
+        (Err(err), Ok(_)) | (Ok(_), Err(err)) => Err(err),
+        (Err(first), Err(second)) => Err(merge_errors(first, second)),
+    }
+    .context(format!("Error while building project {}", project_name))?;
+
+    reporter.progress(BuildStep::Packing);
+
+    // build a sketchware project skeleton out of the project manifest
+    let mut sw_proj: SketchwareProject = match manifest.try_into() {
+        Ok(sw_proj) => sw_proj,
+        Err(err) => {
+            let diagnostic = Diagnostic::from_project_generation_error(&err, &manifest_content);
+            let diagnostics =
+                SourceDiagnostics::new(MANIFEST_FILE_NAME, &manifest_content, vec![diagnostic]);
+            reporter.diagnostics(&diagnostics);
+
+            return Err(Diagnostics::from(diagnostics)).context("Error while parsing the manifest");
+        }
+    };
+
+    // then set stuff
+    sw_proj.screens = screens;
+    sw_proj.custom_views = custom_views;
+    sw_proj.resources = resources.to_swrs();
+
+    // we just need to reconstruct this to project files
+
+    let raw: RawSketchwareProject = sw_proj
+        .try_into()
+        .context("Error while constructing the raw sketchware project")?;
+
+    // and we're done!!!! :D
+    // todo: gotta create like options to pack em into an .swb or .sh file or something
+
+    if let Some(output) = &options.output {
+        reporter.progress(BuildStep::Writing(output));
+        write_project(&raw, output)?;
+        write_resources(source, &resources, output)?;
+    }
+
+    Ok(raw)
+}
+
+/// Writes the encrypted files of a sketchware project into a folder, the folder will be replaced
+/// if it already exists.
+pub fn write_project(raw: &RawSketchwareProject, output: &Path) -> Result<()> {
+    if output.exists() {
+        fs::remove_dir_all(output).context("Failed to remove the output folder")?;
+    }
+
+    fs::create_dir_all(output).context("Failed to create the output folder")?;
+
+    let files = [
+        ("project", &raw.project),
+        ("file", &raw.file),
+        ("library", &raw.library),
+        ("resource", &raw.resource),
+        ("view", &raw.view),
+        ("logic", &raw.logic),
+    ];
+
+    for (name, content) in files {
+        fs::write(output.join(name), swrs::encrypt_sw(content.as_bytes()))
+            .context(format!("Failed to write {}", output.join(name).display()))?;
+    }
+
+    Ok(())
+}
+
+/// Copies the resource files of a project into the `resources` folder inside of `output`.
+pub fn write_resources(
+    source: &dyn SourceProvider,
+    resources: &ProjectResources,
+    output: &Path,
+) -> Result<()> {
+    for resource in &resources.0 {
+        let folder = output.join("resources").join(resource.kind.folder());
+        fs::create_dir_all(&folder).context(format!("Failed to create {}", folder.display()))?;
+
+        let content = source
+            .read_bytes(&resource.path())
+            .context(format!("Failed to read {}", resource.path()))?;
+
+        fs::write(folder.join(&resource.file_name), content)
+            .context(format!("Failed to copy {}", resource.path()))?;
+    }
+
+    Ok(())
+}
+
+// hands the diagnostics that an error carries to the reporter, if there are any
+fn report_error(reporter: &mut dyn BuildReporter, err: anyhow::Error) -> anyhow::Error {
+    if let Some(diagnostics) = err.downcast_ref::<Diagnostics>() {
+        diagnostics
+            .0
+            .iter()
+            .for_each(|source| reporter.diagnostics(source));
+    }
+
+    err
+}
+
+// merges the diagnostics of two errors, an error that's not a diagnostic takes precedence since
+// it's what stopped the build from going further
+fn merge_errors(first: anyhow::Error, second: anyhow::Error) -> anyhow::Error {
+    match (
+        first.downcast::<Diagnostics>(),
+        second.downcast::<Diagnostics>(),
+    ) {
+        (Ok(mut first), Ok(second)) => {
+            first.0.extend(second.0);
+            first.into()
+        }
+        (Err(err), _) | (Ok(_), Err(err)) => err,
+    }
+}
+
+fn compile_custom_views(
+    source: &dyn SourceProvider,
+    views: HashMap<String, ViewTable>,
+    resources: &ResourceNames,
+    reporter: &mut dyn BuildReporter,
+) -> Result<Vec<CustomView>> {
+    let mut custom_views = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    let mut views = views.into_iter().collect::<Vec<_>>();
+    views.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    for (name, view) in views {
+        reporter.progress(BuildStep::CompilingCustomView(&name));
+
+        let layout_file = format!("src/{}", view.layout);
+        let layout = source.read(&layout_file).context(format!(
+            "Error while reading layout file of custom view {}",
+            name
+        ))?;
+
+        match compile_layout(&layout, resources) {
+            Ok(view) => custom_views.push(CustomView {
+                name,
+                layout: vec![view],
+            }),
+            Err(errors) => {
+                let source = SourceDiagnostics::new(&layout_file, &layout, errors);
+                reporter.diagnostics(&source);
+                diagnostics.push(source);
+            }
+        }
+    }
+
+    if !diagnostics.is_empty() {
+        return Err(Diagnostics(diagnostics).into());
+    }
+
+    Ok(custom_views)
+}
+
+fn compile_screens(
+    source: &dyn SourceProvider,
+    activities: HashMap<String, ActivityTable>,
+    resources: &ResourceNames,
+    reporter: &mut dyn BuildReporter,
+) -> Result<Vec<Screen>> {
+    let mut screens = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    // compile the activities in order so the errors are always reported in the same order
+    let mut activities = activities.into_iter().collect::<Vec<_>>();
+    activities.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    for (name, activity) in activities {
+        reporter.progress(BuildStep::CompilingActivity(&name));
+
+        let layout_file = format!("src/{}", activity.layout);
+        let layout = source.read(&layout_file).context(format!(
+            "Error while reading layout file of activity {}",
+            name
+        ))?;
+
+        let logic_file = format!("src/{}", activity.logic);
+        let logic = source.read(&logic_file).context(format!(
+            "Error while reading logic file of activity {}",
+            name
+        ))?;
+
+        // first we compile the layout, errors are collected so that we can keep going and report
+        // every errors of the project at once
+        let view = match compile_layout(&layout, resources) {
+            Ok(view) => Some(view),
+            Err(errors) => {
+                let source = SourceDiagnostics::new(&layout_file, &layout, errors);
+                reporter.diagnostics(&source);
+                diagnostics.push(source);
+                None
+            }
+        };
+
+        // then compile the logic with the compiled layout so the logic can access views from
+        // the layout (global view access baby). if the layout failed, the logic can only be
+        // checked for syntax errors
+        let logic_compile_result = match compile_logic(&logic, view.as_ref()) {
+            Ok(Some(result)) => result,
+            Ok(None) => continue,
+            Err(errors) => {
+                let source = SourceDiagnostics::new(&logic_file, &logic, errors);
+                reporter.diagnostics(&source);
+                diagnostics.push(source);
+                continue;
+            }
+        };
+
+        let Some(view) = view else { continue };
+
+        screens.push(Screen {
+            layout_name: name.clone(),
+            java_name: view_name_to_logic(&name),
+
+            layout: vec![view],
+
+            variables: logic_compile_result.variables,
+            list_variables: logic_compile_result.list_variables,
+            more_blocks: logic_compile_result.more_blocks,
+            components: logic_compile_result.components,
+            events: logic_compile_result.events,
+
+            fab: None, // todo: fab
+
+            fullscreen_enabled: activity.fullscreen,
+            toolbar_enabled: activity.toolbar,
+            drawer_enabled: activity.drawer,
+            fab_enabled: activity.fab,
+            orientation: activity.orientation.into(),
+            theme: activity.theme.into(),
+            keyboard_setting: activity.keyboard.into(),
+        });
+    }
+
+    if !diagnostics.is_empty() {
+        return Err(Diagnostics(diagnostics).into());
+    }
+
+    Ok(screens)
+}
+
+// parses and compiles a layout, and checks the resources it uses. returns every errors found as
+// diagnostics
+fn compile_layout(layout: &str, resources: &ResourceNames) -> Result<View, Vec<Diagnostic>> {
+    let (parsed, errors) = compiler::layout::parser::parse_layout_recovering(layout);
+
+    let Some(parsed) = parsed.filter(|_| errors.is_empty()) else {
+        return Err(errors
+            .iter()
+            .map(|err| Diagnostic::from_parse_error(err, layout))
+            .collect());
+    };
+
+    let resource_errors = compiler::layout::check_resources(&parsed, resources).err();
+
+    let errors = match (compiler::layout::compile_view_tree(parsed), resource_errors) {
+        (Ok(view), None) => return Ok(view),
+        (result, resource_errors) => result.err().into_iter().chain(resource_errors),
+    };
+
+    let mut diagnostics = errors
+        .flat_map(|errors| errors.0)
+        .map(|err| Diagnostic::from_view_compile_error(&err))
+        .collect::<Vec<_>>();
+
+    // both kinds of errors are reported in the order they're on the code
+    diagnostics.sort_by_key(|diagnostic| diagnostic.span.start);
+
+    Err(diagnostics)
+}
+
+// parses and compiles a logic, returns every errors found as diagnostics. when there is no view
+// given, the logic is only parsed and `None` is returned
+fn compile_logic(
+    logic: &str,
+    view: Option<&View>,
+) -> Result<Option<LogicCompileResult>, Vec<Diagnostic>> {
+    let (parsed, errors) = compiler::logic::parser::parse_logic_recovering(logic);
+
+    if !errors.is_empty() {
+        return Err(errors
+            .iter()
+            .map(|err| Diagnostic::from_parse_error(err, logic))
+            .collect());
+    }
+
+    let Some(view) = view else { return Ok(None) };
+
+    compiler::logic::compile_logic(parsed, view)
+        .map(Some)
+        .map_err(|errors| {
+            errors
+                .0
+                .iter()
+                .map(Diagnostic::from_logic_compile_error)
+                .collect()
+        })
+}
+
+// turns a view name to a logic name, something like `main` into `MainActivity`,
+// `screen_display` to `ScreenDisplayActivity`
+fn view_name_to_logic(s: &str) -> String {
+    let mut capitalize = true;
+
+    format!(
+        "{}Activity",
+        s.chars()
+            .into_iter()
+            .filter_map(|ch| {
+                Some(if ch == '_' {
+                    capitalize = true;
+                    return None;
+                } else if capitalize {
+                    capitalize = false;
+                    ch.to_ascii_uppercase()
+                } else {
+                    ch
+                })
+            })
+            .collect::<String>()
+    )
+}
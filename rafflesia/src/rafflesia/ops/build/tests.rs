@@ -0,0 +1,427 @@
+use super::{
+    build_project, compile_custom_views, compile_screens, BuildOptions, BuildReporter, BuildStep,
+};
+use crate::compiler::{ResourceKind, ResourceNames};
+use crate::core::manifest::{parse_manifest_str, ResourcesTable, ViewTable};
+use crate::core::resource::{ProjectResources, ResourceError};
+use crate::core::source::MemorySource;
+use crate::diagnostic::{Diagnostics, SourceDiagnostics};
+use swrs::parser::file::{KeyboardSetting, Orientation, Theme};
+
+const MANIFEST: &str = r#"[project]
+name = "Test"
+package = "com.test"
+version-code = 1
+version-name = "1.0"
+time-created = 2022-06-25T08:51:55Z
+sw-ver = 150
+
+[activity.main]
+logic = "main.logic"
+layout = "main.layout"
+
+[activity.other]
+logic = "other.logic"
+layout = "other.layout"
+"#;
+
+#[derive(Default)]
+struct Recorder {
+    started: Option<String>,
+    activities: Vec<String>,
+    views: Vec<String>,
+    files: Vec<String>,
+    finished: Option<bool>,
+}
+
+impl BuildReporter for Recorder {
+    fn started(&mut self, project_name: &str) {
+        self.started = Some(project_name.to_string());
+    }
+
+    fn progress(&mut self, step: BuildStep) {
+        match step {
+            BuildStep::CompilingActivity(name) => self.activities.push(name.to_string()),
+            BuildStep::CompilingCustomView(name) => self.views.push(name.to_string()),
+            _ => {}
+        }
+    }
+
+    fn diagnostics(&mut self, diagnostics: &SourceDiagnostics) {
+        self.files.push(diagnostics.source.name().to_string());
+    }
+
+    fn finished(&mut self, success: bool) {
+        self.finished = Some(success);
+    }
+}
+
+#[test]
+fn report_diagnostics() {
+    let source = MemorySource::new()
+        .with_file("swproj.toml", MANIFEST)
+        .with_file("src/main.layout", "LinearLayout {\n    Button: button1\n}")
+        .with_file("src/main.logic", "onCreate {\n    a = )\n}")
+        .with_file(
+            "src/other.layout",
+            "LinearLayout {\n    Button (text_size: big): button1\n}",
+        )
+        .with_file("src/other.logic", "onCreate {\n}");
+
+    let mut reporter = Recorder::default();
+    let err = build_project(&source, &BuildOptions::default(), &mut reporter).unwrap_err();
+
+    assert_eq!(reporter.started.as_deref(), Some("Test"));
+    assert_eq!(reporter.activities, vec!["main", "other"]);
+    assert_eq!(reporter.files, vec!["src/main.logic", "src/other.layout"]);
+    assert_eq!(reporter.finished, Some(false));
+
+    // the same diagnostics are carried by the error
+    let diagnostics = err
+        .chain()
+        .find_map(|err| err.downcast_ref::<Diagnostics>())
+        .expect("the error doesn't have diagnostics");
+
+    let files = diagnostics
+        .0
+        .iter()
+        .map(|source| source.source.name())
+        .collect::<Vec<_>>();
+
+    assert_eq!(files, vec!["src/main.logic", "src/other.layout"]);
+}
+
+#[test]
+fn missing_file() {
+    let source = MemorySource::new()
+        .with_file("swproj.toml", MANIFEST)
+        .with_file("src/main.layout", "LinearLayout {\n}")
+        .with_file("src/main.logic", "onCreate {\n}");
+
+    let err = build_project(&source, &BuildOptions::default(), &mut ()).unwrap_err();
+    let message = format!("{:#}", err);
+
+    assert!(message.contains("Error while reading layout file of activity other"));
+    assert!(message.contains("file src/other.layout doesn't exist"));
+}
+
+#[test]
+fn manifest_syntax_error() {
+    let source = MemorySource::new().with_file("swproj.toml", "[project\n");
+
+    let mut reporter = Recorder::default();
+    build_project(&source, &BuildOptions::default(), &mut reporter).unwrap_err();
+
+    assert_eq!(reporter.files, vec!["swproj.toml"]);
+    assert_eq!(reporter.started, None);
+    assert_eq!(reporter.finished, Some(false));
+}
+
+#[test]
+fn screen_settings() {
+    let manifest = MANIFEST.replace(
+        "layout = \"main.layout\"\n",
+        "layout = \"main.layout\"\norientation = \"landscape\"\ntheme = \"no-action-bar\"\n\
+         keyboard = \"hidden\"\nfullscreen = true\ntoolbar = true\n",
+    );
+
+    let manifest = parse_manifest_str(&manifest).expect("failed to parse the manifest");
+
+    let source = MemorySource::new()
+        .with_file("src/main.layout", "LinearLayout {\n}")
+        .with_file("src/main.logic", "onCreate {\n}")
+        .with_file("src/other.layout", "LinearLayout {\n}")
+        .with_file("src/other.logic", "onCreate {\n}");
+
+    let screens = compile_screens(
+        &source,
+        manifest.activity,
+        &ResourceNames::default(),
+        &mut (),
+    )
+    .unwrap();
+
+    assert_eq!(screens[0].layout_name, "main");
+    assert_eq!(screens[0].orientation, Orientation::Landscape);
+    assert_eq!(screens[0].theme, Theme::NoActionBar);
+    assert_eq!(screens[0].keyboard_setting, KeyboardSetting::Hidden);
+    assert!(screens[0].fullscreen_enabled);
+    assert!(screens[0].toolbar_enabled);
+    assert!(!screens[0].drawer_enabled);
+
+    // the settings that aren't specified are left as default
+    assert_eq!(screens[1].orientation, Orientation::Portrait);
+    assert_eq!(screens[1].theme, Theme::None);
+    assert!(!screens[1].toolbar_enabled);
+}
+
+#[test]
+fn invalid_screen_settings() {
+    let error = |setting: &str| {
+        let manifest = MANIFEST.replace(
+            "layout = \"main.layout\"\n",
+            &format!("layout = \"main.layout\"\n{}\n", setting),
+        );
+
+        let err = parse_manifest_str(&manifest).unwrap_err();
+        let diagnostics = err.downcast::<Diagnostics>().unwrap();
+        let diagnostic = &diagnostics.0[0].diagnostics[0];
+
+        // the errors point at the activity table
+        let line = manifest[..diagnostic.span.start].lines().count();
+        assert!(
+            line >= 10,
+            "{} doesn't point at the activity",
+            diagnostic.label
+        );
+
+        diagnostic.label.clone()
+    };
+
+    let label = error("orientation = \"sideways\"");
+    assert!(label.contains("unknown variant `sideways`"), "{}", label);
+    assert!(
+        label.contains("`portrait`, `landscape`, `both`"),
+        "{}",
+        label
+    );
+
+    let label = error("orientaton = \"portrait\"");
+    assert!(label.contains("unknown field `orientaton`"), "{}", label);
+
+    let label = error("fab = \"yes\"");
+    assert!(label.contains("expected a boolean"), "{}", label);
+}
+
+fn resource_source() -> MemorySource {
+    MemorySource::new()
+        .with_file("res/images/app_icon.png", [0u8; 4])
+        .with_file("res/images/background.jpg", [0u8; 4])
+        .with_file("res/sounds/click.mp3", [0u8; 4])
+        .with_file("res/fonts/roboto.ttf", [0u8; 4])
+        .with_file("res/fonts/nested/ignored.ttf", [0u8; 4])
+}
+
+#[test]
+fn collect_resources() {
+    let resources = ProjectResources::collect(&resource_source(), None).unwrap();
+
+    let names = resources
+        .0
+        .iter()
+        .map(|res| (res.kind, res.name.as_str(), res.path()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        names,
+        vec![
+            (
+                ResourceKind::Image,
+                "app_icon",
+                "res/images/app_icon.png".to_string()
+            ),
+            (
+                ResourceKind::Image,
+                "background",
+                "res/images/background.jpg".to_string()
+            ),
+            (
+                ResourceKind::Sound,
+                "click",
+                "res/sounds/click.mp3".to_string()
+            ),
+            (
+                ResourceKind::Font,
+                "roboto",
+                "res/fonts/roboto.ttf".to_string()
+            ),
+        ]
+    );
+
+    let swrs = resources.to_swrs();
+    assert_eq!(swrs.images.len(), 2);
+    assert_eq!(swrs.images[0].name, "app_icon");
+    assert_eq!(swrs.images[0].full_name, "app_icon.png");
+    assert_eq!(swrs.sounds.len(), 1);
+    assert_eq!(swrs.fonts.len(), 1);
+
+    let names = resources.names();
+    assert!(names.contains(ResourceKind::Image, "background"));
+    assert!(!names.contains(ResourceKind::Sound, "background"));
+}
+
+#[test]
+fn collect_listed_resources() {
+    let table = ResourcesTable {
+        images: Some(vec!["background.jpg".to_string()]),
+        sounds: Some(vec![]),
+        fonts: None,
+    };
+
+    let resources = ProjectResources::collect(&resource_source(), Some(&table)).unwrap();
+
+    let names = resources
+        .0
+        .iter()
+        .map(|res| res.name.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(names, vec!["background", "roboto"]);
+
+    let table = ResourcesTable {
+        images: Some(vec!["logo.png".to_string()]),
+        sounds: None,
+        fonts: None,
+    };
+
+    let err = ProjectResources::collect(&resource_source(), Some(&table)).unwrap_err();
+    assert!(
+        matches!(&err, ResourceError::Missing { path, .. } if path == "res/images/logo.png"),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn invalid_resources() {
+    let source = MemorySource::new().with_file("res/images/App-Icon.png", [0u8; 4]);
+    let err = ProjectResources::collect(&source, None).unwrap_err();
+    assert!(
+        matches!(&err, ResourceError::InvalidName { name, .. } if name == "App-Icon"),
+        "{:?}",
+        err
+    );
+
+    let source = MemorySource::new()
+        .with_file("res/images/icon.png", [0u8; 4])
+        .with_file("res/images/icon.webp", [0u8; 4]);
+    let err = ProjectResources::collect(&source, None).unwrap_err();
+    assert!(
+        matches!(&err, ResourceError::DuplicateName { name, .. } if name == "icon"),
+        "{:?}",
+        err
+    );
+
+    // resources of different kinds can share the same name
+    let source = MemorySource::new()
+        .with_file("res/images/click.png", [0u8; 4])
+        .with_file("res/sounds/click.mp3", [0u8; 4]);
+    assert!(ProjectResources::collect(&source, None).is_ok());
+}
+
+#[test]
+fn unknown_layout_resource() {
+    let source = resource_source()
+        .with_file("swproj.toml", MANIFEST)
+        .with_file(
+            "src/main.layout",
+            "LinearLayout {\n    ImageView (image: logo): image1\n}",
+        )
+        .with_file("src/main.logic", "onCreate {\n}")
+        .with_file(
+            "src/other.layout",
+            "LinearLayout {\n    ImageView (image: app_icon): image1\n}",
+        )
+        .with_file("src/other.logic", "onCreate {\n}");
+
+    let mut reporter = Recorder::default();
+    let err = build_project(&source, &BuildOptions::default(), &mut reporter).unwrap_err();
+
+    assert_eq!(reporter.files, vec!["src/main.layout"]);
+
+    let diagnostics = err.downcast::<Diagnostics>().unwrap();
+    let diagnostic = &diagnostics.0[0].diagnostics[0];
+    assert_eq!(diagnostic.code, Some("unknown-resource"));
+    assert_eq!(
+        &diagnostics.0[0].source.source()[diagnostic.span.clone()],
+        "logo"
+    );
+}
+
+#[test]
+fn custom_views() {
+    let views = [("item_row", "item_row.layout"), ("dialog", "dialog.layout")]
+        .into_iter()
+        .map(|(name, layout)| {
+            (
+                name.to_string(),
+                ViewTable {
+                    layout: layout.to_string(),
+                },
+            )
+        })
+        .collect();
+
+    let source = MemorySource::new()
+        .with_file(
+            "src/item_row.layout",
+            "LinearLayout {\n    TextView: title\n}",
+        )
+        .with_file("src/dialog.layout", "LinearLayout {\n}");
+
+    let mut reporter = Recorder::default();
+    let custom_views =
+        compile_custom_views(&source, views, &ResourceNames::default(), &mut reporter).unwrap();
+
+    assert_eq!(reporter.views, vec!["dialog", "item_row"]);
+    assert_eq!(custom_views[0].name, "dialog");
+    assert_eq!(custom_views[1].name, "item_row");
+    assert_eq!(custom_views[1].layout.len(), 1);
+}
+
+#[test]
+fn unknown_custom_view() {
+    let manifest = format!(
+        "{}\n[view.item_row]\nlayout = \"item_row.layout\"\n",
+        MANIFEST
+    );
+
+    let source = MemorySource::new()
+        .with_file("swproj.toml", manifest)
+        .with_file(
+            "src/main.layout",
+            "LinearLayout {\n    ListView (custom_view: item_row): list1\n}",
+        )
+        .with_file("src/main.logic", "onCreate {\n}")
+        .with_file(
+            "src/other.layout",
+            "LinearLayout {\n    ListView (custom_view: item): list1\n}",
+        )
+        .with_file("src/other.logic", "onCreate {\n}")
+        .with_file("src/item_row.layout", "LinearLayout (padding: \"-\") {\n}");
+
+    let mut reporter = Recorder::default();
+    let err = build_project(&source, &BuildOptions::default(), &mut reporter).unwrap_err();
+
+    assert_eq!(reporter.views, vec!["item_row"]);
+    assert_eq!(reporter.activities, vec!["main", "other"]);
+
+    // the errors of the custom views and the activities are reported together
+    assert_eq!(
+        reporter.files,
+        vec!["src/item_row.layout", "src/other.layout"]
+    );
+
+    let diagnostics = err.downcast::<Diagnostics>().unwrap();
+    assert_eq!(diagnostics.0.len(), 2);
+
+    let diagnostic = &diagnostics.0[1].diagnostics[0];
+    assert_eq!(diagnostic.code, Some("unknown-custom-view"));
+    assert_eq!(
+        &diagnostics.0[1].source.source()[diagnostic.span.clone()],
+        "item"
+    );
+}
+
+#[test]
+fn custom_view_activity_name() {
+    let manifest = format!("{}\n[view.other]\nlayout = \"other.layout\"\n", MANIFEST);
+    let source = MemorySource::new().with_file("swproj.toml", manifest);
+
+    let err = build_project(&source, &BuildOptions::default(), &mut ()).unwrap_err();
+    assert!(
+        err.to_string().contains("same name as an activity"),
+        "{}",
+        err
+    );
+}
@@ -0,0 +1,69 @@
+use crate::core::project::Project;
+use crate::formatter;
+use anyhow::{bail, Context, Result};
+use console::style;
+use std::fs;
+
+type FormatFn = fn(&str) -> Result<String>;
+
+/// Formats the logic and layout files of every activities and the layout files of every custom
+/// views in the project.
+///
+/// If `check` is true, the files are left untouched and an error will be returned if there are
+/// files that aren't formatted.
+pub fn fmt(check: bool) -> Result<()> {
+    let project = Project::find_project()?;
+    let src_folder = project.working_directory.join("src");
+
+    let mut activities = project.manifest.activity.into_iter().collect::<Vec<_>>();
+    activities.sort_by(|(first, _), (second, _)| first.cmp(second));
+
+    let mut views = project.manifest.view.into_iter().collect::<Vec<_>>();
+    views.sort_by(|(first, _), (second, _)| first.cmp(second));
+
+    let format_layout: FormatFn = |source| Ok(formatter::format_layout(source)?);
+    let format_logic: FormatFn = |source| Ok(formatter::format_logic(source)?);
+
+    let mut files = Vec::new();
+
+    for (_, activity) in activities {
+        files.push((activity.layout, format_layout));
+        files.push((activity.logic, format_logic));
+    }
+
+    for (_, view) in views {
+        files.push((view.layout, format_layout));
+    }
+
+    let mut unformatted = Vec::new();
+
+    for (file, format) in files {
+        let source = fs::read_to_string(src_folder.join(&file))
+            .context(format!("Failed to read {}", file))?;
+
+        let formatted = format(&source).context(format!("Syntax error on {}", file))?;
+
+        if formatted == source {
+            continue;
+        }
+
+        if check {
+            println!("{} {}", style("Unformatted").yellow(), file);
+            unformatted.push(file);
+        } else {
+            fs::write(src_folder.join(&file), formatted)
+                .context(format!("Failed to write {}", file))?;
+
+            println!("{} {}", style("Formatted").green(), file);
+        }
+    }
+
+    if !unformatted.is_empty() {
+        bail!(
+            "{} file(s) aren't formatted, run `rafflesia fmt` to format them",
+            unformatted.len()
+        );
+    }
+
+    Ok(())
+}
@@ -1 +1,201 @@
+use crate::compiler::logic::LogicCompileResult;
+use crate::compiler::ResourceKind;
+use crate::core::manifest::Manifest;
+use crate::generator;
+use anyhow::{anyhow, bail, Context, Result};
+use console::style;
+use std::fs;
+use std::path::Path;
+use swrs::api::view::View;
+use swrs::api::{Resources, SketchwareProject};
+use swrs::parser::RawSketchwareProject;
 
+/// Generates a rafflesia project out of a sketchware project, the reverse of `ops::build`.
+///
+/// `project_path` is a folder that contains the encrypted sketchware project files (`project`,
+/// `file`, `logic`, `view`, `resource` and `library`) and the resource files inside
+/// `resources/`, just like the `build/` folder that `ops::build` writes to. The generated
+/// project will be written on `output`, or a folder named after the project's workspace name if
+/// not specified.
+///
+/// Anything that can't be generated is skipped and reported instead of failing the whole project.
+pub fn generate(project_path: &Path, output: Option<&Path>) -> Result<()> {
+    let raw = RawSketchwareProject {
+        project: read_sw_file(project_path, "project")?,
+        file: read_sw_file(project_path, "file")?,
+        logic: read_sw_file(project_path, "logic")?,
+        view: read_sw_file(project_path, "view")?,
+        library: read_sw_file(project_path, "library")?,
+        resource: read_sw_file(project_path, "resource")?,
+    };
+
+    let sw_proj =
+        SketchwareProject::try_from(raw).context("Error while parsing the sketchware project")?;
+
+    println!("\n## Project Info ##");
+    println!("# Local ID: {}", sw_proj.metadata.local_id);
+    println!("# Name: {}", style(&sw_proj.metadata.name).bold().cyan());
+    println!("# Package name: {}", sw_proj.metadata.package_name);
+    println!("# Version code: {}", sw_proj.metadata.version_code);
+    println!("# Version name: {}", sw_proj.metadata.version_name);
+    println!();
+
+    let output = output
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| Path::new(&sw_proj.metadata.workspace_name).to_path_buf());
+
+    if output.join("swproj.toml").exists() {
+        bail!("A rafflesia project already exists at {}", output.display());
+    }
+
+    let manifest = toml::to_string(&Manifest::from(&sw_proj))
+        .context("Error while serializing the manifest")?;
+
+    // everything is generated before anything gets written, so a project that fails to generate
+    // doesn't leave a half-written project behind
+    let mut files = Vec::new();
+    let mut skipped_count = 0;
+
+    for screen in sw_proj.screens {
+        match generate_layout(&screen.layout) {
+            Ok(layout) => files.push((format!("{}.layout", screen.layout_name), layout)),
+            Err(err) => {
+                skipped_count += 1;
+                println!(
+                    "{} the layout of {}: {:#}",
+                    style("Skipped").yellow(),
+                    screen.layout_name,
+                    err
+                );
+            }
+        }
+
+        let logic = generator::logic::generate_logic(&LogicCompileResult {
+            variables: screen.variables,
+            list_variables: screen.list_variables,
+            more_blocks: screen.more_blocks,
+            components: screen.components,
+            events: screen.events,
+        });
+
+        // the rest of the screen is still generated, but what's skipped will be lost on a build
+        skipped_count += logic.skipped.len();
+
+        for skipped in &logic.skipped {
+            println!(
+                "{} {} on {}",
+                style("Skipped").yellow(),
+                skipped,
+                screen.layout_name
+            );
+        }
+
+        files.push((
+            format!("{}.logic", screen.layout_name),
+            generator::logic::print_logic(&logic.statements),
+        ));
+    }
+
+    for custom_view in sw_proj.custom_views {
+        match generate_layout(&custom_view.layout) {
+            Ok(layout) => files.push((format!("{}.layout", custom_view.name), layout)),
+            Err(err) => {
+                skipped_count += 1;
+                println!(
+                    "{} the layout of {}: {:#}",
+                    style("Skipped").yellow(),
+                    custom_view.name,
+                    err
+                );
+            }
+        }
+    }
+
+    let src_folder = output.join("src");
+    fs::create_dir_all(&src_folder).context("Failed to create the src folder")?;
+
+    for (name, content) in files {
+        fs::write(src_folder.join(&name), content).context(format!("Failed to write {}", name))?;
+    }
+
+    copy_resources(project_path, &output, &sw_proj.resources)?;
+
+    // written last, an existing swproj.toml means the project has been completely generated
+    fs::write(output.join("swproj.toml"), manifest).context("Failed to write swproj.toml")?;
+
+    println!("## Generated into {}/ ##\n", output.display());
+
+    if skipped_count > 0 {
+        println!(
+            "{} {} item(s) couldn't be generated and were skipped, they're missing from the \
+            generated project and will be lost when it's built",
+            style("Warning:").bold().yellow(),
+            skipped_count
+        );
+    }
+
+    Ok(())
+}
+
+// generates the source code of a layout
+fn generate_layout(layout: &[View]) -> Result<String> {
+    // rafflesia layouts can only have one root view
+    let root = match layout {
+        [root] => root,
+        _ => bail!(
+            "a layout must have exactly one root view, found {}",
+            layout.len()
+        ),
+    };
+
+    let layout = generator::layout::generate_layout(root)?;
+
+    Ok(generator::layout::print_layout(&layout))
+}
+
+// copies the resource files inside the `resources` folder of the project (see
+// `ops::build::write_resources`) into `res/`, they're listed on the manifest
+fn copy_resources(project_path: &Path, output: &Path, resources: &Resources) -> Result<()> {
+    let kinds = [
+        (ResourceKind::Image, &resources.images),
+        (ResourceKind::Sound, &resources.sounds),
+        (ResourceKind::Font, &resources.fonts),
+    ];
+
+    for (kind, items) in kinds {
+        if items.is_empty() {
+            continue;
+        }
+
+        let folder = output.join("res").join(kind.folder());
+        fs::create_dir_all(&folder).context(format!("Failed to create {}", folder.display()))?;
+
+        for item in items {
+            let path = project_path
+                .join("resources")
+                .join(kind.folder())
+                .join(&item.full_name);
+
+            fs::copy(&path, folder.join(&item.full_name)).context(format!(
+                "Failed to copy {} {}",
+                kind,
+                path.display()
+            ))?;
+        }
+    }
+
+    Ok(())
+}
+
+// reads and decrypts a sketchware project file
+fn read_sw_file(project_path: &Path, name: &str) -> Result<String> {
+    let content = fs::read(project_path.join(name)).context(format!(
+        "Failed to read {}",
+        project_path.join(name).display()
+    ))?;
+
+    let decrypted = swrs::decrypt_sw(&content)
+        .map_err(|_| anyhow!("Failed to decrypt {}", project_path.join(name).display()))?;
+
+    String::from_utf8(decrypted).context(format!("{} is not a valid utf-8 file", name))
+}
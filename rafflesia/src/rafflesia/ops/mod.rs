@@ -1,4 +1,5 @@
 pub mod build;
 pub mod clean;
+pub mod fmt;
 pub mod generate;
 pub mod new;